@@ -0,0 +1,140 @@
+use crate::{
+    error::Result,
+    hash,
+    sync::{self, CommitId, FileBlame},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+///
+#[derive(Debug, Hash, Clone, PartialEq)]
+pub struct BlameParams {
+    /// path to the file to blame
+    pub file_path: String,
+    /// commit to blame the file at, `HEAD` if `None`
+    pub commit_id: Option<CommitId>,
+}
+
+type LastResult = Option<(BlameParams, FileBlame)>;
+
+///
+pub struct AsyncBlame {
+    current: Arc<Mutex<u64>>,
+    last: Arc<Mutex<LastResult>>,
+    last_error: Arc<Mutex<Option<String>>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncBlame {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            current: Arc::new(Mutex::new(0)),
+            last: Arc::new(Mutex::new(None)),
+            last_error: Arc::new(Mutex::new(None)),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// last blame that finished successfully
+    pub fn last(&self) -> Result<LastResult> {
+        let last = self.last.lock()?;
+        Ok(last.clone())
+    }
+
+    /// error of the last request, if it failed
+    pub fn last_error(&self) -> Result<Option<String>> {
+        let last = self.last_error.lock()?;
+        Ok(last.clone())
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    /// starts blaming in the background unless `params` are
+    /// already requested, results of older requests get dropped
+    pub fn request(&mut self, params: BlameParams) -> Result<()> {
+        log::trace!("request: {:?}", params);
+
+        let hash = hash(&params);
+
+        {
+            let mut current = self.current.lock()?;
+            if *current == hash {
+                return Ok(());
+            }
+            *current = hash;
+        }
+
+        let arc_current = Arc::clone(&self.current);
+        let arc_last = Arc::clone(&self.last);
+        let arc_error = Arc::clone(&self.last_error);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            let notify = Self::blame_helper(
+                params,
+                hash,
+                &arc_current,
+                &arc_last,
+                &arc_error,
+            )
+            .expect("error blaming file");
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(if notify {
+                    AsyncNotification::Blame
+                } else {
+                    AsyncNotification::FinishUnchanged
+                })
+                .expect("error sending blame");
+        });
+
+        Ok(())
+    }
+
+    fn blame_helper(
+        params: BlameParams,
+        hash: u64,
+        arc_current: &Arc<Mutex<u64>>,
+        arc_last: &Arc<Mutex<LastResult>>,
+        arc_error: &Arc<Mutex<Option<String>>>,
+    ) -> Result<bool> {
+        let res = sync::blame_file(
+            CWD,
+            &params.file_path,
+            params.commit_id,
+        );
+
+        if *arc_current.lock()? != hash {
+            return Ok(false);
+        }
+
+        match res {
+            Ok(blame) => {
+                *arc_error.lock()? = None;
+                *arc_last.lock()? = Some((params, blame));
+            }
+            Err(e) => {
+                *arc_error.lock()? = Some(e.to_string());
+                // allows to request the same params again
+                *arc_current.lock()? = 0;
+            }
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{
+    error::Result,
+    sync::{self, BranchStatus, CommitId, RepoState},
+};
+use sync::Head;
+
+/// everything `BranchStatus` depends on,
+/// cheap to compare to the last lookup
+#[derive(PartialEq)]
+struct Key {
+    head: Head,
+    upstream: Option<(String, CommitId)>,
+    state: RepoState,
+}
+
+/// caches `sync::get_branch_status` as long as HEAD,
+/// its upstream and the repo state stay the same
+pub struct BranchState {
+    last_result: Option<(Key, BranchStatus)>,
+    repo_path: String,
+}
+
+impl BranchState {
+    ///
+    pub fn new(path: &str) -> Self {
+        Self {
+            repo_path: path.to_string(),
+            last_result: None,
+        }
+    }
+
+    ///
+    pub fn lookup(&mut self) -> Result<BranchStatus> {
+        let key = Key {
+            head: sync::get_head_tuple(self.repo_path.as_str())?,
+            upstream: sync::get_head_upstream(
+                self.repo_path.as_str(),
+            )?,
+            state: sync::repo_state(self.repo_path.as_str())?,
+        };
+
+        if let Some((last_key, status)) = self.last_result.as_ref() {
+            if *last_key == key {
+                return Ok(status.clone());
+            }
+        }
+
+        self.fetch(key)
+    }
+
+    fn fetch(&mut self, key: Key) -> Result<BranchStatus> {
+        let status =
+            sync::get_branch_status(self.repo_path.as_str())?;
+        self.last_result = Some((key, status.clone()));
+        Ok(status)
+    }
+}
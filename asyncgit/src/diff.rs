@@ -1,7 +1,7 @@
 use crate::{
     error::Result,
     hash,
-    sync::{self, CommitId},
+    sync::{self, CommitId, DiffOptions},
     AsyncNotification, FileDiff, CWD,
 };
 use crossbeam_channel::Sender;
@@ -31,6 +31,8 @@ pub struct DiffParams {
     pub path: String,
     /// what kind of diff
     pub diff_type: DiffType,
+    /// how to compute it
+    pub options: DiffOptions,
 }
 
 struct Request<R, A>(R, Option<A>);
@@ -144,19 +146,29 @@ impl AsyncDiff {
         hash: u64,
     ) -> Result<bool> {
         let res = match params.diff_type {
-            DiffType::Stage => {
-                sync::diff::get_diff(CWD, params.path.clone(), true)?
-            }
-            DiffType::WorkDir => {
-                sync::diff::get_diff(CWD, params.path.clone(), false)?
-            }
+            DiffType::Stage => sync::diff::get_diff(
+                CWD,
+                params.path.clone(),
+                true,
+                Some(params.options),
+            )?,
+            DiffType::WorkDir => sync::diff::get_diff(
+                CWD,
+                params.path.clone(),
+                false,
+                Some(params.options),
+            )?,
             DiffType::Commit(id) => sync::diff::get_diff_commit(
                 CWD,
                 id,
                 params.path.clone(),
+                Some(params.options),
             )?,
         };
 
+        #[cfg(feature = "syntax")]
+        let res = crate::syntax::highlight_diff(res, &params.path);
+
         let mut notify = false;
         {
             let mut current = arc_current.lock()?;
@@ -0,0 +1,165 @@
+use crate::{
+    error::Result,
+    sync::{self, RemoteProgress},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+/// progress of the currently running fetch
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FetchProgress {
+    /// remote that is currently fetched
+    pub remote: String,
+    /// transfer progress of `remote`
+    pub progress: RemoteProgress,
+}
+
+///
+#[derive(Default)]
+struct FetchState {
+    progress: Option<FetchProgress>,
+    last_error: Option<String>,
+}
+
+///
+pub struct AsyncFetch {
+    state: Arc<Mutex<FetchState>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncFetch {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(FetchState::default())),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    /// progress of the running fetch (`None` if nothing is running)
+    pub fn progress(&self) -> Result<Option<FetchProgress>> {
+        let state = self.state.lock()?;
+        Ok(state.progress.clone())
+    }
+
+    /// error of the last finished fetch (`None` if it succeeded)
+    pub fn last_error(&self) -> Result<Option<String>> {
+        let state = self.state.lock()?;
+        Ok(state.last_error.clone())
+    }
+
+    /// fetch `remote` or all configured remotes if `None`
+    pub fn request(&mut self, remote: Option<String>) -> Result<()> {
+        log::trace!("request: {:?}", remote);
+
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        {
+            let mut state = self.state.lock()?;
+            *state = FetchState::default();
+        }
+
+        let arc_state = Arc::clone(&self.state);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            let res = Self::fetch_helper(remote, &arc_state, &sender);
+
+            if let Err(e) = &res {
+                log::error!("fetch: {}", e);
+            }
+
+            Self::finish(&arc_state, res)
+                .expect("failed to store fetch result");
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::Fetch)
+                .expect("error sending fetch");
+        });
+
+        Ok(())
+    }
+
+    fn fetch_helper(
+        remote: Option<String>,
+        arc_state: &Arc<Mutex<FetchState>>,
+        sender: &Sender<AsyncNotification>,
+    ) -> Result<()> {
+        let remotes = if let Some(remote) = remote {
+            vec![remote]
+        } else {
+            sync::get_remotes(CWD)?
+        };
+
+        for remote in remotes {
+            Self::set_progress(
+                arc_state,
+                &remote,
+                RemoteProgress::default(),
+            )?;
+
+            let mut last_percent = None;
+
+            sync::fetch(CWD, &remote, |progress| {
+                // only notify about visible changes to not flood the ui
+                let percent = Some(progress.percent());
+                if percent != last_percent {
+                    last_percent = percent;
+
+                    if Self::set_progress(
+                        arc_state, &remote, progress,
+                    )
+                    .is_ok()
+                    {
+                        sender
+                            .send(AsyncNotification::Fetch)
+                            .expect("error sending fetch");
+                    }
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn set_progress(
+        arc_state: &Arc<Mutex<FetchState>>,
+        remote: &str,
+        progress: RemoteProgress,
+    ) -> Result<()> {
+        let mut state = arc_state.lock()?;
+        state.progress = Some(FetchProgress {
+            remote: remote.to_string(),
+            progress,
+        });
+        Ok(())
+    }
+
+    fn finish(
+        arc_state: &Arc<Mutex<FetchState>>,
+        res: Result<()>,
+    ) -> Result<()> {
+        let mut state = arc_state.lock()?;
+        state.progress = None;
+        state.last_error = res.err().map(|e| e.to_string());
+        Ok(())
+    }
+}
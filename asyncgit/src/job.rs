@@ -0,0 +1,102 @@
+use crate::{error::Result, AsyncNotification, CWD};
+use crossbeam_channel::Sender;
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+type JobOutcome<R> = std::result::Result<R, String>;
+
+/// runs a single git operation (like push or pull) in the background,
+/// `notification` is sent once it finished
+pub struct AsyncJob<R> {
+    last_result: Arc<Mutex<Option<JobOutcome<R>>>>,
+    notification: AsyncNotification,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl<R: Clone + Debug + Send + 'static> AsyncJob<R> {
+    ///
+    pub fn new(
+        sender: &Sender<AsyncNotification>,
+        notification: AsyncNotification,
+    ) -> Self {
+        Self {
+            last_result: Arc::new(Mutex::new(None)),
+            notification,
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    /// error of the last finished job (`None` if it succeeded)
+    pub fn last_error(&self) -> Result<Option<String>> {
+        let last = self.last_result.lock()?;
+        Ok(last.clone().and_then(|res| res.err()))
+    }
+
+    /// result of the last finished job (`None` if it failed)
+    pub fn last_result(&self) -> Result<Option<R>> {
+        let last = self.last_result.lock()?;
+        Ok(last.clone().and_then(|res| res.ok()))
+    }
+
+    /// run `job` on the repository unless one is still pending
+    pub fn request(
+        &mut self,
+        job: fn(&str) -> Result<R>,
+    ) -> Result<()> {
+        log::trace!("request: {:?}", self.notification);
+
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        self.last_result.lock()?.take();
+
+        let arc_last = Arc::clone(&self.last_result);
+        let notification = self.notification;
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            let res = job(CWD);
+
+            match &res {
+                Ok(result) => {
+                    log::trace!("{:?}: {:?}", notification, result)
+                }
+                Err(e) => log::error!("{:?}: {}", notification, e),
+            }
+
+            Self::set_result(&arc_last, res)
+                .expect("failed to store job result");
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender.send(notification).expect("error sending");
+        });
+
+        Ok(())
+    }
+
+    fn set_result(
+        arc_last: &Arc<Mutex<Option<JobOutcome<R>>>>,
+        res: Result<R>,
+    ) -> Result<()> {
+        let mut last = arc_last.lock()?;
+        *last = Some(res.map_err(|e| e.to_string()));
+        Ok(())
+    }
+}
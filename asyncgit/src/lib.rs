@@ -7,25 +7,35 @@
 #![deny(clippy::panic)]
 #![deny(clippy::perf)]
 
+mod blame;
 pub mod cached;
 mod commit_files;
 mod diff;
 mod error;
+mod fetch;
+mod job;
+mod refs;
 mod revlog;
+mod signature;
 mod status;
 pub mod sync;
-mod tags;
+#[cfg(feature = "syntax")]
+mod syntax;
 
 pub use crate::{
+    blame::{AsyncBlame, BlameParams},
     commit_files::AsyncCommitFiles,
     diff::{AsyncDiff, DiffParams, DiffType},
+    fetch::{AsyncFetch, FetchProgress},
+    job::AsyncJob,
+    refs::AsyncRefs,
     revlog::{AsyncLog, FetchStatus},
+    signature::AsyncSignature,
     status::{AsyncStatus, StatusParams},
     sync::{
-        diff::{DiffLine, DiffLineType, FileDiff},
+        diff::{DiffLine, DiffLineType, FileDiff, SyntaxSpan},
         status::{StatusItem, StatusItemType},
     },
-    tags::AsyncTags,
 };
 use std::{
     collections::hash_map::DefaultHasher,
@@ -46,7 +56,17 @@ pub enum AsyncNotification {
     ///
     CommitFiles,
     ///
-    Tags,
+    Refs,
+    ///
+    Push,
+    ///
+    Fetch,
+    ///
+    Pull,
+    ///
+    Blame,
+    ///
+    Signature,
 }
 
 /// current working director `./`
@@ -12,23 +12,23 @@ use std::{
     },
     time::{Duration, Instant},
 };
-use sync::Tags;
+use sync::Refs;
 
 ///
 #[derive(Default, Clone)]
-struct TagsResult {
+struct RefsResult {
     hash: u64,
-    tags: Tags,
+    refs: Refs,
 }
 
-///
-pub struct AsyncTags {
-    last: Arc<Mutex<Option<(Instant, TagsResult)>>>,
+/// fetches the refs decorating commits in the log
+pub struct AsyncRefs {
+    last: Arc<Mutex<Option<(Instant, RefsResult)>>>,
     sender: Sender<AsyncNotification>,
     pending: Arc<AtomicUsize>,
 }
 
-impl AsyncTags {
+impl AsyncRefs {
     ///
     pub fn new(sender: &Sender<AsyncNotification>) -> Self {
         Self {
@@ -39,10 +39,10 @@ impl AsyncTags {
     }
 
     /// last fetched result
-    pub fn last(&mut self) -> Result<Option<Tags>> {
+    pub fn last(&mut self) -> Result<Option<Refs>> {
         let last = self.last.lock()?;
 
-        Ok(last.clone().map(|last| last.1.tags))
+        Ok(last.clone().map(|last| last.1.refs))
     }
 
     ///
@@ -78,14 +78,14 @@ impl AsyncTags {
         self.pending.fetch_add(1, Ordering::Relaxed);
 
         rayon_core::spawn(move || {
-            let notify = AsyncTags::getter(arc_last)
-                .expect("error getting tags");
+            let notify = AsyncRefs::getter(arc_last)
+                .expect("error getting refs");
 
             arc_pending.fetch_sub(1, Ordering::Relaxed);
 
             sender
                 .send(if notify {
-                    AsyncNotification::Tags
+                    AsyncNotification::Refs
                 } else {
                     AsyncNotification::FinishUnchanged
                 })
@@ -96,11 +96,11 @@ impl AsyncTags {
     }
 
     fn getter(
-        arc_last: Arc<Mutex<Option<(Instant, TagsResult)>>>,
+        arc_last: Arc<Mutex<Option<(Instant, RefsResult)>>>,
     ) -> Result<bool> {
-        let tags = sync::get_tags(CWD)?;
+        let refs = sync::get_refs(CWD)?;
 
-        let hash = hash(&tags);
+        let hash = hash(&refs);
 
         if Self::last_hash(arc_last.clone())
             .map(|last| last == hash)
@@ -112,14 +112,14 @@ impl AsyncTags {
         {
             let mut last = arc_last.lock()?;
             let now = Instant::now();
-            *last = Some((now, TagsResult { tags, hash }));
+            *last = Some((now, RefsResult { refs, hash }));
         }
 
         Ok(true)
     }
 
     fn last_hash(
-        last: Arc<Mutex<Option<(Instant, TagsResult)>>>,
+        last: Arc<Mutex<Option<(Instant, RefsResult)>>>,
     ) -> Option<u64> {
         last.lock()
             .ok()
@@ -1,10 +1,12 @@
 use crate::{
     error::Result,
-    sync::{utils::repo, CommitId, LogWalker},
+    sync::{
+        utils::repo, with_parents, CommitGraph, CommitId, CommitInfo,
+        GraphRow, LogOptions, LogSearch, LogWalker, Pickaxe,
+    },
     AsyncNotification, CWD,
 };
 use crossbeam_channel::Sender;
-use git2::Oid;
 use scopetime::scope_time;
 use std::{
     iter::FromIterator,
@@ -30,12 +32,21 @@ pub enum FetchStatus {
 ///
 pub struct AsyncLog {
     current: Arc<Mutex<Vec<CommitId>>>,
+    current_head: Option<CommitId>,
+    file: Option<String>,
+    search: Option<LogSearch>,
+    pickaxe: Option<Pickaxe>,
+    options: LogOptions,
+    renames: Arc<Mutex<Vec<(usize, String)>>>,
+    graph: Arc<Mutex<CommitGraph>>,
     sender: Sender<AsyncNotification>,
     pending: Arc<AtomicBool>,
     background: Arc<AtomicBool>,
+    canceled: Arc<AtomicBool>,
 }
 
 static LIMIT_COUNT: usize = 3000;
+static PICKAXE_LIMIT_COUNT: usize = 100;
 static SLEEP_FOREGROUND: Duration = Duration::from_millis(2);
 static SLEEP_BACKGROUND: Duration = Duration::from_millis(1000);
 
@@ -44,12 +55,90 @@ impl AsyncLog {
     pub fn new(sender: &Sender<AsyncNotification>) -> Self {
         Self {
             current: Arc::new(Mutex::new(Vec::new())),
+            current_head: None,
+            file: None,
+            search: None,
+            pickaxe: None,
+            options: LogOptions::default(),
+            renames: Arc::new(Mutex::new(Vec::new())),
+            graph: Arc::new(Mutex::new(CommitGraph::default())),
             sender: sender.clone(),
             pending: Arc::new(AtomicBool::new(false)),
             background: Arc::new(AtomicBool::new(false)),
+            canceled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// log of the commits matching `search`
+    pub fn with_search(
+        sender: &Sender<AsyncNotification>,
+        search: LogSearch,
+    ) -> Self {
+        let mut log = Self::new(sender);
+        log.search = Some(search);
+        log
+    }
+
+    /// log of the commits whose diff matches `pickaxe`, the
+    /// walk stops as soon as this gets dropped
+    pub fn with_pickaxe(
+        sender: &Sender<AsyncNotification>,
+        pickaxe: Pickaxe,
+    ) -> Self {
+        let mut log = Self::new(sender);
+        log.pickaxe = Some(pickaxe);
+        log
+    }
+
+    /// log of the commits changing `file_path`, following renames
+    pub fn with_file(
+        sender: &Sender<AsyncNotification>,
+        file_path: &str,
+    ) -> Self {
+        let mut log = Self::new(sender);
+        log.file = Some(file_path.to_string());
+        log
+    }
+
+    /// path of the file in the commit at `index` of a file log
+    pub fn file_path_at(
+        &self,
+        index: usize,
+    ) -> Result<Option<String>> {
+        let renames = self.renames.lock()?;
+
+        Ok(self.file.as_ref().map(|file| {
+            renames
+                .iter()
+                .take_while(|(pos, _)| *pos <= index)
+                .last()
+                .map_or_else(|| file.clone(), |(_, old)| old.clone())
+        }))
+    }
+
+    /// changes scope, filters or order of the log, restarting
+    /// the walk if they differ
+    pub fn set_options(&mut self, options: LogOptions) {
+        if options != self.options {
+            self.options = options;
+            self.restart();
         }
     }
 
+    /// drops the commits walked so far, the next `fetch` walks
+    /// the log again
+    pub fn restart(&mut self) {
+        // a running walk keeps filling its own list until it
+        // notices the cancel
+        self.canceled.store(true, Ordering::Relaxed);
+        self.canceled = Arc::new(AtomicBool::new(false));
+        self.pending = Arc::new(AtomicBool::new(false));
+        self.current = Arc::new(Mutex::new(Vec::new()));
+        self.renames = Arc::new(Mutex::new(Vec::new()));
+        self.graph = Arc::new(Mutex::new(CommitGraph::default()));
+        self.current_head = None;
+    }
+
     ///
     pub fn count(&mut self) -> Result<usize> {
         Ok(self.current.lock()?.len())
@@ -69,6 +158,31 @@ impl AsyncLog {
         Ok(Vec::from_iter(list[min..max].iter().cloned()))
     }
 
+    /// only a walk of all commits following all parents
+    /// connects them to a graph
+    fn has_graph(&self) -> bool {
+        self.file.is_none()
+            && self.search.is_none()
+            && self.pickaxe.is_none()
+            && !self.options.first_parent
+            && !self.options.no_merges
+    }
+
+    /// graph of `commits` which are the entries from `start` on,
+    /// `start` has to be a `CommitGraph::slice_start`. `None` if the
+    /// log has no graph
+    pub fn graph_rows(
+        &self,
+        start: usize,
+        commits: &[CommitInfo],
+    ) -> Result<Option<Vec<GraphRow>>> {
+        if !self.has_graph() {
+            return Ok(None);
+        }
+
+        Ok(self.graph.lock()?.rows(start, commits))
+    }
+
     ///
     pub fn is_pending(&self) -> bool {
         self.pending.load(Ordering::Relaxed)
@@ -80,20 +194,19 @@ impl AsyncLog {
     }
 
     ///
-    fn current_head(&self) -> Result<CommitId> {
-        Ok(self
-            .current
-            .lock()?
-            .first()
-            .map_or(Oid::zero().into(), |f| *f))
+    fn head() -> Result<Option<CommitId>> {
+        Ok(repo(CWD)?
+            .head()
+            .ok()
+            .and_then(|head| head.target())
+            .map(CommitId::from))
     }
 
-    ///
+    /// compares against the head of the last walk, a file log
+    /// does not necessarily start with it
     fn head_changed(&self) -> Result<bool> {
-        if let Ok(head) = repo(CWD)?.head() {
-            if let Some(head) = head.target() {
-                return Ok(head != self.current_head()?.into());
-            }
+        if let Some(head) = Self::head()? {
+            return Ok(Some(head) != self.current_head);
         }
         Ok(false)
     }
@@ -111,11 +224,23 @@ impl AsyncLog {
         }
 
         self.clear()?;
+        self.current_head = Self::head()?;
 
+        let file = self.file.clone();
+        let search = self.search.clone();
+        let pickaxe = self.pickaxe.clone();
+        let options = self.options.clone();
         let arc_current = Arc::clone(&self.current);
+        let arc_renames = Arc::clone(&self.renames);
+        let arc_graph = if self.has_graph() {
+            Some(Arc::clone(&self.graph))
+        } else {
+            None
+        };
         let sender = self.sender.clone();
         let arc_pending = Arc::clone(&self.pending);
         let arc_background = Arc::clone(&self.background);
+        let arc_canceled = Arc::clone(&self.canceled);
 
         self.pending.store(true, Ordering::Relaxed);
 
@@ -123,8 +248,14 @@ impl AsyncLog {
             scope_time!("async::revlog");
 
             AsyncLog::fetch_helper(
-                arc_current,
-                arc_background,
+                file.as_deref(),
+                search,
+                pickaxe,
+                options,
+                &arc_current,
+                &arc_renames,
+                arc_graph.as_deref(),
+                (&arc_background, &arc_canceled),
                 &sender,
             )
             .expect("failed to fetch");
@@ -137,25 +268,59 @@ impl AsyncLog {
         Ok(FetchStatus::Started)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn fetch_helper(
-        arc_current: Arc<Mutex<Vec<CommitId>>>,
-        arc_background: Arc<AtomicBool>,
+        file: Option<&str>,
+        search: Option<LogSearch>,
+        pickaxe: Option<Pickaxe>,
+        options: LogOptions,
+        arc_current: &Arc<Mutex<Vec<CommitId>>>,
+        arc_renames: &Arc<Mutex<Vec<(usize, String)>>>,
+        graph: Option<&Mutex<CommitGraph>>,
+        (arc_background, arc_canceled): (
+            &Arc<AtomicBool>,
+            &Arc<AtomicBool>,
+        ),
         sender: &Sender<AsyncNotification>,
     ) -> Result<()> {
         let mut entries = Vec::with_capacity(LIMIT_COUNT);
         let r = repo(CWD)?;
-        let mut walker = LogWalker::new(&r);
+        let mut walker = file.map_or_else(
+            || LogWalker::new(&r),
+            |file| LogWalker::new_file(&r, file),
+        );
+        walker.set_options(options);
+        walker.set_search(search);
+
+        // diffing every commit is slow, smaller batches show
+        // results earlier and notice a cancel sooner
+        let limit = if pickaxe.is_some() {
+            PICKAXE_LIMIT_COUNT
+        } else {
+            LIMIT_COUNT
+        };
+        walker.set_pickaxe(pickaxe);
+
         loop {
             entries.clear();
-            let res_is_err =
-                walker.read(&mut entries, LIMIT_COUNT).is_err();
+            let res = walker.read(&mut entries, limit);
+
+            if res.is_ok() {
+                // the lanes have to cover every listed commit,
+                // parents are looked up before locking the graph
+                if let Some(graph) = graph {
+                    let commits = with_parents(&r, &entries)?;
+                    graph.lock()?.extend(&commits);
+                }
 
-            if !res_is_err {
                 let mut current = arc_current.lock()?;
                 current.extend(entries.iter());
+                *arc_renames.lock()? = walker.renames().to_vec();
             }
 
-            if res_is_err || entries.len() <= 1 {
+            if res.map_or(true, |walked| walked <= 1)
+                || arc_canceled.load(Ordering::Relaxed)
+            {
                 break;
             } else {
                 Self::notify(&sender);
@@ -175,6 +340,8 @@ impl AsyncLog {
 
     fn clear(&mut self) -> Result<()> {
         self.current.lock()?.clear();
+        self.renames.lock()?.clear();
+        self.graph.lock()?.clear();
         Ok(())
     }
 
@@ -182,3 +349,10 @@ impl AsyncLog {
         sender.send(AsyncNotification::Log).expect("error sending");
     }
 }
+
+impl Drop for AsyncLog {
+    fn drop(&mut self) {
+        // stops a walk nobody is waiting for anymore
+        self.canceled.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{
+    error::Result,
+    sync::{self, CommitId, SignatureStatus},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+type ResultType = SignatureStatus;
+struct Request<R, A>(R, A);
+
+/// verifies commit signatures in the background,
+/// the check runs gpg or ssh-keygen
+pub struct AsyncSignature {
+    current: Arc<Mutex<Option<Request<CommitId, ResultType>>>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncSignature {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            current: Arc::new(Mutex::new(None)),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    ///
+    pub fn current(&self) -> Result<Option<(CommitId, ResultType)>> {
+        let c = self.current.lock()?;
+
+        if let Some(c) = c.as_ref() {
+            Ok(Some((c.0, c.1.clone())))
+        } else {
+            Ok(None)
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    ///
+    pub fn fetch(&mut self, id: CommitId) -> Result<()> {
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        log::trace!("request: {}", id.to_string());
+
+        {
+            let current = self.current.lock()?;
+            if let Some(c) = &*current {
+                if c.0 == id {
+                    return Ok(());
+                }
+            }
+        }
+
+        let arc_current = Arc::clone(&self.current);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            Self::fetch_helper(id, arc_current)
+                .expect("failed to fetch");
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::Signature)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+
+    fn fetch_helper(
+        id: CommitId,
+        arc_current: Arc<
+            Mutex<Option<Request<CommitId, ResultType>>>,
+        >,
+    ) -> Result<()> {
+        // a commit that can not be checked is reported like
+        // one signed with an unknown key
+        let res = sync::verify_commit(CWD, id)
+            .unwrap_or(SignatureStatus::UnknownKey);
+
+        log::trace!("verify_commit: {} ({:?})", id.to_string(), res);
+
+        {
+            let mut current = arc_current.lock()?;
+            *current = Some(Request(id, res));
+        }
+
+        Ok(())
+    }
+}
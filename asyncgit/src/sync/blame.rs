@@ -0,0 +1,193 @@
+//! sync git api for blaming files
+
+use super::{utils, CommitId};
+use crate::error::{Error, Result};
+use git2::{BlameOptions, Oid, Repository};
+use scopetime::scope_time;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    path::Path,
+};
+
+/// commit a range of lines was last changed in
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlameHunk {
+    ///
+    pub commit_id: CommitId,
+    /// first parent of `commit_id`, used to re-blame before it
+    pub parent_id: Option<CommitId>,
+    ///
+    pub author: String,
+    /// seconds since epoch
+    pub time: i64,
+    /// first line of the hunk (zero-based)
+    pub start_line: usize,
+    /// line after the last line of the hunk (zero-based)
+    pub end_line: usize,
+}
+
+/// blame of a whole file at a commit
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileBlame {
+    /// commit the file got blamed at
+    pub commit_id: Option<CommitId>,
+    ///
+    pub path: String,
+    /// every line of the file along with the hunk it belongs to
+    pub lines: Vec<(Option<BlameHunk>, String)>,
+}
+
+struct CommitInfo {
+    author: String,
+    time: i64,
+    parent_id: Option<CommitId>,
+}
+
+fn commit_info(repo: &Repository, id: Oid) -> Result<CommitInfo> {
+    let commit = repo.find_commit(id)?;
+    let author = commit.author();
+
+    Ok(CommitInfo {
+        author: author.name().unwrap_or("<unknown>").into(),
+        time: commit.time().seconds(),
+        parent_id: commit.parent_ids().next().map(CommitId::new),
+    })
+}
+
+/// blames `file_path` as it was in `commit_id` (`HEAD` if `None`)
+pub fn blame_file(
+    repo_path: &str,
+    file_path: &str,
+    commit_id: Option<CommitId>,
+) -> Result<FileBlame> {
+    scope_time!("blame_file");
+
+    let repo = utils::repo(repo_path)?;
+
+    let commit = match commit_id {
+        Some(id) => repo.find_commit(id.into())?,
+        None => repo.head()?.peel_to_commit()?,
+    };
+
+    let entry = commit.tree()?.get_path(Path::new(file_path))?;
+    let blob = repo.find_blob(entry.id())?;
+
+    if blob.is_binary() {
+        return Err(Error::Generic(format!(
+            "cannot blame binary file: {}",
+            file_path
+        )));
+    }
+
+    let mut opts = BlameOptions::new();
+    opts.newest_commit(commit.id());
+    let blame =
+        repo.blame_file(Path::new(file_path), Some(&mut opts))?;
+
+    let mut infos = HashMap::<Oid, CommitInfo>::new();
+    let mut lines = Vec::new();
+
+    for (idx, line) in
+        String::from_utf8_lossy(blob.content()).lines().enumerate()
+    {
+        let hunk = match blame.get_line(idx + 1) {
+            Some(hunk) => {
+                let id = hunk.final_commit_id();
+                if let Entry::Vacant(e) = infos.entry(id) {
+                    e.insert(commit_info(&repo, id)?);
+                }
+                let info = &infos[&id];
+                let start_line = hunk.final_start_line() - 1;
+
+                Some(BlameHunk {
+                    commit_id: id.into(),
+                    parent_id: info.parent_id,
+                    author: info.author.clone(),
+                    time: info.time,
+                    start_line,
+                    end_line: start_line + hunk.lines_in_hunk(),
+                })
+            }
+            None => None,
+        };
+
+        lines.push((hunk, line.to_string()));
+    }
+
+    Ok(FileBlame {
+        commit_id: Some(commit.id().into()),
+        path: file_path.to_string(),
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        tests::{repo_init, write_commit},
+        utils::get_head,
+    };
+    use std::fs;
+
+    #[test]
+    fn test_blame_lines() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit(root, "foo.txt", "a\nb\n");
+        let first = get_head(repo_path).unwrap();
+        write_commit(root, "foo.txt", "a\nc\nd\n");
+        let second = get_head(repo_path).unwrap();
+
+        let blame = blame_file(repo_path, "foo.txt", None).unwrap();
+
+        assert_eq!(blame.commit_id, Some(second));
+        assert_eq!(blame.lines.len(), 3);
+        assert_eq!(blame.lines[0].1, "a");
+
+        let hunk_a = blame.lines[0].0.clone().unwrap();
+        assert_eq!(hunk_a.commit_id, first);
+        assert_eq!(hunk_a.author, "name");
+        assert_eq!((hunk_a.start_line, hunk_a.end_line), (0, 1));
+
+        let hunk_c = blame.lines[1].0.clone().unwrap();
+        assert_eq!(hunk_c.commit_id, second);
+        assert_eq!(hunk_c.parent_id, Some(first));
+        assert_eq!((hunk_c.start_line, hunk_c.end_line), (1, 3));
+    }
+
+    #[test]
+    fn test_blame_at_commit() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit(root, "foo.txt", "a\nb\n");
+        let first = get_head(repo_path).unwrap();
+        write_commit(root, "foo.txt", "c\n");
+
+        // uncommitted changes are ignored
+        fs::write(root.join("foo.txt"), "d\n").unwrap();
+
+        let blame =
+            blame_file(repo_path, "foo.txt", Some(first)).unwrap();
+
+        assert_eq!(blame.lines.len(), 2);
+        assert_eq!(blame.lines[1].1, "b");
+        assert_eq!(
+            blame.lines[1].0.as_ref().map(|h| h.commit_id),
+            Some(first)
+        );
+    }
+
+    #[test]
+    fn test_blame_missing_file() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert!(blame_file(repo_path, "foo.txt", None).is_err());
+    }
+}
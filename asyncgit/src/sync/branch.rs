@@ -2,11 +2,51 @@
 
 use crate::{
     error::{Error, Result},
-    sync::utils,
+    sync::{state::RepoState, utils, CommitId},
+};
+use git2::{
+    build::CheckoutBuilder, Branch, BranchType, Repository,
+    StatusOptions,
 };
 use scopetime::scope_time;
 use utils::get_head_repo;
 
+/// a local or remote-tracking branch
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchInfo {
+    /// short name (e.g. `master` or `origin/master`)
+    pub name: String,
+    /// full ref name (e.g. `refs/heads/master`)
+    pub reference: String,
+    /// `false` for remote-tracking branches
+    pub is_local: bool,
+    /// HEAD points to this branch
+    pub is_head: bool,
+    ///
+    pub top_commit: CommitId,
+    /// first line of the top commit message
+    pub top_commit_message: String,
+    /// short name of the configured upstream (local branches only)
+    pub upstream: Option<String>,
+    /// commits (ahead, behind) of `upstream`
+    pub ahead_behind: Option<(usize, usize)>,
+}
+
+/// HEAD with its upstream and the operation in progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchStatus {
+    /// branch HEAD points to, `None` if HEAD is detached
+    pub branch: Option<String>,
+    /// commit HEAD points to
+    pub head: CommitId,
+    /// short name of the upstream of `branch`
+    pub upstream: Option<String>,
+    /// commits (ahead, behind) of `upstream`
+    pub ahead_behind: Option<(usize, usize)>,
+    /// operation in progress (merge, rebase..)
+    pub state: RepoState,
+}
+
 /// returns the branch-name head is currently pointing to
 /// this might be expensive, see `cached::BranchName`
 pub(crate) fn get_branch_name(repo_path: &str) -> Result<String> {
@@ -46,6 +86,333 @@ pub fn create_branch(repo_path: &str, name: &str) -> Result<()> {
     Ok(())
 }
 
+/// returns all local and remote-tracking branches,
+/// locals first, each sorted by name
+pub fn get_branches_info(repo_path: &str) -> Result<Vec<BranchInfo>> {
+    scope_time!("get_branches_info");
+
+    let repo = utils::repo(repo_path)?;
+
+    let mut branches = repo
+        .branches(None)?
+        .map(|b| {
+            let (branch, kind) = b?;
+            branch_info(&repo, &branch, kind == BranchType::Local)
+        })
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>>>()?;
+
+    branches.sort_by(|a, b| {
+        b.is_local
+            .cmp(&a.is_local)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(branches)
+}
+
+/// `None` for symbolic refs like `origin/HEAD`
+fn branch_info(
+    repo: &Repository,
+    branch: &Branch,
+    is_local: bool,
+) -> Result<Option<BranchInfo>> {
+    let reference = branch.get();
+    if reference.symbolic_target_bytes().is_some() {
+        return Ok(None);
+    }
+
+    let name = String::from_utf8(branch.name_bytes()?.to_vec())?;
+    let ref_name =
+        String::from_utf8(reference.name_bytes().to_vec())?;
+    let top_commit = reference.peel_to_commit()?;
+
+    let (upstream, ahead_behind) =
+        upstream_info(repo, branch, top_commit.id())?;
+
+    Ok(Some(BranchInfo {
+        name,
+        reference: ref_name,
+        is_local,
+        is_head: branch.is_head(),
+        top_commit: top_commit.id().into(),
+        top_commit_message: top_commit
+            .summary_bytes()
+            .map(|s| String::from_utf8_lossy(s).to_string())
+            .unwrap_or_default(),
+        upstream,
+        ahead_behind,
+    }))
+}
+
+/// short name of an upstream and the commits (ahead, behind) of it
+type UpstreamInfo = (Option<String>, Option<(usize, usize)>);
+
+fn upstream_info(
+    repo: &Repository,
+    branch: &Branch,
+    id: git2::Oid,
+) -> Result<UpstreamInfo> {
+    let upstream = branch.upstream().ok();
+
+    let ahead_behind = if let Some(upstream) = &upstream {
+        upstream
+            .get()
+            .target()
+            .map(|upstream_id| {
+                repo.graph_ahead_behind(id, upstream_id)
+            })
+            .transpose()?
+    } else {
+        None
+    };
+
+    let upstream = upstream
+        .map(|u| u.name_bytes().map(|n| n.to_vec()))
+        .transpose()?
+        .map(String::from_utf8)
+        .transpose()?;
+
+    Ok((upstream, ahead_behind))
+}
+
+/// returns the state of HEAD including upstream and ahead/behind counts,
+/// see `cached::BranchState`
+pub fn get_branch_status(repo_path: &str) -> Result<BranchStatus> {
+    scope_time!("get_branch_status");
+
+    let repo = utils::repo(repo_path)?;
+    let state = repo.state().into();
+
+    let head = repo.head()?;
+    let head_id = head.target().ok_or(Error::NoHead)?;
+
+    if !head.is_branch() {
+        return Ok(BranchStatus {
+            branch: None,
+            head: head_id.into(),
+            upstream: None,
+            ahead_behind: None,
+            state,
+        });
+    }
+
+    let branch = Branch::wrap(head);
+    let name = String::from_utf8(branch.name_bytes()?.to_vec())?;
+    let (upstream, ahead_behind) =
+        upstream_info(&repo, &branch, head_id)?;
+
+    Ok(BranchStatus {
+        branch: Some(name),
+        head: head_id.into(),
+        upstream,
+        ahead_behind,
+        state,
+    })
+}
+
+/// ref name and target of the upstream of the branch HEAD points to
+/// (cheap compared to `get_branch_status`)
+pub(crate) fn get_head_upstream(
+    repo_path: &str,
+) -> Result<Option<(String, CommitId)>> {
+    let repo = utils::repo(repo_path)?;
+    let head = repo.head()?;
+
+    if !head.is_branch() {
+        return Ok(None);
+    }
+
+    let upstream = Branch::wrap(head).upstream().ok().and_then(|u| {
+        let name = u.get().name().map(String::from);
+        let target = u.get().target();
+        name.zip(target.map(CommitId::from))
+    });
+
+    Ok(upstream)
+}
+
+fn find_branch<'a>(
+    repo: &'a Repository,
+    branch_ref: &str,
+) -> Result<Branch<'a>> {
+    let reference = repo.find_reference(branch_ref)?;
+    if !reference.is_branch() && !reference.is_remote() {
+        return Err(Error::Generic(format!(
+            "'{}' is not a branch",
+            branch_ref
+        )));
+    }
+
+    Ok(Branch::wrap(reference))
+}
+
+/// returns `true` if there are no changes to tracked files
+/// in the working dir or stage
+pub fn is_workdir_clean(repo_path: &str) -> Result<bool> {
+    scope_time!("is_workdir_clean");
+
+    let repo = utils::repo(repo_path)?;
+
+    let mut options = StatusOptions::new();
+    options.include_untracked(false).include_ignored(false);
+
+    let statuses = repo.statuses(Some(&mut options))?;
+
+    Ok(statuses.is_empty())
+}
+
+/// switches HEAD to the branch `branch_ref` (full ref name).
+///
+/// checking out a remote-tracking branch creates a local branch of the
+/// same name tracking it. refuses to run with local changes.
+pub fn checkout_branch(
+    repo_path: &str,
+    branch_ref: &str,
+) -> Result<()> {
+    scope_time!("checkout_branch");
+
+    if !is_workdir_clean(repo_path)? {
+        return Err(Error::Generic(
+            "checkout refused: working tree has local changes"
+                .to_string(),
+        ));
+    }
+
+    let repo = utils::repo(repo_path)?;
+    let branch = find_branch(&repo, branch_ref)?;
+
+    let local_ref = if branch.get().is_remote() {
+        local_for_remote_branch(&repo, &branch)?
+    } else {
+        branch_ref.to_string()
+    };
+
+    let commit = repo.find_reference(&local_ref)?.peel_to_commit()?;
+
+    repo.checkout_tree(
+        commit.as_object(),
+        Some(CheckoutBuilder::new().safe()),
+    )?;
+    repo.set_head(&local_ref)?;
+
+    Ok(())
+}
+
+/// returns the local branch for the remote-tracking `branch`,
+/// creates it if it does not exist yet
+fn local_for_remote_branch(
+    repo: &Repository,
+    branch: &Branch,
+) -> Result<String> {
+    let remote_name =
+        String::from_utf8(branch.name_bytes()?.to_vec())?;
+    let local_name =
+        remote_name.splitn(2, '/').nth(1).ok_or_else(|| {
+            Error::Generic(format!(
+                "invalid remote branch name: '{}'",
+                remote_name
+            ))
+        })?;
+
+    if let Ok(local) = repo.find_branch(local_name, BranchType::Local)
+    {
+        return Ok(String::from_utf8(
+            local.get().name_bytes().to_vec(),
+        )?);
+    }
+
+    let commit = branch.get().peel_to_commit()?;
+    let mut local = repo.branch(local_name, &commit, false)?;
+    local.set_upstream(Some(&remote_name))?;
+
+    Ok(String::from_utf8(local.get().name_bytes().to_vec())?)
+}
+
+/// renames the local branch `branch_ref` to `new_name`
+pub fn rename_branch(
+    repo_path: &str,
+    branch_ref: &str,
+    new_name: &str,
+) -> Result<()> {
+    scope_time!("rename_branch");
+
+    let repo = utils::repo(repo_path)?;
+    let mut branch = find_branch(&repo, branch_ref)?;
+
+    branch.rename(new_name, false)?;
+
+    Ok(())
+}
+
+/// deletes the branch `branch_ref`, fails for the branch HEAD points to
+pub fn delete_branch(
+    repo_path: &str,
+    branch_ref: &str,
+) -> Result<()> {
+    scope_time!("delete_branch");
+
+    let repo = utils::repo(repo_path)?;
+    let mut branch = find_branch(&repo, branch_ref)?;
+
+    if branch.is_head() {
+        return Err(Error::Generic(
+            "cannot delete the branch HEAD points to".to_string(),
+        ));
+    }
+
+    branch.delete()?;
+
+    Ok(())
+}
+
+/// returns `true` if the branch `branch_ref` is fully merged into
+/// HEAD or its own upstream
+pub fn branch_is_merged(
+    repo_path: &str,
+    branch_ref: &str,
+) -> Result<bool> {
+    scope_time!("branch_is_merged");
+
+    let repo = utils::repo(repo_path)?;
+    let branch = find_branch(&repo, branch_ref)?;
+    let id = branch.get().peel_to_commit()?.id();
+
+    let is_merged_into = |target: Option<git2::Oid>| -> Result<bool> {
+        Ok(match target {
+            Some(target) => {
+                target == id
+                    || repo.graph_descendant_of(target, id)?
+            }
+            None => false,
+        })
+    };
+
+    let head = repo.head()?.target();
+    let upstream =
+        branch.upstream().ok().and_then(|u| u.get().target());
+
+    Ok(is_merged_into(head)? || is_merged_into(upstream)?)
+}
+
+/// sets the upstream of the local branch `branch_ref` to the
+/// remote-tracking branch `upstream` (e.g. `origin/master`),
+/// `None` removes it
+pub fn set_branch_upstream(
+    repo_path: &str,
+    branch_ref: &str,
+    upstream: Option<&str>,
+) -> Result<()> {
+    scope_time!("set_branch_upstream");
+
+    let repo = utils::repo(repo_path)?;
+    let mut branch = find_branch(&repo, branch_ref)?;
+
+    branch.set_upstream(upstream)?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests_branch_name {
     use super::*;
@@ -95,3 +462,210 @@ mod tests_create_branch {
         );
     }
 }
+
+#[cfg(test)]
+mod tests_branches {
+    use super::*;
+    use crate::sync::{
+        push_upstream,
+        tests::{
+            bare_url, path, repo_clone, repo_init, repo_init_bare,
+            write_commit,
+        },
+    };
+    use std::path::Path;
+
+    #[test]
+    fn test_branches_info() {
+        let (_remote_td, remote) = repo_init_bare();
+        let (_td, repo) = repo_clone(&bare_url(&remote));
+        let root = repo.path().parent().unwrap();
+
+        write_commit(root, "a.txt", "initial");
+        push_upstream(path(&repo)).unwrap();
+        write_commit(root, "b.txt", "second");
+
+        let branches = get_branches_info(path(&repo)).unwrap();
+
+        assert_eq!(branches.len(), 2);
+        assert_eq!(branches[0].name, "master");
+        assert!(branches[0].is_local);
+        assert!(branches[0].is_head);
+        assert_eq!(branches[0].top_commit_message, "second");
+        assert_eq!(
+            branches[0].upstream.as_deref(),
+            Some("origin/master")
+        );
+        assert_eq!(branches[0].ahead_behind, Some((1, 0)));
+        assert_eq!(branches[1].name, "origin/master");
+        assert!(!branches[1].is_local);
+        assert_eq!(
+            branches[1].reference,
+            "refs/remotes/origin/master"
+        );
+    }
+
+    #[test]
+    fn test_checkout() {
+        let (_td, repo) = repo_init().unwrap();
+        let repo_path = path(&repo);
+
+        create_branch(repo_path, "branch1").unwrap();
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        assert_eq!(get_branch_name(repo_path).unwrap(), "master");
+    }
+
+    #[test]
+    fn test_checkout_refused_when_dirty() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = path(&repo);
+
+        write_commit(root, "a.txt", "a");
+        create_branch(repo_path, "branch1").unwrap();
+        std::fs::write(root.join("a.txt"), "changed").unwrap();
+
+        assert!(!is_workdir_clean(repo_path).unwrap());
+        assert!(
+            checkout_branch(repo_path, "refs/heads/master").is_err()
+        );
+        assert_eq!(get_branch_name(repo_path).unwrap(), "branch1");
+    }
+
+    #[test]
+    fn test_checkout_remote_creates_tracking_branch() {
+        let (_remote_td, remote) = repo_init_bare();
+        let (_td1, upstream) = repo_clone(&bare_url(&remote));
+        write_commit(upstream.path().parent().unwrap(), "a.txt", "a");
+        push_upstream(path(&upstream)).unwrap();
+        create_branch(path(&upstream), "feature").unwrap();
+        upstream
+            .config()
+            .unwrap()
+            .set_str("branch.feature.remote", "origin")
+            .unwrap();
+        upstream
+            .config()
+            .unwrap()
+            .set_str("branch.feature.merge", "refs/heads/feature")
+            .unwrap();
+        push_upstream(path(&upstream)).unwrap();
+
+        let (_td2, local) = repo_clone(&bare_url(&remote));
+        checkout_branch(path(&local), "refs/remotes/origin/feature")
+            .unwrap();
+
+        assert_eq!(get_branch_name(path(&local)).unwrap(), "feature");
+        let feature =
+            local.find_branch("feature", BranchType::Local).unwrap();
+        assert_eq!(
+            feature.upstream().unwrap().name().unwrap(),
+            Some("origin/feature")
+        );
+    }
+
+    #[test]
+    fn test_rename_and_delete() {
+        let (_td, repo) = repo_init().unwrap();
+        let repo_path = path(&repo);
+
+        create_branch(repo_path, "branch1").unwrap();
+        rename_branch(repo_path, "refs/heads/branch1", "branch2")
+            .unwrap();
+        assert_eq!(get_branch_name(repo_path).unwrap(), "branch2");
+
+        assert!(
+            delete_branch(repo_path, "refs/heads/branch2").is_err()
+        );
+
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        assert!(branch_is_merged(repo_path, "refs/heads/branch2")
+            .unwrap());
+        delete_branch(repo_path, "refs/heads/branch2").unwrap();
+
+        assert_eq!(get_branches_info(repo_path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_unmerged_and_upstream() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = path(&repo);
+
+        create_branch(repo_path, "branch1").unwrap();
+        write_commit(root, "a.txt", "a");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        assert!(!branch_is_merged(repo_path, "refs/heads/branch1")
+            .unwrap());
+        assert!(!Path::new(&root.join("a.txt")).exists());
+
+        set_branch_upstream(
+            repo_path,
+            "refs/heads/branch1",
+            Some("master"),
+        )
+        .unwrap();
+        let branches = get_branches_info(repo_path).unwrap();
+        assert_eq!(branches[0].upstream.as_deref(), Some("master"));
+        assert_eq!(branches[0].ahead_behind, Some((1, 0)));
+
+        set_branch_upstream(repo_path, "refs/heads/branch1", None)
+            .unwrap();
+        let branches = get_branches_info(repo_path).unwrap();
+        assert_eq!(branches[0].upstream, None);
+    }
+}
+
+#[cfg(test)]
+mod tests_branch_status {
+    use super::*;
+    use crate::sync::{
+        push_upstream,
+        tests::{bare_url, repo_clone, repo_init_bare, write_commit},
+        utils::get_head,
+    };
+
+    #[test]
+    fn test_branch_status() {
+        let (_remote_td, remote) = repo_init_bare();
+        let (_td, repo) = repo_clone(&bare_url(&remote));
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.to_str().unwrap();
+
+        write_commit(root, "a.txt", "initial");
+        push_upstream(repo_path).unwrap();
+        write_commit(root, "b.txt", "second");
+
+        let status = get_branch_status(repo_path).unwrap();
+
+        assert_eq!(status.branch.as_deref(), Some("master"));
+        assert_eq!(status.upstream.as_deref(), Some("origin/master"));
+        assert_eq!(status.ahead_behind, Some((1, 0)));
+        assert_eq!(status.state, RepoState::Clean);
+        assert_eq!(
+            get_head_upstream(repo_path).unwrap().unwrap().0,
+            "refs/remotes/origin/master"
+        );
+    }
+
+    #[test]
+    fn test_branch_status_detached() {
+        let (_remote_td, remote) = repo_init_bare();
+        let (_td, repo) = repo_clone(&bare_url(&remote));
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.to_str().unwrap();
+
+        write_commit(root, "a.txt", "initial");
+        let head = get_head(repo_path).unwrap();
+        repo.set_head_detached(head.into()).unwrap();
+
+        let status = get_branch_status(repo_path).unwrap();
+
+        assert_eq!(status.branch, None);
+        assert_eq!(status.head, head);
+        assert_eq!(status.upstream, None);
+        assert_eq!(get_head_upstream(repo_path).unwrap(), None);
+    }
+}
@@ -0,0 +1,278 @@
+//! sync git api for cherry-picking and reverting commits
+
+use super::{merge::merge_commit, utils, CommitId};
+use crate::error::{Error, Result};
+use git2::{Oid, Repository, RepositoryState, Sort};
+use scopetime::scope_time;
+use std::collections::HashSet;
+
+/// outcome of `cherry_pick` and `revert`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickResult {
+    /// all commits were applied and committed
+    Committed,
+    /// all commits were applied, the last one is staged and
+    /// waits to be committed using the prepared message
+    Prepared,
+    /// applying a commit stopped with conflicts, the repo is in
+    /// cherry-pick/revert state. holds the number of commits
+    /// that were not applied
+    Conflict(usize),
+}
+
+/// orders `ids` by history, oldest first
+fn sort_ids(repo: &Repository, ids: &[CommitId]) -> Result<Vec<Oid>> {
+    let wanted =
+        ids.iter().map(|id| (*id).into()).collect::<HashSet<Oid>>();
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    for id in &wanted {
+        walk.push(*id)?;
+    }
+
+    let mut sorted = Vec::with_capacity(wanted.len());
+    for id in walk {
+        let id = id?;
+        if wanted.contains(&id) {
+            sorted.push(id);
+        }
+    }
+
+    Ok(sorted)
+}
+
+fn check_clean(repo: &Repository) -> Result<()> {
+    if repo.state() != RepositoryState::Clean {
+        return Err(Error::Generic(
+            "another operation is in progress".to_string(),
+        ));
+    }
+
+    utils::check_index_matches_head(repo)
+}
+
+fn check_no_merge(commit: &git2::Commit) -> Result<()> {
+    if commit.parent_count() > 1 {
+        Err(Error::Generic(format!(
+            "merge commits are not supported ({})",
+            commit.id()
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// applies `ids` on top of HEAD oldest first, committing each
+/// with its original message and author
+pub fn cherry_pick(
+    repo_path: &str,
+    ids: &[CommitId],
+) -> Result<PickResult> {
+    scope_time!("cherry_pick");
+
+    let repo = utils::repo(repo_path)?;
+    check_clean(&repo)?;
+
+    let ids = sort_ids(&repo, ids)?;
+
+    for (idx, id) in ids.iter().enumerate() {
+        let commit = repo.find_commit(*id)?;
+        check_no_merge(&commit)?;
+
+        repo.cherrypick(&commit, None)?;
+
+        let mut index = repo.index()?;
+        if index.has_conflicts() {
+            return Ok(PickResult::Conflict(ids.len() - idx - 1));
+        }
+
+        let head_tree = repo.head()?.peel_to_tree()?.id();
+        if index.write_tree()? == head_tree {
+            repo.cleanup_state()?;
+            return Err(Error::Generic(format!(
+                "cherry-pick of {} is empty, changes are already applied",
+                id
+            )));
+        }
+
+        merge_commit(
+            repo_path,
+            commit.message().unwrap_or_default(),
+        )?;
+    }
+
+    Ok(PickResult::Committed)
+}
+
+/// reverts `ids` newest first. all but the last revert are
+/// committed right away, the last one stays staged with the
+/// prepared message (see `merge_msg`)
+pub fn revert(
+    repo_path: &str,
+    ids: &[CommitId],
+) -> Result<PickResult> {
+    scope_time!("revert");
+
+    let repo = utils::repo(repo_path)?;
+    check_clean(&repo)?;
+
+    let mut ids = sort_ids(&repo, ids)?;
+    ids.reverse();
+
+    for (idx, id) in ids.iter().enumerate() {
+        let commit = repo.find_commit(*id)?;
+        check_no_merge(&commit)?;
+
+        repo.revert(&commit, None)?;
+
+        if repo.index()?.has_conflicts() {
+            return Ok(PickResult::Conflict(ids.len() - idx - 1));
+        }
+
+        if idx + 1 < ids.len() {
+            merge_commit(repo_path, &super::merge_msg(repo_path)?)?;
+        }
+    }
+
+    Ok(PickResult::Prepared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch, merge_msg, repo_state,
+        tests::{path, repo_init, write_commit},
+        utils::get_head,
+        RepoState,
+    };
+    use std::{fs, path::Path};
+
+    /// `feature` gets commits `b` and `c` on top of master
+    fn setup_feature(repo: &Repository) -> (CommitId, CommitId) {
+        let root = repo.path().parent().unwrap();
+        write_commit(root, "a.txt", "a");
+        create_branch(path(repo), "feature").unwrap();
+        write_commit(root, "b.txt", "b");
+        let b = get_head(path(repo)).unwrap();
+        write_commit(root, "c.txt", "c");
+        let c = get_head(path(repo)).unwrap();
+        checkout_branch(path(repo), "refs/heads/master").unwrap();
+        (b, c)
+    }
+
+    #[test]
+    fn test_cherry_pick_range() {
+        let (_td, repo) = repo_init().unwrap();
+        let (b, c) = setup_feature(&repo);
+
+        // order of ids does not matter
+        assert_eq!(
+            cherry_pick(path(&repo), &[c, b]).unwrap(),
+            PickResult::Committed
+        );
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("c"));
+        assert_eq!(head.parent(0).unwrap().message(), Some("b"));
+        assert_eq!(repo.head().unwrap().shorthand(), Some("master"));
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_cherry_pick_staged_changes() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let (b, _) = setup_feature(&repo);
+
+        fs::write(root.join("d.txt"), "d").unwrap();
+        crate::sync::stage_add_file(path(&repo), Path::new("d.txt"))
+            .unwrap();
+        let head = get_head(path(&repo)).unwrap();
+
+        assert!(cherry_pick(path(&repo), &[b]).is_err());
+        assert!(revert(path(&repo), &[head]).is_err());
+        assert_eq!(get_head(path(&repo)).unwrap(), head);
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_cherry_pick_empty() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        write_commit(root, "a.txt", "a");
+        let a = get_head(path(&repo)).unwrap();
+
+        assert!(cherry_pick(path(&repo), &[a]).is_err());
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_cherry_pick_conflict() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let (b, c) = setup_feature(&repo);
+        write_commit(root, "b.txt", "master");
+
+        assert_eq!(
+            cherry_pick(path(&repo), &[b, c]).unwrap(),
+            PickResult::Conflict(1)
+        );
+        assert_eq!(
+            repo_state(path(&repo)).unwrap(),
+            RepoState::CherryPick
+        );
+
+        fs::write(root.join("b.txt"), "resolved").unwrap();
+        crate::sync::stage_add_file(
+            path(&repo),
+            std::path::Path::new("b.txt"),
+        )
+        .unwrap();
+
+        merge_commit(path(&repo), "b").unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 1);
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_revert_prepares_message() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        write_commit(root, "a.txt", "a");
+        write_commit(root, "b.txt", "b");
+        let b = get_head(path(&repo)).unwrap();
+        write_commit(root, "c.txt", "c");
+        let c = get_head(path(&repo)).unwrap();
+
+        assert_eq!(
+            revert(path(&repo), &[b, c]).unwrap(),
+            PickResult::Prepared
+        );
+
+        // `c` got reverted and committed first
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert!(head.message().unwrap().starts_with("Revert \"c\""));
+        assert!(!root.join("c.txt").exists());
+
+        assert_eq!(
+            repo_state(path(&repo)).unwrap(),
+            RepoState::Revert
+        );
+        assert!(!root.join("b.txt").exists());
+        assert_eq!(
+            merge_msg(path(&repo)).unwrap(),
+            format!(
+                "Revert \"b\"\n\nThis reverts commit {}.",
+                b.to_string()
+            )
+        );
+
+        merge_commit(path(&repo), &merge_msg(path(&repo)).unwrap())
+            .unwrap();
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+}
@@ -1,6 +1,14 @@
-use super::{get_head, utils::repo, CommitId};
-use crate::error::Result;
-use git2::{ErrorCode, ObjectType, Repository, Signature};
+use super::{
+    get_head,
+    sign::{signed_tag_buffer, Signer},
+    utils::repo,
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{
+    Commit, ErrorCode, ObjectType, Oid, Reference, Repository,
+    Signature, Tree,
+};
 use scopetime::scope_time;
 
 ///
@@ -18,22 +26,74 @@ pub fn amend(
     let tree_id = index.write_tree()?;
     let tree = repo.find_tree(tree_id)?;
 
-    let new_id = commit.amend(
-        Some("HEAD"),
-        None,
-        None,
-        None,
-        Some(msg),
-        Some(&tree),
+    let parents = commit.parents().collect::<Vec<_>>();
+    let parents = parents.iter().collect::<Vec<_>>();
+
+    let new_id = create_commit(
+        &repo,
+        &commit.author(),
+        &commit.committer(),
+        msg,
+        &tree,
+        parents.as_slice(),
     )?;
+    update_head(&repo, new_id, "commit (amend)")?;
 
     Ok(CommitId::new(new_id))
 }
 
+/// creates a commit like `Repository::commit` without updating
+/// any ref, signed when `commit.gpgsign` is set
+pub(crate) fn create_commit(
+    repo: &Repository,
+    author: &Signature,
+    committer: &Signature,
+    msg: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+) -> Result<Oid> {
+    if let Some(signer) = Signer::for_commits(repo)? {
+        let buffer = repo.commit_create_buffer(
+            author, committer, msg, tree, parents,
+        )?;
+        let buffer = buffer.as_str().ok_or_else(|| {
+            Error::Generic("commit is no valid utf8".to_string())
+        })?;
+
+        let signature = signer.sign(buffer)?;
+
+        Ok(repo.commit_signed(buffer, &signature, None)?)
+    } else {
+        Ok(repo.commit(None, author, committer, msg, tree, parents)?)
+    }
+}
+
+/// points HEAD, or the branch it is on, to commit `id` logging
+/// `operation` and the commit summary to the reflog
+pub(crate) fn update_head(
+    repo: &Repository,
+    id: Oid,
+    operation: &str,
+) -> Result<()> {
+    let commit = repo.find_commit(id)?;
+    let log_msg = format!(
+        "{}: {}",
+        operation,
+        commit.summary().unwrap_or_default()
+    );
+
+    let head = repo.find_reference("HEAD")?;
+    let target = head.symbolic_target().unwrap_or("HEAD").to_string();
+
+    repo.reference(&target, id, true, &log_msg)?;
+
+    Ok(())
+}
+
 /// Wrap Repository::signature to allow unknown user.name.
 ///
 /// See <https://github.com/extrawurst/gitui/issues/79>.
-fn signature_allow_undefined_name(
+pub(crate) fn signature_allow_undefined_name(
     repo: &Repository,
 ) -> std::result::Result<Signature<'_>, git2::Error> {
     match repo.signature() {
@@ -68,19 +128,28 @@ pub fn commit(repo_path: &str, msg: &str) -> Result<CommitId> {
 
     let parents = parents.iter().collect::<Vec<_>>();
 
-    Ok(repo
-        .commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            msg,
-            &tree,
-            parents.as_slice(),
-        )?
-        .into())
+    let id = create_commit(
+        &repo,
+        &signature,
+        &signature,
+        msg,
+        &tree,
+        parents.as_slice(),
+    )?;
+    update_head(
+        &repo,
+        id,
+        if parents.is_empty() {
+            "commit (initial)"
+        } else {
+            "commit"
+        },
+    )?;
+
+    Ok(id.into())
 }
 
-/// Tag a commit.
+/// Tag a commit, signed when `tag.gpgsign` is set.
 ///
 /// This function will return an `Err(…)` variant if the tag’s name is refused
 /// by git or if the tag already exists.
@@ -98,7 +167,26 @@ pub fn tag(
     let target =
         repo.find_object(object_id, Some(ObjectType::Commit))?;
 
-    Ok(repo.tag(tag, &target, &signature, "", false)?.into())
+    if let Some(signer) = Signer::for_tags(&repo)? {
+        let name = format!("refs/tags/{}", tag);
+        if !Reference::is_valid_name(&name) {
+            return Err(Error::Generic(format!(
+                "invalid tag name: {}",
+                tag
+            )));
+        }
+
+        let buffer =
+            signed_tag_buffer(&signer, &target, tag, &signature, "")?;
+        let id =
+            repo.odb()?.write(ObjectType::Tag, buffer.as_bytes())?;
+
+        repo.reference(&name, id, false, "")?;
+
+        Ok(id.into())
+    } else {
+        Ok(repo.tag(tag, &target, &signature, "", false)?.into())
+    }
 }
 
 #[cfg(test)]
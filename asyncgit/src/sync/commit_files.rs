@@ -1,6 +1,11 @@
-use super::{stash::is_stash_commit, utils::repo, CommitId};
+use super::{
+    diff::{apply_options, DiffOptions},
+    stash::is_stash_commit,
+    utils::repo,
+    CommitId,
+};
 use crate::{error::Result, StatusItem, StatusItemType};
-use git2::{Diff, DiffDelta, DiffOptions, Repository};
+use git2::{Diff, DiffDelta, Repository};
 use scopetime::scope_time;
 
 /// get all files that are part of a commit
@@ -12,7 +17,7 @@ pub fn get_commit_files(
 
     let repo = repo(repo_path)?;
 
-    let diff = get_commit_diff(&repo, id, None)?;
+    let diff = get_commit_diff(&repo, id, &[], None)?;
 
     let mut res = Vec::new();
 
@@ -36,12 +41,14 @@ pub fn get_commit_files(
     Ok(res)
 }
 
-///
-pub(crate) fn get_commit_diff(
-    repo: &Repository,
+/// diff of a commit against its first parent, limited to `pathspecs`
+/// unless empty
+pub(crate) fn get_commit_diff<'a>(
+    repo: &'a Repository,
     id: CommitId,
-    pathspec: Option<String>,
-) -> Result<Diff<'_>> {
+    pathspecs: &[String],
+    options: Option<DiffOptions>,
+) -> Result<Diff<'a>> {
     // scope_time!("get_commit_diff");
 
     let commit = repo.find_commit(id.into())?;
@@ -52,17 +59,17 @@ pub(crate) fn get_commit_diff(
         None
     };
 
-    let mut opt = pathspec.as_ref().map(|p| {
-        let mut opts = DiffOptions::new();
-        opts.pathspec(p);
-        opts.show_binary(true);
-        opts
-    });
+    let mut opt = git2::DiffOptions::new();
+    for p in pathspecs {
+        opt.pathspec(p);
+    }
+    opt.show_binary(true);
+    apply_options(&mut opt, options);
 
     let mut diff = repo.diff_tree_to_tree(
         parent.as_ref(),
         Some(&commit_tree),
-        opt.as_mut(),
+        Some(&mut opt),
     )?;
 
     if is_stash_commit(
@@ -73,7 +80,8 @@ pub(crate) fn get_commit_diff(
             let untracked_diff = get_commit_diff(
                 repo,
                 CommitId::new(untracked_commit),
-                pathspec,
+                pathspecs,
+                options,
             )?;
 
             diff.merge(&untracked_diff)?;
@@ -48,6 +48,8 @@ pub struct CommitInfo {
     pub author: String,
     ///
     pub id: CommitId,
+    ///
+    pub parents: Vec<CommitId>,
 }
 
 ///
@@ -79,6 +81,7 @@ pub fn get_commits_info(
                 author,
                 time: c.time().seconds(),
                 id: CommitId(c.id()),
+                parents: c.parent_ids().map(CommitId).collect(),
             }
         })
         .collect::<Vec<_>>();
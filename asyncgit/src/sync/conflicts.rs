@@ -0,0 +1,542 @@
+//! sync git api for resolving conflicts of a merge in progress
+
+use super::utils::{self, work_dir};
+use crate::error::{Error, Result};
+use git2::{DiffOptions, IndexEntry, Patch, Repository};
+use scopetime::scope_time;
+use std::{fs, path::Path};
+
+/// which version of a conflict to keep
+#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+pub enum ConflictResolution {
+    /// the version of HEAD
+    Ours,
+    /// the version being merged in
+    Theirs,
+    /// ours followed by theirs
+    Both,
+}
+
+/// part of a conflicted file
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum ConflictChunk {
+    /// lines both sides agree on
+    Merged(Vec<String>),
+    /// lines changed differently on both sides
+    Conflict {
+        ///
+        base: Vec<String>,
+        ///
+        ours: Vec<String>,
+        ///
+        theirs: Vec<String>,
+    },
+}
+
+/// three-way view of a conflicted file built from its index stages
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct FileConflict {
+    /// the file split into merged parts and conflicts,
+    /// lines keep their line endings
+    pub chunks: Vec<ConflictChunk>,
+    /// binary files can only be resolved as a whole
+    pub binary: bool,
+}
+
+impl FileConflict {
+    /// number of `ConflictChunk::Conflict` entries
+    pub fn conflict_count(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|c| matches!(c, ConflictChunk::Conflict { .. }))
+            .count()
+    }
+
+    /// content after applying one of `choices` to each conflict (in order)
+    pub fn resolve(&self, choices: &[ConflictResolution]) -> String {
+        let mut choices = choices.iter();
+        let mut res = String::new();
+
+        for chunk in &self.chunks {
+            match chunk {
+                ConflictChunk::Merged(lines) => {
+                    res.extend(lines.iter().map(String::as_str))
+                }
+                ConflictChunk::Conflict { ours, theirs, .. } => {
+                    let (take_ours, take_theirs) =
+                        match choices.next() {
+                            Some(ConflictResolution::Ours) => {
+                                (true, false)
+                            }
+                            Some(ConflictResolution::Theirs) => {
+                                (false, true)
+                            }
+                            Some(ConflictResolution::Both) | None => {
+                                (true, true)
+                            }
+                        };
+
+                    if take_ours {
+                        res.extend(ours.iter().map(String::as_str));
+                    }
+                    if take_theirs {
+                        res.extend(theirs.iter().map(String::as_str));
+                    }
+                }
+            }
+        }
+
+        res
+    }
+}
+
+/// range of base lines `[base.0,base.1)` replaced by `[side.0,side.1)`
+#[derive(Debug, Clone, Copy)]
+struct Change {
+    base: (usize, usize),
+    side: (usize, usize),
+}
+
+/// changes between `base` and `side` line by line
+fn changes(base: &[u8], side: &[u8]) -> Result<Vec<Change>> {
+    let mut opt = DiffOptions::new();
+    opt.context_lines(0);
+
+    let patch =
+        Patch::from_buffers(base, None, side, None, Some(&mut opt))?;
+
+    // libgit2 reports the line *after* which lines are inserted
+    // (or removed) as the start of empty ranges
+    let range = |start: u32, len: u32| {
+        let start = start as usize;
+        let len = len as usize;
+        if len == 0 {
+            (start, start)
+        } else {
+            (start - 1, start - 1 + len)
+        }
+    };
+
+    (0..patch.num_hunks())
+        .map(|i| {
+            let (hunk, _) = patch.hunk(i)?;
+            Ok(Change {
+                base: range(hunk.old_start(), hunk.old_lines()),
+                side: range(hunk.new_start(), hunk.new_lines()),
+            })
+        })
+        .collect()
+}
+
+fn lines(content: &str) -> Vec<String> {
+    content.split_inclusive('\n').map(String::from).collect()
+}
+
+/// line based three-way merge of `ours` and `theirs` (diff3)
+fn merge_chunks(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+) -> Result<Vec<ConflictChunk>> {
+    let base_lines = lines(base);
+    let sides = [lines(ours), lines(theirs)];
+
+    let mut all_changes = changes(base.as_bytes(), ours.as_bytes())?
+        .into_iter()
+        .map(|c| (0, c))
+        .chain(
+            changes(base.as_bytes(), theirs.as_bytes())?
+                .into_iter()
+                .map(|c| (1, c)),
+        )
+        .collect::<Vec<_>>();
+    all_changes.sort_by_key(|(_, c)| c.base);
+
+    let mut chunks = Vec::new();
+    let mut merged = Vec::new();
+    // how many lines each side is ahead of base so far
+    let mut offsets = [0_isize, 0_isize];
+    let mut base_pos = 0;
+    let mut changes = all_changes.into_iter().peekable();
+
+    while let Some((side, first)) = changes.next() {
+        let mut group = vec![(side, first)];
+        let mut group_end = first.base.1;
+
+        while let Some((side, next)) = changes.peek().copied() {
+            if next.base.0 > group_end {
+                break;
+            }
+            group_end = group_end.max(next.base.1);
+            group.push((side, next));
+            changes.next();
+        }
+
+        let group_start = group[0].1.base.0;
+
+        merged.extend_from_slice(&base_lines[base_pos..group_start]);
+        base_pos = group_end;
+
+        let mut changed = [false, false];
+        let mut versions = [Vec::new(), Vec::new()];
+
+        for side in 0..2 {
+            let growth: isize = group
+                .iter()
+                .filter(|(s, _)| *s == side)
+                .map(|(_, c)| {
+                    (c.side.1 - c.side.0) as isize
+                        - (c.base.1 - c.base.0) as isize
+                })
+                .sum();
+
+            changed[side] = group.iter().any(|(s, _)| *s == side);
+
+            let start =
+                (group_start as isize + offsets[side]) as usize;
+            let end = (group_end as isize + offsets[side] + growth)
+                as usize;
+            versions[side] = sides[side][start..end].to_vec();
+
+            offsets[side] += growth;
+        }
+
+        let [ours, theirs] = versions;
+
+        if !changed[1] || ours == theirs {
+            merged.extend(ours);
+        } else if !changed[0] {
+            merged.extend(theirs);
+        } else {
+            if !merged.is_empty() {
+                chunks
+                    .push(ConflictChunk::Merged(merged.split_off(0)));
+            }
+            chunks.push(ConflictChunk::Conflict {
+                base: base_lines[group_start..group_end].to_vec(),
+                ours,
+                theirs,
+            });
+        }
+    }
+
+    merged.extend_from_slice(&base_lines[base_pos..]);
+    if !merged.is_empty() {
+        chunks.push(ConflictChunk::Merged(merged));
+    }
+
+    Ok(chunks)
+}
+
+fn blob_content(
+    repo: &Repository,
+    entry: Option<&IndexEntry>,
+) -> Result<Option<Vec<u8>>> {
+    entry
+        .map(|e| Ok(repo.find_blob(e.id)?.content().to_vec()))
+        .transpose()
+}
+
+/// (ancestor, ours, theirs), missing if deleted on that side
+type ConflictEntries =
+    (Option<IndexEntry>, Option<IndexEntry>, Option<IndexEntry>);
+
+/// conflict entries of `path`
+fn conflict_entries(
+    repo: &Repository,
+    path: &str,
+) -> Result<Option<ConflictEntries>> {
+    let index = repo.index()?;
+
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry_path = conflict
+            .our
+            .as_ref()
+            .or(conflict.their.as_ref())
+            .or(conflict.ancestor.as_ref())
+            .map(|e| e.path.clone());
+
+        if entry_path.as_deref() == Some(path.as_bytes()) {
+            return Ok(Some((
+                conflict.ancestor,
+                conflict.our,
+                conflict.their,
+            )));
+        }
+    }
+
+    Ok(None)
+}
+
+/// three-way view of `path`, `None` if it is not conflicted
+pub fn get_file_conflict(
+    repo_path: &str,
+    path: &str,
+) -> Result<Option<FileConflict>> {
+    scope_time!("get_file_conflict");
+
+    let repo = utils::repo(repo_path)?;
+
+    let (ancestor, ours, theirs) =
+        if let Some(entries) = conflict_entries(&repo, path)? {
+            entries
+        } else {
+            return Ok(None);
+        };
+
+    let base = blob_content(&repo, ancestor.as_ref())?;
+    let ours = blob_content(&repo, ours.as_ref())?;
+    let theirs = blob_content(&repo, theirs.as_ref())?;
+
+    let texts = [base, ours, theirs]
+        .iter()
+        .map(|c| {
+            String::from_utf8(c.clone().unwrap_or_default()).ok()
+        })
+        .collect::<Option<Vec<_>>>();
+
+    Ok(Some(match texts {
+        Some(texts) if !texts.iter().any(|t| t.contains('\0')) => {
+            FileConflict {
+                chunks: merge_chunks(
+                    &texts[0], &texts[1], &texts[2],
+                )?,
+                binary: false,
+            }
+        }
+        _ => FileConflict {
+            chunks: Vec::new(),
+            binary: true,
+        },
+    }))
+}
+
+/// writes `content` to the conflicted `path` and stages it,
+/// which marks the conflict resolved
+pub fn resolve_conflict(
+    repo_path: &str,
+    path: &str,
+    content: &str,
+) -> Result<()> {
+    scope_time!("resolve_conflict");
+
+    let repo = utils::repo(repo_path)?;
+
+    fs::write(work_dir(&repo).join(path), content)?;
+
+    let mut index = repo.index()?;
+    index.add_path(Path::new(path))?;
+    index.write()?;
+
+    Ok(())
+}
+
+/// resolves the conflicted `path` by taking the whole file of one side.
+/// if that side deleted the file it gets removed.
+pub fn resolve_conflict_side(
+    repo_path: &str,
+    path: &str,
+    side: ConflictResolution,
+) -> Result<()> {
+    scope_time!("resolve_conflict_side");
+
+    let repo = utils::repo(repo_path)?;
+
+    let (_, ours, theirs) = conflict_entries(&repo, path)?
+        .ok_or_else(|| {
+            Error::Generic(format!("'{}' is not conflicted", path))
+        })?;
+
+    let entry = match side {
+        ConflictResolution::Ours => ours,
+        ConflictResolution::Theirs => theirs,
+        ConflictResolution::Both => {
+            return Err(Error::Generic(
+                "only one side can be taken as a whole".to_string(),
+            ))
+        }
+    };
+
+    let file_path = work_dir(&repo).join(path);
+    let mut index = repo.index()?;
+
+    if let Some(content) = blob_content(&repo, entry.as_ref())? {
+        fs::write(file_path, content)?;
+        index.add_path(Path::new(path))?;
+    } else {
+        if file_path.exists() {
+            fs::remove_file(file_path)?;
+        }
+        index.remove_path(Path::new(path))?;
+    }
+
+    index.write()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch, merge_branch,
+        status::{get_status, StatusItemType, StatusType},
+        tests::{repo_init, write_commit},
+        MergeResult,
+    };
+
+    fn conflict(base: &str, ours: &str, theirs: &str) -> Vec<String> {
+        merge_chunks(base, ours, theirs)
+            .unwrap()
+            .into_iter()
+            .map(|c| match c {
+                ConflictChunk::Merged(l) => l.concat(),
+                ConflictChunk::Conflict { base, ours, theirs } => {
+                    format!(
+                        "<{}|{}={}>",
+                        ours.concat(),
+                        base.concat(),
+                        theirs.concat()
+                    )
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_chunks_clean() {
+        assert_eq!(
+            conflict("a\nb\nc\n", "A\nb\nc\n", "a\nb\nC\n"),
+            vec!["A\nb\nC\n"]
+        );
+        assert_eq!(
+            conflict("a\nb\n", "x\na\nb\n", "a\nb\ny\n"),
+            vec!["x\na\nb\ny\n"]
+        );
+        assert_eq!(
+            conflict("a\nb\nc\n", "a\nc\n", "a\nc\n"),
+            vec!["a\nc\n"]
+        );
+    }
+
+    #[test]
+    fn test_merge_chunks_conflict() {
+        assert_eq!(
+            conflict("a\nb\nc\n", "a\nB\nc\n", "a\nX\nc\n"),
+            vec!["a\n", "<B\n|b\n=X\n>", "c\n"]
+        );
+        assert_eq!(conflict("", "a\n", "b\n"), vec!["<a\n|=b\n>"]);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let c = FileConflict {
+            chunks: merge_chunks(
+                "1\na\n2\nb\n3\n",
+                "1\nA\n2\nB\n3\n",
+                "1\nX\n2\nY\n3\n",
+            )
+            .unwrap(),
+            binary: false,
+        };
+
+        assert_eq!(c.conflict_count(), 2);
+        assert_eq!(
+            c.resolve(&[
+                ConflictResolution::Ours,
+                ConflictResolution::Both
+            ]),
+            "1\nA\n2\nB\nY\n3\n"
+        );
+        assert_eq!(
+            c.resolve(&[
+                ConflictResolution::Theirs,
+                ConflictResolution::Ours
+            ]),
+            "1\nX\n2\nB\n3\n"
+        );
+    }
+
+    #[test]
+    fn test_resolve_in_repo() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.to_str().unwrap();
+
+        write_commit(root, "a.txt", "1\nbase\n2\n");
+        create_branch(repo_path, "feature").unwrap();
+        write_commit(root, "a.txt", "1\ntheirs\n2\n");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit(root, "a.txt", "1\nours\n2\n");
+
+        assert_eq!(
+            merge_branch(repo_path, "refs/heads/feature").unwrap(),
+            MergeResult::Conflict
+        );
+
+        assert_eq!(
+            get_file_conflict(repo_path, "b.txt").unwrap(),
+            None
+        );
+
+        let c =
+            get_file_conflict(repo_path, "a.txt").unwrap().unwrap();
+        assert_eq!(c.conflict_count(), 1);
+        assert_eq!(
+            c.chunks[1],
+            ConflictChunk::Conflict {
+                base: vec!["base\n".to_string()],
+                ours: vec!["ours\n".to_string()],
+                theirs: vec!["theirs\n".to_string()],
+            }
+        );
+
+        resolve_conflict(
+            repo_path,
+            "a.txt",
+            &c.resolve(&[ConflictResolution::Theirs]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "1\ntheirs\n2\n"
+        );
+        let status =
+            get_status(repo_path, StatusType::Stage, true).unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].status, StatusItemType::Modified);
+    }
+
+    #[test]
+    fn test_resolve_side_deleted() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.to_str().unwrap();
+
+        write_commit(root, "a.txt", "base");
+        create_branch(repo_path, "feature").unwrap();
+        write_commit(root, "a.txt", "theirs");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        crate::sync::stage_addremoved(repo_path, Path::new("a.txt"))
+            .unwrap();
+        fs::remove_file(root.join("a.txt")).unwrap();
+        crate::sync::commit(repo_path, "delete").unwrap();
+
+        assert_eq!(
+            merge_branch(repo_path, "refs/heads/feature").unwrap(),
+            MergeResult::Conflict
+        );
+
+        resolve_conflict_side(
+            repo_path,
+            "a.txt",
+            ConflictResolution::Ours,
+        )
+        .unwrap();
+
+        assert!(!root.join("a.txt").exists());
+        assert!(!repo.index().unwrap().has_conflicts());
+    }
+}
@@ -7,11 +7,149 @@ use super::{
 };
 use crate::{error::Error, error::Result, hash};
 use git2::{
-    Delta, Diff, DiffDelta, DiffFormat, DiffHunk, DiffOptions, Patch,
-    Repository,
+    Delta, Diff, DiffDelta, DiffFindOptions, DiffFormat, DiffHunk,
+    Patch, Repository,
 };
 use scopetime::scope_time;
-use std::{cell::RefCell, fs, path::Path, rc::Rc};
+use std::{
+    cell::RefCell, convert::TryFrom, fs, ops::Range, path::Path,
+    rc::Rc,
+};
+
+/// similarity in percent git uses to detect renames and copies
+/// by default
+pub const DEFAULT_SIMILARITY: u16 = 50;
+
+/// algorithm to compute the changed lines (`--diff-algorithm`)
+#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+pub enum DiffAlgorithm {
+    ///
+    Myers,
+    /// spends extra time to find the smallest diff
+    Minimal,
+    ///
+    Patience,
+}
+
+/// how file diffs get computed, like the options of `git diff`
+#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+pub struct DiffOptions {
+    /// ignores all whitespace (`-w`)
+    pub ignore_whitespace: bool,
+    /// ignores changes in the amount of whitespace (`-b`)
+    pub ignore_whitespace_change: bool,
+    /// ignores whitespace at the end of lines (`--ignore-space-at-eol`)
+    pub ignore_whitespace_eol: bool,
+    /// lines of context around changes (`-U`)
+    pub context: u32,
+    ///
+    pub algorithm: DiffAlgorithm,
+    /// similarity in percent to detect renames in commits (`-M`),
+    /// `None` disables it
+    pub rename_threshold: Option<u16>,
+    /// similarity in percent to detect copies in commits (`-C`),
+    /// `None` disables it
+    pub copy_threshold: Option<u16>,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            ignore_whitespace: false,
+            ignore_whitespace_change: false,
+            ignore_whitespace_eol: false,
+            context: 3,
+            algorithm: DiffAlgorithm::Myers,
+            rename_threshold: Some(DEFAULT_SIMILARITY),
+            copy_threshold: None,
+        }
+    }
+}
+
+impl DiffOptions {
+    /// defaults changed by `diff.context`, `diff.algorithm` and
+    /// `diff.renames` of the repo config
+    pub fn from_config(repo_path: &str) -> Result<Self> {
+        let repo = utils::repo(repo_path)?;
+        let config = repo.config()?;
+        let mut res = Self::default();
+
+        if let Ok(context) = config.get_i32("diff.context") {
+            res.context =
+                u32::try_from(context).unwrap_or(res.context);
+        }
+
+        if let Ok(algorithm) = config.get_string("diff.algorithm") {
+            res.algorithm = match algorithm.as_str() {
+                "minimal" => DiffAlgorithm::Minimal,
+                // libgit2 has no histogram diff, patience comes closest
+                "patience" | "histogram" => DiffAlgorithm::Patience,
+                _ => DiffAlgorithm::Myers,
+            };
+        }
+
+        if let Ok(renames) = config.get_bool("diff.renames") {
+            res.rename_threshold = if renames {
+                Some(DEFAULT_SIMILARITY)
+            } else {
+                None
+            };
+        } else if let Ok(renames) = config.get_string("diff.renames")
+        {
+            if renames == "copy" || renames == "copies" {
+                res.rename_threshold = Some(DEFAULT_SIMILARITY);
+                res.copy_threshold = Some(DEFAULT_SIMILARITY);
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// a diff ignoring whitespace cannot be applied to stage or
+    /// revert parts of it
+    pub const fn ignores_whitespace(&self) -> bool {
+        self.ignore_whitespace
+            || self.ignore_whitespace_change
+            || self.ignore_whitespace_eol
+    }
+
+    fn apply(&self, opt: &mut git2::DiffOptions) {
+        opt.ignore_whitespace(self.ignore_whitespace)
+            .ignore_whitespace_change(self.ignore_whitespace_change)
+            .ignore_whitespace_eol(self.ignore_whitespace_eol)
+            .context_lines(self.context)
+            .patience(self.algorithm == DiffAlgorithm::Patience)
+            .minimal(self.algorithm == DiffAlgorithm::Minimal);
+    }
+
+    fn find_options(&self) -> Option<DiffFindOptions> {
+        if self.rename_threshold.is_none()
+            && self.copy_threshold.is_none()
+        {
+            return None;
+        }
+
+        let mut find = DiffFindOptions::new();
+        if let Some(threshold) = self.rename_threshold {
+            find.renames(true).rename_threshold(threshold);
+        }
+        if let Some(threshold) = self.copy_threshold {
+            find.copies(true).copy_threshold(threshold);
+        }
+
+        Some(find)
+    }
+}
+
+/// applies `options` to the git2 options of a diff
+pub(crate) fn apply_options(
+    opt: &mut git2::DiffOptions,
+    options: Option<DiffOptions>,
+) {
+    if let Some(options) = options {
+        options.apply(opt);
+    }
+}
 
 /// type of diff of a single line
 #[derive(Copy, Clone, PartialEq, Hash, Debug)]
@@ -39,14 +177,29 @@ pub struct DiffLine {
     pub content: String,
     ///
     pub line_type: DiffLineType,
+    /// line number in the old file, `None` for added lines
+    pub old_lineno: Option<u32>,
+    /// line number in the new file, `None` for deleted lines
+    pub new_lineno: Option<u32>,
+    /// colours of the content by its syntax, empty if not highlighted
+    pub syntax: Vec<SyntaxSpan>,
+}
+
+/// byte range of `DiffLine::content` in a colour of the syntax theme
+#[derive(Default, Clone, Hash, Debug, PartialEq)]
+pub struct SyntaxSpan {
+    ///
+    pub range: Range<usize>,
+    /// rgb foreground colour
+    pub color: (u8, u8, u8),
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Hash)]
 pub(crate) struct HunkHeader {
-    old_start: u32,
-    old_lines: u32,
-    new_start: u32,
-    new_lines: u32,
+    pub(crate) old_start: u32,
+    pub(crate) old_lines: u32,
+    pub(crate) new_start: u32,
+    pub(crate) new_lines: u32,
 }
 
 impl From<DiffHunk<'_>> for HunkHeader {
@@ -89,12 +242,14 @@ pub(crate) fn get_diff_raw<'a>(
     p: &str,
     stage: bool,
     reverse: bool,
+    options: Option<DiffOptions>,
 ) -> Result<Diff<'a>> {
     // scope_time!("get_diff_raw");
 
-    let mut opt = DiffOptions::new();
+    let mut opt = git2::DiffOptions::new();
     opt.pathspec(p);
     opt.reverse(reverse);
+    apply_options(&mut opt, options);
 
     let diff = if stage {
         // diff against head
@@ -128,36 +283,90 @@ pub fn get_diff(
     repo_path: &str,
     p: String,
     stage: bool,
+    options: Option<DiffOptions>,
 ) -> Result<FileDiff> {
     scope_time!("get_diff");
 
     let repo = utils::repo(repo_path)?;
     let work_dir = work_dir(&repo);
-    let diff = get_diff_raw(&repo, &p, stage, false)?;
+    let diff = get_diff_raw(&repo, &p, stage, false, options)?;
 
-    raw_diff_to_file_diff(&diff, work_dir)
+    raw_diff_to_file_diff(&diff, work_dir, None)
 }
 
-/// returns diff of a specific file inside a commit
+/// returns diff of a specific file inside a commit, against the file
+/// it got renamed or copied from if `options` detect that
 /// see `get_commit_diff`
 pub fn get_diff_commit(
     repo_path: &str,
     id: CommitId,
     p: String,
+    options: Option<DiffOptions>,
 ) -> Result<FileDiff> {
     scope_time!("get_diff_commit");
 
     let repo = utils::repo(repo_path)?;
     let work_dir = work_dir(&repo);
-    let diff = get_commit_diff(&repo, id, Some(p))?;
+    let mut find = options.and_then(|o| o.find_options());
+
+    let mut pathspecs = vec![p.clone()];
+    if let Some(find) = &mut find {
+        if let Some(source) =
+            similar_source(&repo, id, &p, options, find)?
+        {
+            pathspecs.push(source);
+        }
+    }
+
+    let mut diff = get_commit_diff(&repo, id, &pathspecs, options)?;
+    if let Some(find) = &mut find {
+        diff.find_similar(Some(find))?;
+    }
+
+    // the source shows up as a change of its own for copies
+    let only = if pathspecs.len() > 1 {
+        Some(Path::new(&p))
+    } else {
+        None
+    };
 
-    raw_diff_to_file_diff(&diff, work_dir)
+    raw_diff_to_file_diff(&diff, work_dir, only)
 }
 
-///
+/// path that `p` got renamed or copied from in the commit
+fn similar_source(
+    repo: &Repository,
+    id: CommitId,
+    p: &str,
+    options: Option<DiffOptions>,
+    find: &mut DiffFindOptions,
+) -> Result<Option<String>> {
+    let mut diff = get_commit_diff(repo, id, &[], options)?;
+    diff.find_similar(Some(find))?;
+
+    let source = diff
+        .deltas()
+        .find(|delta| delta.new_file().path() == Some(Path::new(p)))
+        .filter(|delta| {
+            delta.status() == Delta::Renamed
+                || delta.status() == Delta::Copied
+        })
+        .and_then(|delta| {
+            delta
+                .old_file()
+                .path()
+                .and_then(Path::to_str)
+                .map(String::from)
+        });
+
+    Ok(source)
+}
+
+/// only lines of the file at `path` are taken if given
 fn raw_diff_to_file_diff<'a>(
     diff: &'a Diff,
     work_dir: &Path,
+    path: Option<&Path>,
 ) -> Result<FileDiff> {
     let res = Rc::new(RefCell::new(FileDiff::default()));
     {
@@ -179,6 +388,11 @@ fn raw_diff_to_file_diff<'a>(
         let mut put = |delta: DiffDelta,
                        hunk: Option<DiffHunk>,
                        line: git2::DiffLine| {
+            if path
+                .map_or(false, |p| delta.new_file().path() != Some(p))
+            {
+                return;
+            }
             {
                 let mut res = res_cell.borrow_mut();
                 res.sizes = (
@@ -212,66 +426,19 @@ fn raw_diff_to_file_diff<'a>(
                     content: String::from_utf8_lossy(line.content())
                         .to_string(),
                     line_type,
+                    old_lineno: line.old_lineno(),
+                    new_lineno: line.new_lineno(),
+                    syntax: Vec::new(),
                 };
 
                 current_lines.push(diff_line);
             }
         };
 
-        let new_file_diff = if diff.deltas().len() == 1 {
-            let delta: DiffDelta = diff
-                .deltas()
-                .next()
-                .expect("it's safe to unwrap here because we check first that diff.deltas has a single element");
-
-            if delta.status() == Delta::Untracked {
-                let relative_path =
-                    delta.new_file().path().ok_or_else(|| {
-                        Error::Generic(
-                            "new file path is unspecified."
-                                .to_string(),
-                        )
-                    })?;
-
-                let newfile_path = work_dir.join(relative_path);
-
-                if let Some(newfile_content) =
-                    new_file_content(&newfile_path)
-                {
-                    let mut patch = Patch::from_buffers(
-                        &[],
-                        None,
-                        newfile_content.as_slice(),
-                        Some(&newfile_path),
-                        None,
-                    )?;
-
-                    patch
-                    .print(&mut |delta, hunk:Option<DiffHunk>, line: git2::DiffLine| {
-                        put(delta,hunk,line);
-                        true
-                    })?;
-
-                    true
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        } else {
-            false
-        };
-
-        if !new_file_diff {
-            diff.print(
-                DiffFormat::Patch,
-                move |delta, hunk, line: git2::DiffLine| {
-                    put(delta, hunk, line);
-                    true
-                },
-            )?;
-        }
+        let new_file_diff =
+            foreach_line(diff, work_dir, |delta, hunk, line| {
+                put(delta, hunk, line)
+            })?;
 
         if !current_lines.is_empty() {
             adder(
@@ -288,6 +455,67 @@ fn raw_diff_to_file_diff<'a>(
     Ok(res.into_inner())
 }
 
+/// calls `f` for every line of the single file `diff`, an untracked
+/// file is diffed against an empty one to list all of its lines.
+/// returns true for an untracked file
+pub(crate) fn foreach_line<F>(
+    diff: &Diff,
+    work_dir: &Path,
+    mut f: F,
+) -> Result<bool>
+where
+    F: FnMut(DiffDelta, Option<DiffHunk>, git2::DiffLine),
+{
+    if diff.deltas().len() == 1 {
+        let delta: DiffDelta = diff
+            .deltas()
+            .next()
+            .expect("it's safe to unwrap here because we check first that diff.deltas has a single element");
+
+        if delta.status() == Delta::Untracked {
+            let relative_path =
+                delta.new_file().path().ok_or_else(|| {
+                    Error::Generic(
+                        "new file path is unspecified.".to_string(),
+                    )
+                })?;
+
+            let newfile_path = work_dir.join(relative_path);
+
+            if let Some(newfile_content) =
+                new_file_content(&newfile_path)
+            {
+                let mut patch = Patch::from_buffers(
+                    &[],
+                    None,
+                    newfile_content.as_slice(),
+                    Some(&newfile_path),
+                    None,
+                )?;
+
+                patch.print(&mut |delta,
+                                  hunk: Option<DiffHunk>,
+                                  line: git2::DiffLine| {
+                    f(delta, hunk, line);
+                    true
+                })?;
+
+                return Ok(true);
+            }
+        }
+    }
+
+    diff.print(
+        DiffFormat::Patch,
+        |delta, hunk, line: git2::DiffLine| {
+            f(delta, hunk, line);
+            true
+        },
+    )?;
+
+    Ok(false)
+}
+
 fn new_file_content(path: &Path) -> Option<Vec<u8>> {
     if let Ok(meta) = fs::symlink_metadata(path) {
         if meta.file_type().is_symlink() {
@@ -308,10 +536,12 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_diff, get_diff_commit};
+    use super::{
+        get_diff, get_diff_commit, DiffAlgorithm, DiffOptions,
+    };
     use crate::error::Result;
     use crate::sync::{
-        commit, stage_add_file,
+        commit, stage_add_file, stage_addremoved,
         status::{get_status, StatusType},
         tests::{get_statuses, repo_init, repo_init_empty},
     };
@@ -337,9 +567,13 @@ mod tests {
 
         assert_eq!(get_statuses(repo_path), (1, 0));
 
-        let diff =
-            get_diff(repo_path, "foo/bar.txt".to_string(), false)
-                .unwrap();
+        let diff = get_diff(
+            repo_path,
+            "foo/bar.txt".to_string(),
+            false,
+            None,
+        )
+        .unwrap();
 
         assert_eq!(diff.hunks.len(), 1);
         assert_eq!(diff.hunks[0].lines[1].content, "test\n");
@@ -369,6 +603,7 @@ mod tests {
             repo_path,
             String::from(file_path.to_str().unwrap()),
             true,
+            None,
         )
         .unwrap();
 
@@ -436,8 +671,9 @@ mod tests {
 
         assert_eq!(get_statuses(repo_path), (1, 1));
 
-        let res = get_diff(repo_path, "bar.txt".to_string(), false)
-            .unwrap();
+        let res =
+            get_diff(repo_path, "bar.txt".to_string(), false, None)
+                .unwrap();
 
         assert_eq!(res.hunks.len(), 2)
     }
@@ -460,6 +696,7 @@ mod tests {
             sub_path.to_str().unwrap(),
             String::from(file_path.to_str().unwrap()),
             false,
+            None,
         )
         .unwrap();
 
@@ -486,6 +723,7 @@ mod tests {
             repo_path,
             String::from(file_path.to_str().unwrap()),
             false,
+            None,
         )
         .unwrap();
 
@@ -510,6 +748,7 @@ mod tests {
             repo_path,
             String::from(file_path.to_str().unwrap()),
             false,
+            None,
         )
         .unwrap();
 
@@ -541,7 +780,8 @@ mod tests {
         let id = commit(repo_path, "").unwrap();
 
         let diff =
-            get_diff_commit(repo_path, id, String::new()).unwrap();
+            get_diff_commit(repo_path, id, String::new(), None)
+                .unwrap();
 
         dbg!(&diff);
         assert_eq!(diff.sizes, (1, 2));
@@ -549,4 +789,122 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_diff_options() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let lines = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        File::create(&root.join("f.txt"))
+            .unwrap()
+            .write_all(lines.as_bytes())
+            .unwrap();
+        stage_add_file(repo_path, Path::new("f.txt")).unwrap();
+        commit(repo_path, "lines").unwrap();
+
+        File::create(&root.join("f.txt"))
+            .unwrap()
+            .write_all(b"1\n2 \n3\n4\n5\n6\n7\n8\nnine\n")
+            .unwrap();
+
+        let diff = |options| {
+            get_diff(repo_path, String::from("f.txt"), false, options)
+                .unwrap()
+        };
+
+        assert_eq!(diff(None).hunks.len(), 1);
+        assert_eq!(diff(None).lines, 12);
+
+        let no_context = DiffOptions {
+            context: 0,
+            ..DiffOptions::default()
+        };
+        assert_eq!(diff(Some(no_context)).hunks.len(), 2);
+
+        let ignore_eol = DiffOptions {
+            ignore_whitespace_eol: true,
+            ..DiffOptions::default()
+        };
+        let res = diff(Some(ignore_eol));
+        assert_eq!(res.hunks.len(), 1);
+        assert!(res.hunks[0]
+            .lines
+            .iter()
+            .all(|l| l.content != "2 \n"));
+    }
+
+    #[test]
+    fn test_options_from_config() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert_eq!(
+            DiffOptions::from_config(repo_path).unwrap(),
+            DiffOptions::default()
+        );
+
+        let mut config = repo.config().unwrap();
+        config.set_i32("diff.context", 5).unwrap();
+        config.set_str("diff.algorithm", "histogram").unwrap();
+        config.set_str("diff.renames", "copies").unwrap();
+
+        let options = DiffOptions::from_config(repo_path).unwrap();
+        assert_eq!(options.context, 5);
+        assert_eq!(options.algorithm, DiffAlgorithm::Patience);
+        assert_eq!(options.copy_threshold, Some(50));
+
+        config.set_bool("diff.renames", false).unwrap();
+
+        let options = DiffOptions::from_config(repo_path).unwrap();
+        assert_eq!(options.rename_threshold, None);
+        assert_eq!(options.copy_threshold, None);
+    }
+
+    #[test]
+    fn test_rename_in_commit() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let content = "a\nb\nc\nd\ne\nf\n";
+        File::create(&root.join("old.txt"))
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+        stage_add_file(repo_path, Path::new("old.txt")).unwrap();
+        commit(repo_path, "add").unwrap();
+
+        fs::remove_file(root.join("old.txt")).unwrap();
+        File::create(&root.join("new.txt"))
+            .unwrap()
+            .write_all(b"a\nb\nc\nd\ne\nF\n")
+            .unwrap();
+        stage_addremoved(repo_path, Path::new("old.txt")).unwrap();
+        stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+        let id = commit(repo_path, "rename").unwrap();
+
+        let diff = |options| {
+            get_diff_commit(repo_path, id, "new.txt".into(), options)
+                .unwrap()
+        };
+
+        // all lines added without rename detection
+        let no_renames = DiffOptions {
+            rename_threshold: None,
+            ..DiffOptions::default()
+        };
+        assert_eq!(diff(Some(no_renames)).lines, 7);
+
+        let renamed = diff(Some(DiffOptions::default()));
+        let changed: Vec<&str> = renamed.hunks[0]
+            .lines
+            .iter()
+            .filter(|l| l.line_type != super::DiffLineType::None)
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(changed[1..], ["f\n", "F\n"]);
+    }
 }
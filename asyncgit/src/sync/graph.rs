@@ -0,0 +1,291 @@
+//! lanes of the commit graph drawn next to the log
+
+use super::{CommitId, CommitInfo};
+use crate::error::Result;
+use git2::Repository;
+
+/// amount of commits between two stored lane states
+const CHECKPOINT_INTERVAL: usize = 100;
+
+/// lanes waiting for the commit they continue with
+type Lanes = Vec<Option<CommitId>>;
+
+/// what a lane shows in the row of a commit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphLane {
+    /// no line
+    Empty,
+    /// the commit of the row
+    Commit,
+    /// a line passing the row
+    Line,
+    /// a lane ending in the commit, it got branched off here
+    Join,
+    /// a new lane starting for a further parent of the commit
+    Fork,
+    /// a passing lane that is a further parent of the commit
+    Merge,
+}
+
+/// graph of a single commit in the log
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphRow {
+    ///
+    pub lanes: Vec<GraphLane>,
+    /// index of the lane of the commit
+    pub commit: usize,
+}
+
+/// computes the graph lane by lane in log order. only the lane
+/// state every `CHECKPOINT_INTERVAL` commits is kept, rows get
+/// computed on demand for slices starting at such a checkpoint.
+/// the lanes are walked along with the log in `AsyncLog`
+#[derive(Default)]
+pub struct CommitGraph {
+    checkpoints: Vec<Lanes>,
+    lanes: Lanes,
+    processed: usize,
+}
+
+impl CommitGraph {
+    /// closest index at or before `index` a slice of rows
+    /// can start at
+    pub const fn slice_start(index: usize) -> usize {
+        index - index % CHECKPOINT_INTERVAL
+    }
+
+    /// amount of log entries walked so far
+    pub const fn processed(&self) -> usize {
+        self.processed
+    }
+
+    ///
+    pub fn clear(&mut self) {
+        self.checkpoints.clear();
+        self.lanes.clear();
+        self.processed = 0;
+    }
+
+    /// walks the lanes over `commits` (see `with_parents`), which
+    /// continue the log at `processed`
+    pub(crate) fn extend(
+        &mut self,
+        commits: &[(CommitId, Vec<CommitId>)],
+    ) {
+        for (id, parents) in commits {
+            self.push(*id, parents);
+        }
+    }
+
+    /// rows of `commits` which are the log entries from `start`
+    /// on, `start` has to be a `slice_start`. `None` if the lanes
+    /// were not walked up to `start` yet
+    pub fn rows(
+        &self,
+        start: usize,
+        commits: &[CommitInfo],
+    ) -> Option<Vec<GraphRow>> {
+        let mut lanes = self
+            .checkpoints
+            .get(start / CHECKPOINT_INTERVAL)?
+            .clone();
+
+        Some(
+            commits
+                .iter()
+                .map(|c| step(&mut lanes, c.id, &c.parents))
+                .collect(),
+        )
+    }
+
+    fn push(&mut self, id: CommitId, parents: &[CommitId]) {
+        if self.processed % CHECKPOINT_INTERVAL == 0 {
+            self.checkpoints.push(self.lanes.clone());
+        }
+
+        step(&mut self.lanes, id, parents);
+        self.processed += 1;
+    }
+}
+
+/// moves `lanes` past commit `id` returning its row
+fn step(
+    lanes: &mut Lanes,
+    id: CommitId,
+    parents: &[CommitId],
+) -> GraphRow {
+    let mut row = lanes
+        .iter()
+        .map(|lane| {
+            if lane.is_some() {
+                GraphLane::Line
+            } else {
+                GraphLane::Empty
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let col = lanes
+        .iter()
+        .position(|lane| *lane == Some(id))
+        .unwrap_or_else(|| free_lane(lanes, &mut row));
+
+    // further children of the commit end here
+    for (idx, lane) in lanes.iter_mut().enumerate() {
+        if idx != col && *lane == Some(id) {
+            *lane = None;
+            row[idx] = GraphLane::Join;
+        }
+    }
+
+    row[col] = GraphLane::Commit;
+    lanes[col] = parents.first().copied();
+
+    for parent in parents.iter().skip(1) {
+        match lanes.iter().position(|lane| *lane == Some(*parent)) {
+            Some(idx) if idx != col => row[idx] = GraphLane::Merge,
+            Some(_) => (),
+            None => {
+                let idx = free_lane(lanes, &mut row);
+                lanes[idx] = Some(*parent);
+                row[idx] = GraphLane::Fork;
+            }
+        }
+    }
+
+    while lanes.last().map_or(false, Option::is_none) {
+        lanes.pop();
+    }
+    while row.last() == Some(&GraphLane::Empty) {
+        row.pop();
+    }
+
+    GraphRow {
+        lanes: row,
+        commit: col,
+    }
+}
+
+/// pairs `ids` with their parents for `CommitGraph::extend`
+pub(crate) fn with_parents(
+    repo: &Repository,
+    ids: &[CommitId],
+) -> Result<Vec<(CommitId, Vec<CommitId>)>> {
+    ids.iter()
+        .map(|id| {
+            let parents = repo
+                .find_commit((*id).into())?
+                .parent_ids()
+                .map(CommitId::from)
+                .collect();
+
+            Ok((*id, parents))
+        })
+        .collect()
+}
+
+/// first lane unused in this row, a lane joined in this row
+/// is not reused to keep the row readable
+fn free_lane(lanes: &mut Lanes, row: &mut Vec<GraphLane>) -> usize {
+    row.iter()
+        .position(|lane| *lane == GraphLane::Empty)
+        .unwrap_or_else(|| {
+            lanes.push(None);
+            row.push(GraphLane::Empty);
+            row.len() - 1
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch, get_commits_info,
+        merge_branch,
+        tests::{repo_init, write_commit},
+        LogWalker,
+    };
+    use git2::Oid;
+    use GraphLane::{Commit, Empty, Fork, Join, Line, Merge};
+
+    fn id(n: u8) -> CommitId {
+        CommitId::new(Oid::from_bytes(&[n; 20]).unwrap())
+    }
+
+    #[test]
+    fn test_step_merge_and_branch() {
+        let mut lanes = Lanes::new();
+
+        // 4 merges 3 into 2, both branched off 1
+        let row = step(&mut lanes, id(4), &[id(2), id(3)]);
+        assert_eq!(row.lanes, vec![Commit, Fork]);
+
+        let row = step(&mut lanes, id(3), &[id(1)]);
+        assert_eq!(row.lanes, vec![Line, Commit]);
+        assert_eq!(row.commit, 1);
+
+        let row = step(&mut lanes, id(2), &[id(1)]);
+        assert_eq!(row.lanes, vec![Commit, Line]);
+
+        let row = step(&mut lanes, id(1), &[]);
+        assert_eq!(row.lanes, vec![Commit, Join]);
+        assert!(lanes.is_empty());
+    }
+
+    #[test]
+    fn test_step_merge_into_existing_lane() {
+        let mut lanes = vec![None, Some(id(1)), Some(id(2))];
+
+        let row = step(&mut lanes, id(3), &[id(9), id(2)]);
+        assert_eq!(row.lanes, vec![Commit, Line, Merge]);
+
+        // a new tip takes the first free lane
+        let mut lanes = vec![Some(id(1)), None, Some(id(2))];
+        let row = step(&mut lanes, id(5), &[id(1)]);
+        assert_eq!(row.lanes, vec![Line, Commit, Line]);
+        assert_eq!(
+            lanes,
+            vec![Some(id(1)), Some(id(1)), Some(id(2))]
+        );
+
+        let row = step(&mut lanes, id(1), &[]);
+        assert_eq!(row.lanes, vec![Commit, Join, Line]);
+        assert_eq!(lanes, vec![None, None, Some(id(2))]);
+
+        let row = step(&mut lanes, id(6), &[]);
+        assert_eq!(row.lanes, vec![Commit, Empty, Line]);
+    }
+
+    #[test]
+    fn test_rows_from_checkpoint() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit(root, "a.txt", "a");
+        create_branch(repo_path, "feature").unwrap();
+        write_commit(root, "b.txt", "b");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit(root, "c.txt", "c");
+        merge_branch(repo_path, "refs/heads/feature").unwrap();
+
+        let mut ids = Vec::new();
+        LogWalker::new(&repo).read(&mut ids, 100).unwrap();
+
+        let mut graph = CommitGraph::default();
+        assert!(graph.rows(0, &[]).is_none());
+
+        graph.extend(&with_parents(&repo, &ids).unwrap());
+        assert_eq!(graph.processed(), ids.len());
+
+        let commits = get_commits_info(repo_path, &ids, 10).unwrap();
+        let rows = graph
+            .rows(CommitGraph::slice_start(0), &commits)
+            .unwrap();
+
+        assert_eq!(rows.len(), ids.len());
+        assert!(rows.iter().any(|r| r.lanes.contains(&Fork)));
+        assert!(rows.iter().any(|r| r.lanes.contains(&Join)));
+        assert_eq!(rows.last().unwrap().lanes, vec![Commit]);
+    }
+}
@@ -1,20 +1,84 @@
-use super::utils::{repo, work_dir};
+use super::{
+    utils::{repo, work_dir},
+    CommitId,
+};
 use crate::error::{Error, Result};
+use git2::Repository;
 use scopetime::scope_time;
 use std::{
-    fs::File,
+    fs::{self, File},
     io::{Read, Write},
     path::{Path, PathBuf},
-    process::Command,
+    process::{Command, Stdio},
 };
 
-const HOOK_POST_COMMIT: &str = ".git/hooks/post-commit";
-const HOOK_COMMIT_MSG: &str = ".git/hooks/commit-msg";
-const HOOK_COMMIT_MSG_TEMP_FILE: &str = ".git/COMMIT_EDITMSG";
+const HOOK_PRE_COMMIT: &str = "pre-commit";
+const HOOK_PREPARE_COMMIT_MSG: &str = "prepare-commit-msg";
+const HOOK_COMMIT_MSG: &str = "commit-msg";
+const HOOK_POST_COMMIT: &str = "post-commit";
+const HOOK_PRE_PUSH: &str = "pre-push";
+const HOOK_POST_CHECKOUT: &str = "post-checkout";
+const HOOK_POST_MERGE: &str = "post-merge";
+const HOOK_COMMIT_MSG_TEMP_FILE: &str = "COMMIT_EDITMSG";
+
+/// where the message passed to `hooks_prepare_commit_msg` comes from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrepareCommitMsgSource {
+    /// a fresh commit with an empty message
+    Message,
+    /// the message of a merge in progress
+    Merge,
+    /// the message of an existing commit, e.g. when amending
+    Commit(CommitId),
+}
+
+/// this hook is documented here https://git-scm.com/docs/githooks#_pre_commit
+/// a failing hook aborts the commit
+pub fn hooks_pre_commit(repo_path: &str) -> Result<HookResult> {
+    scope_time!("hooks_pre_commit");
+
+    let repo = repo(repo_path)?;
+
+    Hook::new(&repo, HOOK_PRE_COMMIT)?.run(&[], "")
+}
+
+/// this hook is documented here https://git-scm.com/docs/githooks#_prepare_commit_msg
+/// it is passed the same temp file as `hooks_commit_msg` and the
+/// source of `msg`, which is replaced by the possibly altered message
+pub fn hooks_prepare_commit_msg(
+    repo_path: &str,
+    source: PrepareCommitMsgSource,
+    msg: &mut String,
+) -> Result<HookResult> {
+    scope_time!("hooks_prepare_commit_msg");
+
+    let repo = repo(repo_path)?;
+    let hook = Hook::new(&repo, HOOK_PREPARE_COMMIT_MSG)?;
+
+    if !hook.runable() {
+        return Ok(HookResult::Ok);
+    }
+
+    let commit;
+    let args: &[&str] = match source {
+        PrepareCommitMsgSource::Message => &["message"],
+        PrepareCommitMsgSource::Merge => &["merge"],
+        PrepareCommitMsgSource::Commit(id) => {
+            commit = id.to_string();
+            &["commit", commit.as_str()]
+        }
+    };
+
+    with_msg_file(&repo, msg, |file| {
+        let mut file_args = vec![file];
+        file_args.extend_from_slice(args);
+        hook.run(&file_args, "")
+    })
+}
 
 /// this hook is documented here https://git-scm.com/docs/githooks#_commit_msg
 /// we use the same convention as other git clients to create a temp file containing
-/// the commit message at `.git/COMMIT_EDITMSG` and pass it's relative path as the only
+/// the commit message at `.git/COMMIT_EDITMSG` and pass it's path as the only
 /// parameter to the hook script.
 pub fn hooks_commit_msg(
     repo_path: &str,
@@ -22,60 +86,124 @@ pub fn hooks_commit_msg(
 ) -> Result<HookResult> {
     scope_time!("hooks_commit_msg");
 
-    let work_dir = work_dir_as_string(repo_path)?;
-
-    if hook_runable(work_dir.as_str(), HOOK_COMMIT_MSG) {
-        let temp_file = Path::new(work_dir.as_str())
-            .join(HOOK_COMMIT_MSG_TEMP_FILE);
-        File::create(&temp_file)?.write_all(msg.as_bytes())?;
-
-        let res = run_hook(
-            work_dir.as_str(),
-            HOOK_COMMIT_MSG,
-            &[HOOK_COMMIT_MSG_TEMP_FILE],
-        );
-
-        // load possibly altered msg
-        msg.clear();
-        File::open(temp_file)?.read_to_string(msg)?;
+    let repo = repo(repo_path)?;
+    let hook = Hook::new(&repo, HOOK_COMMIT_MSG)?;
 
-        Ok(res)
-    } else {
-        Ok(HookResult::Ok)
+    if !hook.runable() {
+        return Ok(HookResult::Ok);
     }
+
+    with_msg_file(&repo, msg, |file| hook.run(&[file], ""))
 }
 
 ///
 pub fn hooks_post_commit(repo_path: &str) -> Result<HookResult> {
     scope_time!("hooks_post_commit");
 
-    let work_dir = work_dir_as_string(repo_path)?;
-    let work_dir_str = work_dir.as_str();
+    let repo = repo(repo_path)?;
 
-    if hook_runable(work_dir_str, HOOK_POST_COMMIT) {
-        Ok(run_hook(work_dir_str, HOOK_POST_COMMIT, &[]))
-    } else {
-        Ok(HookResult::Ok)
-    }
+    Hook::new(&repo, HOOK_POST_COMMIT)?.run(&[], "")
 }
 
-fn work_dir_as_string(repo_path: &str) -> Result<String> {
+/// this hook is documented here https://git-scm.com/docs/githooks#_pre_push
+/// `updates` are the lines git passes on stdin:
+/// `<local ref> <local sha> <remote ref> <remote sha>`
+pub(crate) fn hooks_pre_push(
+    repo: &Repository,
+    remote: &str,
+    url: &str,
+    updates: &str,
+) -> Result<HookResult> {
+    scope_time!("hooks_pre_push");
+
+    Hook::new(repo, HOOK_PRE_PUSH)?.run(&[remote, url], updates)
+}
+
+/// this hook is documented here https://git-scm.com/docs/githooks#_post_checkout
+/// run after switching branches, its result cannot undo the checkout
+pub fn hooks_post_checkout(
+    repo_path: &str,
+    previous: CommitId,
+    new: CommitId,
+) -> Result<HookResult> {
+    scope_time!("hooks_post_checkout");
+
     let repo = repo(repo_path)?;
-    work_dir(&repo)
-        .to_str()
-        .map(|s| s.to_string())
-        .ok_or_else(|| {
-            Error::Generic(
-                "workdir contains invalid utf8".to_string(),
-            )
-        })
+
+    Hook::new(&repo, HOOK_POST_CHECKOUT)?.run(
+        &[
+            previous.to_string().as_str(),
+            new.to_string().as_str(),
+            // branch checkout as opposed to a file checkout
+            "1",
+        ],
+        "",
+    )
 }
 
-fn hook_runable(path: &str, hook: &str) -> bool {
-    let path = Path::new(path);
-    let path = path.join(hook);
+/// this hook is documented here https://git-scm.com/docs/githooks#_post_merge
+/// run after a successful merge or fast-forward
+pub fn hooks_post_merge(
+    repo_path: &str,
+    squash: bool,
+) -> Result<HookResult> {
+    scope_time!("hooks_post_merge");
+
+    let repo = repo(repo_path)?;
 
-    path.exists() && is_executable(path)
+    Hook::new(&repo, HOOK_POST_MERGE)?
+        .run(&[if squash { "1" } else { "0" }], "")
+}
+
+/// writes `msg` to `.git/COMMIT_EDITMSG`, runs `f` with the path
+/// of that file and loads the possibly altered msg back
+fn with_msg_file<F>(
+    repo: &Repository,
+    msg: &mut String,
+    f: F,
+) -> Result<HookResult>
+where
+    F: FnOnce(&str) -> Result<HookResult>,
+{
+    let temp_file = repo.path().join(HOOK_COMMIT_MSG_TEMP_FILE);
+    File::create(&temp_file)?.write_all(msg.as_bytes())?;
+
+    let res = f(path_as_str(&temp_file)?)?;
+
+    msg.clear();
+    File::open(temp_file)?.read_to_string(msg)?;
+
+    Ok(res)
+}
+
+/// directory hooks are looked up in: `core.hooksPath` if set,
+/// relative to the workdir like git does, otherwise `hooks` in the
+/// common git dir shared by all worktrees of the repo
+fn hooks_dir(repo: &Repository) -> Result<PathBuf> {
+    if let Ok(path) = repo.config()?.get_path("core.hooksPath") {
+        return Ok(work_dir(repo).join(path));
+    }
+
+    Ok(common_dir(repo)?.join("hooks"))
+}
+
+/// a linked worktree has its own git dir in
+/// `.git/worktrees/<name>` that names the main one in `commondir`
+fn common_dir(repo: &Repository) -> Result<PathBuf> {
+    let git_dir = repo.path();
+
+    if repo.is_worktree() {
+        let common = fs::read_to_string(git_dir.join("commondir"))?;
+        Ok(git_dir.join(common.trim()))
+    } else {
+        Ok(git_dir.to_path_buf())
+    }
+}
+
+fn path_as_str(path: &Path) -> Result<&str> {
+    path.to_str().ok_or_else(|| {
+        Error::Generic("hook path contains invalid utf8".to_string())
+    })
 }
 
 ///
@@ -87,48 +215,69 @@ pub enum HookResult {
     NotOk(String),
 }
 
-/// this function calls hook scripts based on conventions documented here
-/// https://git-scm.com/docs/githooks
-fn run_hook(
-    path: &str,
-    hook_script: &str,
-    args: &[&str],
-) -> HookResult {
-    let mut bash_args = vec![hook_script.to_string()];
-    bash_args.extend_from_slice(
-        &args
-            .iter()
-            .map(|x| (*x).to_string())
-            .collect::<Vec<String>>(),
-    );
-
-    let output = Command::new("bash")
-        .args(bash_args)
-        .current_dir(path)
-        // This call forces Command to handle the Path environment correctly on windows,
-        // the specific env set here does not matter
-        // see https://github.com/rust-lang/rust/issues/37519
-        .env(
-            "DUMMY_ENV_TO_FIX_WINDOWS_CMD_RUNS",
-            "FixPathHandlingOnWindows",
-        )
-        .output();
+/// a hook script of a repo and the workdir it runs in
+struct Hook<'a> {
+    script: PathBuf,
+    work_dir: &'a Path,
+}
 
-    let output = output.expect("general hook error");
+impl<'a> Hook<'a> {
+    fn new(repo: &'a Repository, name: &str) -> Result<Self> {
+        Ok(Self {
+            script: hooks_dir(repo)?.join(name),
+            work_dir: work_dir(repo),
+        })
+    }
 
-    if output.status.success() {
-        HookResult::Ok
-    } else {
-        let err = String::from_utf8_lossy(&output.stderr);
-        let out = String::from_utf8_lossy(&output.stdout);
-        let formatted = format!("{}{}", out, err);
+    fn runable(&self) -> bool {
+        self.script.exists() && is_executable(&self.script)
+    }
+
+    /// this function calls hook scripts based on conventions documented here
+    /// https://git-scm.com/docs/githooks
+    /// a missing or non executable hook counts as success
+    fn run(&self, args: &[&str], stdin: &str) -> Result<HookResult> {
+        if !self.runable() {
+            return Ok(HookResult::Ok);
+        }
+
+        let mut child = Command::new("bash")
+            .arg(&self.script)
+            .args(args)
+            .current_dir(self.work_dir)
+            // This call forces Command to handle the Path environment correctly on windows,
+            // the specific env set here does not matter
+            // see https://github.com/rust-lang/rust/issues/37519
+            .env(
+                "DUMMY_ENV_TO_FIX_WINDOWS_CMD_RUNS",
+                "FixPathHandlingOnWindows",
+            )
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        if let Some(mut pipe) = child.stdin.take() {
+            // a hook is free to exit without reading its input
+            pipe.write_all(stdin.as_bytes()).ok();
+        }
 
-        HookResult::NotOk(formatted)
+        let output = child.wait_with_output()?;
+
+        if output.status.success() {
+            Ok(HookResult::Ok)
+        } else {
+            let err = String::from_utf8_lossy(&output.stderr);
+            let out = String::from_utf8_lossy(&output.stdout);
+            let formatted = format!("{}{}", out, err);
+
+            Ok(HookResult::NotOk(formatted))
+        }
     }
 }
 
 #[cfg(not(windows))]
-fn is_executable(path: PathBuf) -> bool {
+fn is_executable(path: &Path) -> bool {
     use std::os::unix::fs::PermissionsExt;
     let metadata = match path.metadata() {
         Ok(metadata) => metadata,
@@ -142,15 +291,18 @@ fn is_executable(path: PathBuf) -> bool {
 #[cfg(windows)]
 /// windows does not consider bash scripts to be executable so we consider everything
 /// to be executable (which is not far from the truth for windows platform.)
-fn is_executable(_: PathBuf) -> bool {
+fn is_executable(_: &Path) -> bool {
     true
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sync::tests::repo_init;
-    use std::fs::{self, File};
+    use crate::sync::{
+        tests::{debug_cmd_print, repo_init},
+        utils::get_head,
+    };
+    use tempfile::TempDir;
 
     #[test]
     fn test_smoke() {
@@ -168,8 +320,17 @@ mod tests {
         assert_eq!(res, HookResult::Ok);
     }
 
-    fn create_hook(path: &Path, hook_path: &str, hook_script: &[u8]) {
-        File::create(&path.join(hook_path))
+    fn create_hook(
+        repo: &Repository,
+        hook: &str,
+        hook_script: &[u8],
+    ) {
+        create_hook_in(&hooks_dir(repo).unwrap(), hook, hook_script);
+    }
+
+    fn create_hook_in(dir: &Path, hook: &str, hook_script: &[u8]) {
+        fs::create_dir_all(dir).unwrap();
+        File::create(dir.join(hook))
             .unwrap()
             .write_all(hook_script)
             .unwrap();
@@ -177,8 +338,8 @@ mod tests {
         #[cfg(not(windows))]
         {
             Command::new("chmod")
-                .args(&["+x", hook_path])
-                .current_dir(path)
+                .args(["+x", hook])
+                .current_dir(dir)
                 .output()
                 .unwrap();
         }
@@ -194,7 +355,7 @@ mod tests {
 exit 0
         ";
 
-        create_hook(root, HOOK_COMMIT_MSG, hook);
+        create_hook(&repo, HOOK_COMMIT_MSG, hook);
 
         let mut msg = String::from("test");
         let res = hooks_commit_msg(repo_path, &mut msg).unwrap();
@@ -216,7 +377,7 @@ echo 'rejected'
 exit 1
         ";
 
-        create_hook(root, HOOK_COMMIT_MSG, hook);
+        create_hook(&repo, HOOK_COMMIT_MSG, hook);
 
         let mut msg = String::from("test");
         let res = hooks_commit_msg(repo_path, &mut msg).unwrap();
@@ -241,7 +402,7 @@ echo 'rejected'
 exit 1
         ";
 
-        create_hook(root, HOOK_COMMIT_MSG, hook);
+        create_hook(&repo, HOOK_COMMIT_MSG, hook);
 
         let subfolder = root.join("foo/");
         fs::create_dir_all(&subfolder).unwrap();
@@ -270,7 +431,7 @@ echo 'msg' > $1
 exit 0
         ";
 
-        create_hook(root, HOOK_COMMIT_MSG, hook);
+        create_hook(&repo, HOOK_COMMIT_MSG, hook);
 
         let mut msg = String::from("test");
         let res = hooks_commit_msg(repo_path, &mut msg).unwrap();
@@ -289,7 +450,7 @@ echo 'rejected'
 exit 1
         ";
 
-        create_hook(root, HOOK_POST_COMMIT, hook);
+        create_hook(&repo, HOOK_POST_COMMIT, hook);
 
         let subfolder = root.join("foo/");
         fs::create_dir_all(&subfolder).unwrap();
@@ -302,4 +463,193 @@ exit 1
             HookResult::NotOk(String::from("rejected\n"))
         );
     }
+
+    #[test]
+    fn test_pre_commit_reject() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let hook = b"#!/bin/sh
+echo 'lint failed' >&2
+exit 1
+        ";
+
+        create_hook(&repo, HOOK_PRE_COMMIT, hook);
+
+        let res = hooks_pre_commit(repo_path).unwrap();
+
+        assert_eq!(
+            res,
+            HookResult::NotOk(String::from("lint failed\n"))
+        );
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_seeds_msg() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let hook = b"#!/bin/sh
+echo \"$2 $3\" >> $1
+exit 0
+        ";
+
+        create_hook(&repo, HOOK_PREPARE_COMMIT_MSG, hook);
+
+        let mut msg = String::new();
+        let res = hooks_prepare_commit_msg(
+            repo_path,
+            PrepareCommitMsgSource::Message,
+            &mut msg,
+        )
+        .unwrap();
+
+        assert_eq!(res, HookResult::Ok);
+        assert_eq!(msg, String::from("message \n"));
+
+        let head = get_head(repo_path).unwrap();
+        let mut msg = String::from("amend\n");
+        hooks_prepare_commit_msg(
+            repo_path,
+            PrepareCommitMsgSource::Commit(head),
+            &mut msg,
+        )
+        .unwrap();
+
+        assert_eq!(
+            msg,
+            format!("amend\ncommit {}\n", head.to_string())
+        );
+    }
+
+    #[test]
+    fn test_hooks_path() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let hook = b"#!/bin/sh
+echo 'rejected'
+exit 1
+        ";
+
+        // the default location is ignored once `core.hooksPath` is set
+        create_hook(&repo, HOOK_POST_COMMIT, hook);
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", "my-hooks")
+            .unwrap();
+
+        assert_eq!(
+            hooks_post_commit(repo_path).unwrap(),
+            HookResult::Ok
+        );
+
+        create_hook_in(
+            &root.join("my-hooks"),
+            HOOK_POST_COMMIT,
+            hook,
+        );
+
+        let subfolder = root.join("foo/");
+        fs::create_dir_all(&subfolder).unwrap();
+
+        assert_eq!(
+            hooks_post_commit(subfolder.to_str().unwrap()).unwrap(),
+            HookResult::NotOk(String::from("rejected\n"))
+        );
+    }
+
+    #[test]
+    fn test_hooks_in_linked_worktree() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let worktree_td = TempDir::new().unwrap();
+        let worktree = worktree_td.path().join("linked");
+        let worktree_path = worktree.to_str().unwrap();
+        debug_cmd_print(
+            repo_path,
+            &format!("git worktree add -b linked {}", worktree_path),
+        );
+        assert!(worktree.join(".git").is_file());
+
+        let hook = b"#!/bin/sh
+pwd
+exit 1
+        ";
+
+        create_hook(&repo, HOOK_PRE_COMMIT, hook);
+
+        let res = hooks_pre_commit(worktree_path).unwrap();
+
+        assert_eq!(
+            res,
+            HookResult::NotOk(format!(
+                "{}\n",
+                worktree.canonicalize().unwrap().to_str().unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_pre_push_reads_updates() {
+        let (_td, repo) = repo_init().unwrap();
+
+        let hook = b"#!/bin/sh
+read local_ref local_sha remote_ref remote_sha
+echo \"$1 $2 $local_ref $remote_ref\"
+exit 1
+        ";
+
+        create_hook(&repo, HOOK_PRE_PUSH, hook);
+
+        let res = hooks_pre_push(
+            &repo,
+            "origin",
+            "url",
+            "refs/heads/master 1 refs/heads/main 0\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            res,
+            HookResult::NotOk(String::from(
+                "origin url refs/heads/master refs/heads/main\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_post_checkout_and_merge_args() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let hook = b"#!/bin/sh
+echo \"$@\"
+exit 1
+        ";
+
+        create_hook(&repo, HOOK_POST_CHECKOUT, hook);
+        create_hook(&repo, HOOK_POST_MERGE, hook);
+
+        let head = get_head(repo_path).unwrap();
+
+        assert_eq!(
+            hooks_post_checkout(repo_path, head, head).unwrap(),
+            HookResult::NotOk(format!(
+                "{} {} 1\n",
+                head.to_string(),
+                head.to_string()
+            ))
+        );
+        assert_eq!(
+            hooks_post_merge(repo_path, false).unwrap(),
+            HookResult::NotOk(String::from("0\n"))
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use super::{
-    diff::{get_diff_raw, HunkHeader},
+    diff::{get_diff_raw, DiffOptions, HunkHeader},
     utils::repo,
 };
 use crate::{
@@ -14,12 +14,14 @@ pub fn stage_hunk(
     repo_path: &str,
     file_path: String,
     hunk_hash: u64,
+    options: Option<DiffOptions>,
 ) -> Result<()> {
     scope_time!("stage_hunk");
 
     let repo = repo(repo_path)?;
 
-    let diff = get_diff_raw(&repo, &file_path, false, false)?;
+    let diff =
+        get_diff_raw(&repo, &file_path, false, false, options)?;
 
     let mut opt = ApplyOptions::new();
     opt.hunk_callback(|hunk| {
@@ -38,12 +40,14 @@ pub fn reset_hunk(
     repo_path: &str,
     file_path: String,
     hunk_hash: u64,
+    options: Option<DiffOptions>,
 ) -> Result<()> {
     scope_time!("reset_hunk");
 
     let repo = repo(repo_path)?;
 
-    let diff = get_diff_raw(&repo, &file_path, false, false)?;
+    let diff =
+        get_diff_raw(&repo, &file_path, false, false, options)?;
 
     let hunk_index = find_hunk_index(&diff, hunk_hash);
     if let Some(hunk_index) = hunk_index {
@@ -55,7 +59,8 @@ pub fn reset_hunk(
             res
         });
 
-        let diff = get_diff_raw(&repo, &file_path, false, true)?;
+        let diff =
+            get_diff_raw(&repo, &file_path, false, true, options)?;
 
         repo.apply(&diff, ApplyLocation::WorkDir, Some(&mut opt))?;
 
@@ -96,12 +101,13 @@ pub fn unstage_hunk(
     repo_path: &str,
     file_path: String,
     hunk_hash: u64,
+    options: Option<DiffOptions>,
 ) -> Result<bool> {
     scope_time!("revert_hunk");
 
     let repo = repo(repo_path)?;
 
-    let diff = get_diff_raw(&repo, &file_path, true, false)?;
+    let diff = get_diff_raw(&repo, &file_path, true, false, options)?;
     let diff_count_positive = diff.deltas().len();
 
     let hunk_index = find_hunk_index(&diff, hunk_hash);
@@ -109,7 +115,7 @@ pub fn unstage_hunk(
         return Err(Error::Generic("hunk not found".to_string()));
     }
 
-    let diff = get_diff_raw(&repo, &file_path, true, true)?;
+    let diff = get_diff_raw(&repo, &file_path, true, true, options)?;
 
     if diff.deltas().len() != diff_count_positive {
         return Err(Error::Generic(format!(
@@ -172,12 +178,14 @@ mod tests {
             sub_path.to_str().unwrap(),
             String::from(file_path.to_str().unwrap()),
             false,
+            None,
         )?;
 
         assert!(reset_hunk(
             repo_path,
             String::from(file_path.to_str().unwrap()),
             diff.hunks[0].header_hash,
+            None,
         )
         .is_err());
 
@@ -0,0 +1,381 @@
+//! sync git api for staging, unstaging and resetting single lines
+
+use super::{
+    diff::{foreach_line, get_diff_raw, DiffOptions, HunkHeader},
+    utils::{repo, work_dir},
+};
+use crate::{
+    error::{Error, Result},
+    hash,
+};
+use git2::{Index, IndexEntry, IndexTime, Oid, Repository};
+use scopetime::scope_time;
+use std::{fs, path::Path};
+
+/// position of a single line of a `FileDiff`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiffLinePosition {
+    /// `header_hash` of the hunk containing the line
+    pub hunk: u64,
+    /// index of the line in `Hunk::lines`, the header being 0
+    pub line: usize,
+}
+
+/// line of a diff as given by git2 and whether it was selected
+struct PatchLine {
+    origin: char,
+    content: Vec<u8>,
+    selected: bool,
+}
+
+struct PatchHunk {
+    header: HunkHeader,
+    lines: Vec<PatchLine>,
+}
+
+/// stages the selected `lines` of the workdir diff of `file_path`
+pub fn stage_lines(
+    repo_path: &str,
+    file_path: &str,
+    lines: &[DiffLinePosition],
+    options: Option<DiffOptions>,
+) -> Result<()> {
+    scope_time!("stage_lines");
+
+    let repo = repo(repo_path)?;
+    let hunks =
+        selected_hunks(&repo, file_path, false, lines, options)?;
+
+    let mut index = repo.index()?;
+    let old = index_content(&repo, &index, file_path)?;
+    let new = apply_selection(&old, &hunks, false)?;
+
+    write_to_index(&mut index, file_path, &new)
+}
+
+/// unstages the selected `lines` of the staged diff of `file_path`
+pub fn unstage_lines(
+    repo_path: &str,
+    file_path: &str,
+    lines: &[DiffLinePosition],
+    options: Option<DiffOptions>,
+) -> Result<()> {
+    scope_time!("unstage_lines");
+
+    let repo = repo(repo_path)?;
+    let hunks =
+        selected_hunks(&repo, file_path, true, lines, options)?;
+
+    let mut index = repo.index()?;
+    let staged = index_content(&repo, &index, file_path)?;
+    let new = apply_selection(&staged, &hunks, true)?;
+
+    write_to_index(&mut index, file_path, &new)
+}
+
+/// reverts the selected `lines` of the workdir diff of `file_path`
+/// in the workdir, the changes are lost
+pub fn reset_lines(
+    repo_path: &str,
+    file_path: &str,
+    lines: &[DiffLinePosition],
+    options: Option<DiffOptions>,
+) -> Result<()> {
+    scope_time!("reset_lines");
+
+    let repo = repo(repo_path)?;
+    let hunks =
+        selected_hunks(&repo, file_path, false, lines, options)?;
+
+    let path = work_dir(&repo).join(file_path);
+    let current = fs::read(&path)?;
+    let new = apply_selection(&current, &hunks, true)?;
+
+    fs::write(path, new)?;
+
+    Ok(())
+}
+
+fn selected_hunks(
+    repo: &Repository,
+    file_path: &str,
+    stage: bool,
+    lines: &[DiffLinePosition],
+    options: Option<DiffOptions>,
+) -> Result<Vec<PatchHunk>> {
+    let diff = get_diff_raw(repo, file_path, stage, false, options)?;
+
+    let mut hunks: Vec<PatchHunk> = Vec::new();
+    foreach_line(&diff, work_dir(repo), |_, hunk, line| {
+        if let Some(hunk) = hunk {
+            let header = HunkHeader::from(hunk);
+
+            if hunks.last().map_or(true, |h| h.header != header) {
+                hunks.push(PatchHunk {
+                    header,
+                    lines: Vec::new(),
+                });
+            }
+
+            if let Some(current) = hunks.last_mut() {
+                let position = DiffLinePosition {
+                    hunk: hash(&header),
+                    line: current.lines.len(),
+                };
+
+                current.lines.push(PatchLine {
+                    origin: line.origin(),
+                    content: line.content().to_vec(),
+                    selected: lines.contains(&position),
+                });
+            }
+        }
+    })?;
+
+    if !hunks
+        .iter()
+        .flat_map(|h| h.lines.iter())
+        .any(|l| l.selected)
+    {
+        return Err(Error::Generic("lines not found".to_string()));
+    }
+
+    Ok(hunks)
+}
+
+/// applies the selected lines of `hunks` to `base`, the old side of
+/// the diff. `reverse` instead reverts them from `base` being the
+/// new side of the diff
+fn apply_selection(
+    base: &[u8],
+    hunks: &[PatchHunk],
+    reverse: bool,
+) -> Result<Vec<u8>> {
+    // lines of `base` are kept unless selected, lines of the other
+    // side are only taken if selected
+    let (base_origin, other_origin) =
+        if reverse { ('+', '-') } else { ('-', '+') };
+
+    let base_lines: Vec<&[u8]> =
+        base.split_inclusive(|b| *b == b'\n').collect();
+    let mismatch =
+        || Error::Generic("diff does not match file".to_string());
+
+    let mut res = Vec::with_capacity(base.len());
+    let mut pos = 0;
+
+    for hunk in hunks {
+        let (start, len) = if reverse {
+            (hunk.header.new_start, hunk.header.new_lines)
+        } else {
+            (hunk.header.old_start, hunk.header.old_lines)
+        };
+        // an empty side starts after the line it is inserted behind
+        let hunk_pos =
+            if len == 0 { start } else { start - 1 } as usize;
+
+        if hunk_pos < pos || hunk_pos > base_lines.len() {
+            return Err(mismatch());
+        }
+
+        for line in &base_lines[pos..hunk_pos] {
+            push_line(&mut res, line);
+        }
+        pos = hunk_pos;
+
+        for line in &hunk.lines {
+            let origin = line.origin;
+
+            if origin == ' ' || origin == base_origin {
+                let current =
+                    base_lines.get(pos).ok_or_else(mismatch)?;
+                if *current != line.content.as_slice() {
+                    return Err(mismatch());
+                }
+                pos += 1;
+
+                if origin == ' ' || !line.selected {
+                    push_line(&mut res, current);
+                }
+            } else if origin == other_origin && line.selected {
+                push_line(&mut res, &line.content);
+            }
+        }
+    }
+
+    for line in &base_lines[pos..] {
+        push_line(&mut res, line);
+    }
+
+    Ok(res)
+}
+
+/// appends `line`, ending a previous last line without newline first
+fn push_line(res: &mut Vec<u8>, line: &[u8]) {
+    if res.last().map_or(false, |last| *last != b'\n') {
+        res.push(b'\n');
+    }
+    res.extend_from_slice(line);
+}
+
+fn index_content(
+    repo: &Repository,
+    index: &Index,
+    file_path: &str,
+) -> Result<Vec<u8>> {
+    match index.get_path(Path::new(file_path), 0) {
+        Some(entry) => {
+            Ok(repo.find_blob(entry.id)?.content().to_vec())
+        }
+        // not in the index yet
+        None => Ok(Vec::new()),
+    }
+}
+
+fn write_to_index(
+    index: &mut Index,
+    file_path: &str,
+    content: &[u8],
+) -> Result<()> {
+    let mut entry = index
+        .get_path(Path::new(file_path), 0)
+        .unwrap_or_else(|| IndexEntry {
+            ctime: IndexTime::new(0, 0),
+            mtime: IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode: 0o100_644,
+            uid: 0,
+            gid: 0,
+            file_size: 0,
+            id: Oid::zero(),
+            flags: 0,
+            flags_extended: 0,
+            path: file_path.as_bytes().to_vec(),
+        });
+
+    // the workdir file differs from the index now, a stale
+    // timestamp would have it look unchanged
+    entry.ctime = IndexTime::new(0, 0);
+    entry.mtime = IndexTime::new(0, 0);
+    entry.file_size = content.len() as u32;
+
+    index.add_frombuffer(&entry, content)?;
+    index.write()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit,
+        diff::get_diff,
+        stage_add_file,
+        tests::{get_statuses, repo_init},
+    };
+    use std::path::Path;
+
+    const BEFORE: &str = "1\n2\n3\n4\n5\n6\n7\n8\n";
+    const AFTER: &str = "1\nTWO\n3\n4\n5\n6\n7\n8\nnine\n";
+
+    fn setup(root: &Path, repo_path: &str) {
+        fs::write(root.join("f.txt"), BEFORE).unwrap();
+        stage_add_file(repo_path, Path::new("f.txt")).unwrap();
+        commit(repo_path, "before").unwrap();
+        fs::write(root.join("f.txt"), AFTER).unwrap();
+    }
+
+    /// positions of the lines of `diff` with the given content
+    fn positions(
+        repo_path: &str,
+        stage: bool,
+        contents: &[&str],
+    ) -> Vec<DiffLinePosition> {
+        let diff =
+            get_diff(repo_path, String::from("f.txt"), stage, None)
+                .unwrap();
+
+        diff.hunks
+            .iter()
+            .flat_map(|hunk| {
+                hunk.lines.iter().enumerate().filter_map(
+                    move |(line, l)| {
+                        if contents.contains(&l.content.as_str()) {
+                            Some(DiffLinePosition {
+                                hunk: hunk.header_hash,
+                                line,
+                            })
+                        } else {
+                            None
+                        }
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn staged(repo: &Repository) -> String {
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        let content = index_content(repo, &index, "f.txt").unwrap();
+        String::from_utf8(content).unwrap()
+    }
+
+    #[test]
+    fn test_stage_and_unstage_lines() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(root, repo_path);
+
+        // only the addition of the replacement, keeping line 2
+        let lines = positions(repo_path, false, &["TWO\n", "nine\n"]);
+        stage_lines(repo_path, "f.txt", &lines, None).unwrap();
+
+        assert_eq!(
+            staged(&repo),
+            "1\n2\nTWO\n3\n4\n5\n6\n7\n8\nnine\n"
+        );
+        assert_eq!(get_statuses(repo_path), (1, 1));
+
+        let lines = positions(repo_path, true, &["TWO\n"]);
+        unstage_lines(repo_path, "f.txt", &lines, None).unwrap();
+
+        assert_eq!(staged(&repo), "1\n2\n3\n4\n5\n6\n7\n8\nnine\n");
+    }
+
+    #[test]
+    fn test_reset_lines() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(root, repo_path);
+
+        let lines = positions(repo_path, false, &["2\n", "TWO\n"]);
+        reset_lines(repo_path, "f.txt", &lines, None).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("f.txt")).unwrap(),
+            "1\n2\n3\n4\n5\n6\n7\n8\nnine\n"
+        );
+        assert_eq!(get_statuses(repo_path), (1, 0));
+    }
+
+    #[test]
+    fn test_stage_lines_of_untracked_file() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        fs::write(root.join("f.txt"), "a\nb\nc").unwrap();
+
+        let lines = positions(repo_path, false, &["a\n", "c"]);
+        stage_lines(repo_path, "f.txt", &lines, None).unwrap();
+
+        assert_eq!(staged(&repo), "a\nc");
+    }
+}
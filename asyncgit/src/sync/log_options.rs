@@ -0,0 +1,237 @@
+//! which commits the log walks and in what order
+
+use super::utils::repo;
+use crate::error::{Error, Result};
+use git2::{Repository, RevparseMode, Revwalk, Sort};
+
+/// where the log starts walking
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogScope {
+    /// commits reachable from HEAD
+    Head,
+    /// commits reachable from any ref or HEAD (`--all`)
+    All,
+    /// a revision like `feature`, or a range like `main..feature`
+    /// or `main...feature`
+    Revision(String),
+}
+
+/// order of the commits in the log
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogSort {
+    /// newest commits first
+    Date,
+    /// no parent before all of its children (`--topo-order`)
+    Topological,
+}
+
+/// scope, filters and order of the log
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogOptions {
+    ///
+    pub scope: LogScope,
+    /// only follows the first parent of merges (`--first-parent`)
+    pub first_parent: bool,
+    /// hides merge commits (`--no-merges`)
+    pub no_merges: bool,
+    ///
+    pub sort: LogSort,
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        Self {
+            scope: LogScope::Head,
+            first_parent: false,
+            no_merges: false,
+            sort: LogSort::Date,
+        }
+    }
+}
+
+impl LogOptions {
+    /// checks the revision of the scope resolves in the repo
+    pub fn validate(&self, repo_path: &str) -> Result<()> {
+        let repo = repo(repo_path)?;
+        self.revwalk(&repo)?;
+
+        Ok(())
+    }
+
+    /// creates a revwalk over the scope in the order of the options
+    pub(crate) fn revwalk<'a>(
+        &self,
+        repo: &'a Repository,
+    ) -> Result<Revwalk<'a>> {
+        let mut walk = repo.revwalk()?;
+
+        walk.set_sorting(match self.sort {
+            // libgit2 default, reverse chronological like git
+            LogSort::Date => Sort::NONE,
+            LogSort::Topological => Sort::TOPOLOGICAL,
+        })?;
+
+        match &self.scope {
+            LogScope::Head => walk.push_head()?,
+            LogScope::All => {
+                walk.push_glob("refs/*")?;
+                // a detached HEAD is no ref, an unborn one no commit
+                walk.push_head().ok();
+            }
+            LogScope::Revision(revision) => {
+                Self::push_revision(repo, &mut walk, revision)?
+            }
+        }
+
+        if self.first_parent {
+            walk.simplify_first_parent()?;
+        }
+
+        Ok(walk)
+    }
+
+    fn push_revision(
+        repo: &Repository,
+        walk: &mut Revwalk,
+        revision: &str,
+    ) -> Result<()> {
+        let spec = repo.revparse(revision)?;
+        let mode = spec.mode();
+
+        let from =
+            spec.from().map(|o| o.peel_to_commit()).transpose()?;
+        let to = spec.to().map(|o| o.peel_to_commit()).transpose()?;
+
+        if mode.contains(RevparseMode::SINGLE) {
+            let commit = from.ok_or_else(|| {
+                Error::Generic(format!("bad revision: {}", revision))
+            })?;
+            walk.push(commit.id())?;
+        } else if let (Some(from), Some(to)) = (from, to) {
+            walk.push(to.id())?;
+
+            if mode.contains(RevparseMode::MERGE_BASE) {
+                // symmetric difference, commits of either side
+                walk.push(from.id())?;
+                walk.hide(repo.merge_base(from.id(), to.id())?)?;
+            } else {
+                walk.hide(from.id())?;
+            }
+        } else {
+            return Err(Error::Generic(format!(
+                "bad revision: {}",
+                revision
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch, merge_branch,
+        tests::{repo_init, write_commit},
+        utils::get_head,
+        CommitId, LogWalker,
+    };
+
+    fn walk(
+        repo: &Repository,
+        options: &LogOptions,
+    ) -> Vec<CommitId> {
+        let mut ids = Vec::new();
+        let mut walker = LogWalker::new(repo);
+        walker.set_options(options.clone());
+        walker.read(&mut ids, 100).unwrap();
+        ids
+    }
+
+    #[test]
+    fn test_scopes() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "feature").unwrap();
+        write_commit(root, "a.txt", "a");
+        let feature = get_head(repo_path).unwrap();
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit(root, "b.txt", "b");
+        let master = get_head(repo_path).unwrap();
+
+        let head = walk(&repo, &LogOptions::default());
+        assert_eq!(head.len(), 2);
+        assert!(!head.contains(&feature));
+
+        let all = walk(
+            &repo,
+            &LogOptions {
+                scope: LogScope::All,
+                ..LogOptions::default()
+            },
+        );
+        assert_eq!(all.len(), 3);
+
+        let range = LogOptions {
+            scope: LogScope::Revision("master..feature".into()),
+            ..LogOptions::default()
+        };
+        assert_eq!(walk(&repo, &range), vec![feature]);
+
+        let symmetric = LogOptions {
+            scope: LogScope::Revision("master...feature".into()),
+            ..LogOptions::default()
+        };
+        let ids = walk(&repo, &symmetric);
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&master) && ids.contains(&feature));
+
+        let bad = LogOptions {
+            scope: LogScope::Revision("missing".into()),
+            ..LogOptions::default()
+        };
+        assert!(bad.validate(repo_path).is_err());
+        assert!(range.validate(repo_path).is_ok());
+    }
+
+    #[test]
+    fn test_merges() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "feature").unwrap();
+        write_commit(root, "a.txt", "a");
+        let feature = get_head(repo_path).unwrap();
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit(root, "b.txt", "b");
+        merge_branch(repo_path, "refs/heads/feature").unwrap();
+
+        let all = walk(&repo, &LogOptions::default());
+        assert_eq!(all.len(), 4);
+
+        let first_parent = walk(
+            &repo,
+            &LogOptions {
+                first_parent: true,
+                ..LogOptions::default()
+            },
+        );
+        assert_eq!(first_parent.len(), 3);
+        assert!(!first_parent.contains(&feature));
+
+        let no_merges = walk(
+            &repo,
+            &LogOptions {
+                no_merges: true,
+                sort: LogSort::Topological,
+                ..LogOptions::default()
+            },
+        );
+        assert_eq!(no_merges.len(), 3);
+        assert!(!no_merges.contains(&all[0]));
+    }
+}
@@ -0,0 +1,216 @@
+//! filter for the commit log
+
+use crate::error::{Error, Result};
+use git2::{Commit, Signature};
+use regex::Regex;
+
+const SECS_PER_DAY: i64 = 60 * 60 * 24;
+
+#[derive(Debug, Clone)]
+enum TextMatcher {
+    /// lowercase substring
+    Substring(String),
+    Regex(Regex),
+}
+
+impl TextMatcher {
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Self::Substring(s) => text.to_lowercase().contains(s),
+            Self::Regex(re) => re.is_match(text),
+        }
+    }
+}
+
+/// search parsed from a query like
+/// `fix crash author:jane since:2020-01-01 until:2020-02-01 sha:4f2a`
+///
+/// free text matches the commit message as a case insensitive
+/// substring or, wrapped in slashes (`/fix(es)?/`), as a regex.
+/// `author:` matches name or email of author or committer
+#[derive(Debug, Clone, Default)]
+pub struct LogSearch {
+    message: Option<TextMatcher>,
+    author: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    sha: Option<String>,
+}
+
+impl LogSearch {
+    ///
+    pub fn parse(query: &str) -> Result<Self> {
+        let mut search = Self::default();
+        let mut text = Vec::new();
+
+        for token in query.split_whitespace() {
+            if let Some(author) = token.strip_prefix("author:") {
+                search.author = Some(author.to_lowercase());
+            } else if let Some(date) = token.strip_prefix("since:") {
+                search.since = Some(parse_date(date)?);
+            } else if let Some(date) = token.strip_prefix("until:") {
+                search.until = Some(parse_date(date)? + SECS_PER_DAY);
+            } else if let Some(sha) = token.strip_prefix("sha:") {
+                search.sha = Some(sha.to_lowercase());
+            } else {
+                text.push(token);
+            }
+        }
+
+        let text = text.join(" ");
+        if text.len() > 2
+            && text.starts_with('/')
+            && text.ends_with('/')
+        {
+            let re = Regex::new(&text[1..text.len() - 1])
+                .map_err(|e| Error::Generic(e.to_string()))?;
+            search.message = Some(TextMatcher::Regex(re));
+        } else if !text.is_empty() {
+            search.message =
+                Some(TextMatcher::Substring(text.to_lowercase()));
+        }
+
+        Ok(search)
+    }
+
+    /// true if nothing would be filtered
+    pub fn is_empty(&self) -> bool {
+        self.message.is_none()
+            && self.author.is_none()
+            && self.since.is_none()
+            && self.until.is_none()
+            && self.sha.is_none()
+    }
+
+    ///
+    pub fn matches(&self, commit: &Commit) -> bool {
+        if let Some(sha) = &self.sha {
+            if !commit.id().to_string().starts_with(sha.as_str()) {
+                return false;
+            }
+        }
+
+        let time = commit.time().seconds();
+        if self.since.map_or(false, |since| time < since)
+            || self.until.map_or(false, |until| time >= until)
+        {
+            return false;
+        }
+
+        if let Some(author) = &self.author {
+            if !signature_matches(&commit.author(), author)
+                && !signature_matches(&commit.committer(), author)
+            {
+                return false;
+            }
+        }
+
+        if let Some(message) = &self.message {
+            let msg = String::from_utf8_lossy(commit.message_bytes());
+            if !message.is_match(&msg) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn signature_matches(sig: &Signature, needle: &str) -> bool {
+    let contains = |s: Option<&str>| {
+        s.map_or(false, |s| s.to_lowercase().contains(needle))
+    };
+
+    contains(sig.name()) || contains(sig.email())
+}
+
+/// parses `YYYY-MM-DD` into seconds since epoch (UTC midnight)
+fn parse_date(date: &str) -> Result<i64> {
+    let invalid = || {
+        Error::Generic(format!(
+            "invalid date `{}`, expected YYYY-MM-DD",
+            date
+        ))
+    };
+
+    let mut parts = date.splitn(3, '-').map(str::parse::<i64>);
+    let (y, m, d) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(y)), Some(Ok(m)), Some(Ok(d)))
+            if (1..=12).contains(&m) && (1..=31).contains(&d) =>
+        {
+            (y, m, d)
+        }
+        _ => return Err(invalid()),
+    };
+
+    // days from civil, see http://howardhinnant.github.io/date_algorithms.html
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    Ok(days * SECS_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        tests::{repo_init, write_commit},
+        utils::get_head,
+    };
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("1970-01-01").unwrap(), 0);
+        assert_eq!(parse_date("2020-03-01").unwrap(), 1_583_020_800);
+        assert!(parse_date("2020-13-01").is_err());
+        assert!(parse_date("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(LogSearch::parse("").unwrap().is_empty());
+        assert!(LogSearch::parse("/(/").is_err());
+
+        let search =
+            LogSearch::parse("fix  crash author:Jane sha:AB")
+                .unwrap();
+        assert_eq!(search.author.as_deref(), Some("jane"));
+        assert_eq!(search.sha.as_deref(), Some("ab"));
+        assert!(search
+            .message
+            .unwrap()
+            .is_match("Fix Crash on start"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit(root, "a.txt", "fix crash in parser");
+        let id = get_head(repo_path).unwrap();
+        let commit = repo.find_commit(id.into()).unwrap();
+        let sha = &id.to_string()[..6];
+
+        let matches =
+            |q: &str| LogSearch::parse(q).unwrap().matches(&commit);
+
+        assert!(matches("CRASH"));
+        assert!(matches("/cr.sh in/"));
+        assert!(!matches("/^crash/"));
+        assert!(matches("author:NAME"));
+        assert!(matches("author:email"));
+        assert!(!matches("author:jane"));
+        assert!(matches(&format!("sha:{}", sha)));
+        assert!(!matches("sha:zz"));
+        assert!(matches("since:1970-01-01"));
+        assert!(!matches("until:1970-01-01"));
+        assert!(!matches("parser since:2999-01-01"));
+    }
+}
@@ -1,11 +1,25 @@
-use super::CommitId;
+use super::{CommitId, LogOptions, LogSearch, Pickaxe};
 use crate::error::Result;
-use git2::{Repository, Revwalk};
+use git2::{Commit, DiffFindOptions, Oid, Repository, Revwalk};
+use std::path::Path;
+
+/// state of a walk that only yields commits touching a file
+struct FileFilter {
+    /// path of the file in the commits walked next
+    path: String,
+    /// position in the output and path before each rename
+    renames: Vec<(usize, String)>,
+}
 
 ///
 pub struct LogWalker<'a> {
     repo: &'a Repository,
     revwalk: Option<Revwalk<'a>>,
+    file: Option<FileFilter>,
+    search: Option<LogSearch>,
+    pickaxe: Option<Pickaxe>,
+    options: LogOptions,
+    yielded: usize,
 }
 
 impl<'a> LogWalker<'a> {
@@ -14,10 +28,50 @@ impl<'a> LogWalker<'a> {
         Self {
             repo,
             revwalk: None,
+            file: None,
+            search: None,
+            pickaxe: None,
+            options: LogOptions::default(),
+            yielded: 0,
         }
     }
 
-    ///
+    /// walks only commits changing `path`, following renames
+    pub fn new_file(repo: &'a Repository, path: &str) -> Self {
+        Self {
+            file: Some(FileFilter {
+                path: path.to_string(),
+                renames: Vec::new(),
+            }),
+            ..Self::new(repo)
+        }
+    }
+
+    /// renames found so far in a file walk: each entry holds the
+    /// number of commits yielded before the renaming commit was
+    /// passed and the path the file had before it
+    pub fn renames(&self) -> &[(usize, String)] {
+        self.file.as_ref().map_or(&[], |f| f.renames.as_slice())
+    }
+
+    /// only yields commits matching `search`
+    pub fn set_search(&mut self, search: Option<LogSearch>) {
+        self.search = search.filter(|s| !s.is_empty());
+    }
+
+    /// only yields commits whose diff matches `pickaxe`
+    pub fn set_pickaxe(&mut self, pickaxe: Option<Pickaxe>) {
+        self.pickaxe = pickaxe;
+    }
+
+    /// scope, filters and order of the walk, has to be set
+    /// before the first `read`
+    pub fn set_options(&mut self, options: LogOptions) {
+        self.options = options;
+    }
+
+    /// reads up to `limit` commits of the walk, returns the amount
+    /// walked (which includes commits skipped by a filter)
     pub fn read(
         &mut self,
         out: &mut Vec<CommitId>,
@@ -26,17 +80,61 @@ impl<'a> LogWalker<'a> {
         let mut count = 0_usize;
 
         if self.revwalk.is_none() {
-            let mut walk = self.repo.revwalk()?;
-            walk.push_head()?;
-            self.revwalk = Some(walk);
+            self.revwalk = Some(self.options.revwalk(self.repo)?);
         }
 
         if let Some(ref mut walk) = self.revwalk {
             for id in walk {
                 if let Ok(id) = id {
-                    out.push(id.into());
                     count += 1;
 
+                    if self.file.is_none()
+                        && self.search.is_none()
+                        && self.pickaxe.is_none()
+                        && !self.options.no_merges
+                    {
+                        out.push(id.into());
+                    } else {
+                        let commit = self.repo.find_commit(id)?;
+
+                        let (mut wanted, rename) = match &self.file {
+                            Some(file) => Self::touches_file(
+                                self.repo, &commit, &file.path,
+                            )?,
+                            None => (true, None),
+                        };
+
+                        if wanted && self.options.no_merges {
+                            wanted = commit.parent_count() < 2;
+                        }
+
+                        if let (true, Some(search)) =
+                            (wanted, &self.search)
+                        {
+                            wanted = search.matches(&commit);
+                        }
+
+                        if let (true, Some(pickaxe)) =
+                            (wanted, &self.pickaxe)
+                        {
+                            wanted = pickaxe
+                                .matches(self.repo, &commit)?;
+                        }
+
+                        if wanted {
+                            out.push(id.into());
+                            self.yielded += 1;
+                        }
+
+                        if let (Some(file), Some(old)) =
+                            (self.file.as_mut(), rename)
+                        {
+                            file.renames
+                                .push((self.yielded, old.clone()));
+                            file.path = old;
+                        }
+                    }
+
                     if count == limit {
                         break;
                     }
@@ -46,16 +144,86 @@ impl<'a> LogWalker<'a> {
 
         Ok(count)
     }
+
+    /// checks whether `commit` changes `path` compared to its
+    /// parents, returns the former path if it renamed the file
+    fn touches_file(
+        repo: &Repository,
+        commit: &Commit,
+        path: &str,
+    ) -> Result<(bool, Option<String>)> {
+        let entry = file_id(commit, path)?;
+
+        let parents = commit.parents().collect::<Vec<_>>();
+        if parents.is_empty() {
+            return Ok((entry.is_some(), None));
+        }
+
+        let mut changed = true;
+        for parent in &parents {
+            if file_id(parent, path)? == entry {
+                changed = false;
+            }
+        }
+
+        let mut rename = None;
+        if changed && entry.is_some() {
+            let parent = &parents[0];
+            if file_id(parent, path)?.is_none() {
+                rename = renamed_from(repo, parent, commit, path)?;
+            }
+        }
+
+        Ok((changed, rename))
+    }
+}
+
+fn file_id(commit: &Commit, path: &str) -> Result<Option<Oid>> {
+    match commit.tree()?.get_path(Path::new(path)) {
+        Ok(entry) => Ok(Some(entry.id())),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// looks for a rename to `path` between `parent` and `commit`
+fn renamed_from(
+    repo: &Repository,
+    parent: &Commit,
+    commit: &Commit,
+    path: &str,
+) -> Result<Option<String>> {
+    let mut diff = repo.diff_tree_to_tree(
+        Some(&parent.tree()?),
+        Some(&commit.tree()?),
+        None,
+    )?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    let old = diff
+        .deltas()
+        .filter(|d| d.status() == git2::Delta::Renamed)
+        .find(|d| d.new_file().path() == Some(Path::new(path)))
+        .and_then(|d| {
+            d.old_file()
+                .path()
+                .and_then(Path::to_str)
+                .map(String::from)
+        });
+
+    Ok(old)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::sync::{
-        commit, get_commits_info, stage_add_file,
-        tests::repo_init_empty,
+        commit, get_commits_info, stage_add_file, stage_addremoved,
+        tests::{repo_init, repo_init_empty, write_commit},
+        utils::get_head,
+        LogSearch,
     };
-    use std::{fs::File, io::Write, path::Path};
+    use std::{fs, fs::File, io::Write, path::Path};
 
     #[test]
     fn test_limit() -> Result<()> {
@@ -112,4 +280,55 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_file_history_follows_renames() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit(root, "foo.txt", "a\nb\nc\nd\n");
+        let first = get_head(repo_path).unwrap();
+        write_commit(root, "other.txt", "other");
+        write_commit(root, "foo.txt", "a\nb\nc\nd\ne\n");
+        let second = get_head(repo_path).unwrap();
+
+        fs::rename(root.join("foo.txt"), root.join("bar.txt"))
+            .unwrap();
+        stage_addremoved(repo_path, Path::new("foo.txt")).unwrap();
+        stage_add_file(repo_path, Path::new("bar.txt")).unwrap();
+        commit(repo_path, "rename").unwrap();
+        let renamed = get_head(repo_path).unwrap();
+
+        write_commit(root, "bar.txt", "a\nb\nc\nd\ne\nf\n");
+        let third = get_head(repo_path).unwrap();
+
+        let mut items = Vec::new();
+        let mut walk = LogWalker::new_file(&repo, "bar.txt");
+        assert_eq!(walk.read(&mut items, 100).unwrap(), 6);
+
+        assert_eq!(items, vec![third, renamed, second, first]);
+        assert_eq!(walk.renames(), &[(2, String::from("foo.txt"))]);
+    }
+
+    #[test]
+    fn test_search() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit(root, "a.txt", "fix a");
+        let a = get_head(repo_path).unwrap();
+        write_commit(root, "b.txt", "feature b");
+        write_commit(root, "c.txt", "fix c");
+        let c = get_head(repo_path).unwrap();
+
+        let mut items = Vec::new();
+        let mut walk = LogWalker::new(&repo);
+        walk.set_search(Some(LogSearch::parse("FIX").unwrap()));
+
+        // walked count includes commits that did not match
+        assert_eq!(walk.read(&mut items, 100).unwrap(), 4);
+        assert_eq!(items, vec![c, a]);
+    }
 }
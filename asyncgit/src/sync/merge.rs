@@ -0,0 +1,391 @@
+//! sync git api for merging branches
+
+use super::{
+    commit::{
+        create_commit, signature_allow_undefined_name, update_head,
+    },
+    utils, CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{
+    build::CheckoutBuilder, Oid, Repository, RepositoryState,
+    ResetType,
+};
+use scopetime::scope_time;
+use std::fs;
+
+/// outcome of `merge_branch`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MergeResult {
+    /// HEAD already contained the branch
+    UpToDate,
+    /// HEAD was fast-forwarded to the branch
+    FastForward,
+    /// a merge commit was created
+    Merged,
+    /// the merge stopped with conflicts, the repo is in merge state
+    Conflict,
+}
+
+/// generated commit message for merging `branch_ref`
+fn merge_msg_for(branch_ref: &str) -> String {
+    if let Some(name) = branch_ref.strip_prefix("refs/remotes/") {
+        format!("Merge remote-tracking branch '{}'", name)
+    } else {
+        format!(
+            "Merge branch '{}'",
+            branch_ref.trim_start_matches("refs/heads/")
+        )
+    }
+}
+
+/// merges `branch_ref` into HEAD.
+///
+/// fast-forwards when possible, otherwise merges into the working dir
+/// and commits. on conflicts the repo is left in merge state for the
+/// user to resolve and either `merge_commit` or `abort_merge`.
+pub fn merge_branch(
+    repo_path: &str,
+    branch_ref: &str,
+) -> Result<MergeResult> {
+    scope_time!("merge_branch");
+
+    let repo = utils::repo(repo_path)?;
+
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err(Error::Generic(
+            "another operation is in progress".to_string(),
+        ));
+    }
+
+    let theirs = repo.reference_to_annotated_commit(
+        &repo.find_reference(branch_ref)?,
+    )?;
+
+    let (analysis, _) = repo.merge_analysis(&[&theirs])?;
+
+    if analysis.is_up_to_date() {
+        return Ok(MergeResult::UpToDate);
+    }
+
+    if analysis.is_fast_forward() {
+        fast_forward(&repo, theirs.id())?;
+        return Ok(MergeResult::FastForward);
+    }
+
+    utils::check_index_matches_head(&repo)?;
+
+    let mut checkout = CheckoutBuilder::new();
+    checkout.safe().allow_conflicts(true);
+
+    repo.merge(&[&theirs], None, Some(&mut checkout))?;
+
+    let msg = merge_msg_for(branch_ref);
+
+    if repo.index()?.has_conflicts() {
+        // keep the conflict hints libgit2 appended to the message
+        let conflicts = repo.message().unwrap_or_default();
+        let hints = conflicts
+            .lines()
+            .skip_while(|l| !l.starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(
+            repo.path().join("MERGE_MSG"),
+            format!("{}\n\n{}\n", msg, hints),
+        )?;
+
+        return Ok(MergeResult::Conflict);
+    }
+
+    merge_commit(repo_path, &msg)?;
+
+    Ok(MergeResult::Merged)
+}
+
+fn fast_forward(repo: &Repository, target: Oid) -> Result<()> {
+    let commit = repo.find_commit(target)?;
+
+    repo.checkout_tree(
+        commit.as_object(),
+        Some(CheckoutBuilder::new().safe()),
+    )
+    .map_err(|e| {
+        Error::Generic(format!(
+            "merge aborted, local changes would be overwritten: {}",
+            e.message()
+        ))
+    })?;
+
+    repo.head()?.set_target(target, "merge: fast-forward")?;
+
+    Ok(())
+}
+
+/// commits that are merged into HEAD (read from `MERGE_HEAD`),
+/// empty for a cherry-pick or revert in progress
+fn merge_heads(repo: &Repository) -> Result<Vec<Oid>> {
+    match repo.state() {
+        RepositoryState::Merge => (),
+        RepositoryState::CherryPick | RepositoryState::Revert => {
+            return Ok(Vec::new())
+        }
+        _ => {
+            return Err(Error::Generic(
+                "no merge in progress".to_string(),
+            ))
+        }
+    }
+
+    let content = fs::read_to_string(repo.path().join("MERGE_HEAD"))
+        .map_err(|_| {
+            Error::Generic("no merge in progress".to_string())
+        })?;
+
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| Oid::from_str(l.trim()).map_err(Error::from))
+        .collect()
+}
+
+/// commit being cherry-picked (read from `CHERRY_PICK_HEAD`)
+fn cherry_pick_head(
+    repo: &Repository,
+) -> Result<Option<git2::Commit<'_>>> {
+    if repo.state() != RepositoryState::CherryPick {
+        return Ok(None);
+    }
+
+    let content =
+        fs::read_to_string(repo.path().join("CHERRY_PICK_HEAD"))?;
+
+    Ok(Some(repo.find_commit(Oid::from_str(content.trim())?)?))
+}
+
+/// message prepared for the merge in progress without comment lines
+pub fn merge_msg(repo_path: &str) -> Result<String> {
+    scope_time!("merge_msg");
+
+    let repo = utils::repo(repo_path)?;
+
+    let msg = repo.message().unwrap_or_default();
+
+    Ok(msg
+        .lines()
+        .filter(|l| !l.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string())
+}
+
+/// commits the index of the merge in progress with HEAD and
+/// all of `MERGE_HEAD` as parents and leaves the merge state.
+///
+/// works the same for a cherry-pick or revert in progress,
+/// a cherry-picked commit keeps its original author.
+pub fn merge_commit(repo_path: &str, msg: &str) -> Result<CommitId> {
+    scope_time!("merge_commit");
+
+    let repo = utils::repo(repo_path)?;
+
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        return Err(Error::Generic(
+            "resolve all conflicts before committing the merge"
+                .to_string(),
+        ));
+    }
+
+    let mut parents = vec![repo.head()?.peel_to_commit()?];
+    for id in merge_heads(&repo)? {
+        parents.push(repo.find_commit(id)?);
+    }
+    let parents = parents.iter().collect::<Vec<_>>();
+
+    let signature = signature_allow_undefined_name(&repo)?;
+    let author = cherry_pick_head(&repo)?.map_or_else(
+        || signature.to_owned(),
+        |c| c.author().to_owned(),
+    );
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let id = create_commit(
+        &repo,
+        &author,
+        &signature,
+        msg,
+        &tree,
+        parents.as_slice(),
+    )?;
+    update_head(&repo, id, "commit (merge)")?;
+
+    repo.cleanup_state()?;
+
+    Ok(id.into())
+}
+
+/// throws away the merge, cherry-pick or revert in progress
+/// resetting index and working dir to HEAD
+pub fn abort_merge(repo_path: &str) -> Result<()> {
+    scope_time!("abort_merge");
+
+    let repo = utils::repo(repo_path)?;
+
+    if !matches!(
+        repo.state(),
+        RepositoryState::Merge
+            | RepositoryState::CherryPick
+            | RepositoryState::Revert
+    ) {
+        return Err(Error::Generic(
+            "no merge in progress".to_string(),
+        ));
+    }
+
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), ResetType::Hard, None)?;
+    repo.cleanup_state()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch,
+        status::{get_status, StatusItemType, StatusType},
+        tests::{path, repo_init, write_commit},
+        utils::get_head,
+        RepoState,
+    };
+    use std::path::Path;
+
+    /// creates `feature` with a commit of `file`,`content` and
+    /// goes back to master
+    fn setup_feature(repo: &Repository, file: &str, content: &str) {
+        let root = repo.path().parent().unwrap();
+        write_commit(root, "a.txt", "base");
+        create_branch(path(repo), "feature").unwrap();
+        write_commit(root, file, content);
+        checkout_branch(path(repo), "refs/heads/master").unwrap();
+    }
+
+    #[test]
+    fn test_merge_fast_forward() {
+        let (_td, repo) = repo_init().unwrap();
+        setup_feature(&repo, "b.txt", "b");
+
+        assert_eq!(
+            merge_branch(path(&repo), "refs/heads/feature").unwrap(),
+            MergeResult::FastForward
+        );
+        assert_eq!(
+            merge_branch(path(&repo), "refs/heads/feature").unwrap(),
+            MergeResult::UpToDate
+        );
+        assert!(repo.path().parent().unwrap().join("b.txt").exists());
+    }
+
+    #[test]
+    fn test_merge_commit() {
+        let (_td, repo) = repo_init().unwrap();
+        setup_feature(&repo, "b.txt", "b");
+        write_commit(repo.path().parent().unwrap(), "c.txt", "c");
+
+        assert_eq!(
+            merge_branch(path(&repo), "refs/heads/feature").unwrap(),
+            MergeResult::Merged
+        );
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2);
+        assert_eq!(head.message(), Some("Merge branch 'feature'"));
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_merge_staged_changes() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        setup_feature(&repo, "b.txt", "b");
+        write_commit(root, "c.txt", "c");
+
+        fs::write(root.join("d.txt"), "d").unwrap();
+        crate::sync::stage_add_file(path(&repo), Path::new("d.txt"))
+            .unwrap();
+        let head = get_head(path(&repo)).unwrap();
+
+        assert!(
+            merge_branch(path(&repo), "refs/heads/feature").is_err()
+        );
+        assert_eq!(get_head(path(&repo)).unwrap(), head);
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(
+            get_status(path(&repo), StatusType::Stage, true)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_merge_conflict_continue() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        setup_feature(&repo, "a.txt", "feature");
+        write_commit(root, "a.txt", "master");
+
+        assert_eq!(
+            merge_branch(path(&repo), "refs/heads/feature").unwrap(),
+            MergeResult::Conflict
+        );
+        assert_eq!(
+            crate::sync::repo_state(path(&repo)).unwrap(),
+            RepoState::Merge
+        );
+
+        let status =
+            get_status(path(&repo), StatusType::WorkingDir, true)
+                .unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].status, StatusItemType::Conflicted);
+
+        assert_eq!(
+            merge_msg(path(&repo)).unwrap(),
+            "Merge branch 'feature'"
+        );
+        assert!(merge_commit(path(&repo), "merge").is_err());
+
+        fs::write(root.join("a.txt"), "resolved").unwrap();
+        crate::sync::stage_add_file(path(&repo), Path::new("a.txt"))
+            .unwrap();
+
+        let id = merge_commit(path(&repo), "merge").unwrap();
+        let commit = repo.find_commit(id.into()).unwrap();
+
+        assert_eq!(commit.parent_count(), 2);
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_merge_abort() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        setup_feature(&repo, "a.txt", "feature");
+        write_commit(root, "a.txt", "master");
+        let head = get_head(path(&repo)).unwrap();
+
+        merge_branch(path(&repo), "refs/heads/feature").unwrap();
+        abort_merge(path(&repo)).unwrap();
+
+        assert_eq!(get_head(path(&repo)).unwrap(), head);
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "master"
+        );
+    }
+}
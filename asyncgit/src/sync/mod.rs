@@ -1,36 +1,93 @@
 //! sync git api
 
+mod blame;
 mod branch;
+mod cherry_pick;
 mod commit;
 mod commit_details;
 mod commit_files;
 mod commits_info;
+mod conflicts;
 pub mod diff;
+mod graph;
 mod hooks;
 mod hunks;
 mod ignore;
+mod lines;
+mod log_options;
+mod log_search;
 mod logwalker;
+mod merge;
+mod pickaxe;
+mod pull;
+mod rebase;
+mod reflog;
+mod refs;
+mod remotes;
 mod reset;
+mod sign;
 mod stash;
+mod state;
 pub mod status;
 mod tags;
 pub mod utils;
 
-pub use branch::create_branch;
-pub(crate) use branch::get_branch_name;
+pub use blame::{blame_file, BlameHunk, FileBlame};
+pub use branch::{
+    branch_is_merged, checkout_branch, create_branch, delete_branch,
+    get_branch_status, get_branches_info, is_workdir_clean,
+    rename_branch, set_branch_upstream, BranchInfo, BranchStatus,
+};
+pub(crate) use branch::{get_branch_name, get_head_upstream};
+pub use cherry_pick::{cherry_pick, revert, PickResult};
 pub use commit::{amend, commit, tag};
 pub use commit_details::{
     get_commit_details, CommitDetails, CommitMessage,
 };
 pub use commit_files::get_commit_files;
 pub use commits_info::{get_commits_info, CommitId, CommitInfo};
-pub use diff::get_diff_commit;
-pub use hooks::{hooks_commit_msg, hooks_post_commit, HookResult};
+pub use conflicts::{
+    get_file_conflict, resolve_conflict, resolve_conflict_side,
+    ConflictChunk, ConflictResolution, FileConflict,
+};
+pub use diff::{get_diff_commit, DiffAlgorithm, DiffOptions};
+pub use graph::{CommitGraph, GraphLane, GraphRow};
+pub(crate) use graph::with_parents;
+pub use hooks::{
+    hooks_commit_msg, hooks_post_checkout, hooks_post_commit,
+    hooks_post_merge, hooks_pre_commit, hooks_prepare_commit_msg,
+    HookResult, PrepareCommitMsgSource,
+};
 pub use hunks::{reset_hunk, stage_hunk, unstage_hunk};
 pub use ignore::add_to_ignore;
+pub use lines::{
+    reset_lines, stage_lines, unstage_lines, DiffLinePosition,
+};
+pub use log_options::{LogOptions, LogScope, LogSort};
+pub use log_search::LogSearch;
 pub use logwalker::LogWalker;
-pub use reset::{reset_stage, reset_workdir};
+pub use merge::{
+    abort_merge, merge_branch, merge_commit, merge_msg, MergeResult,
+};
+pub use pickaxe::Pickaxe;
+pub use pull::{pull, PullResult};
+pub use rebase::{
+    get_rebase_plan, rebase_abort, rebase_continue, rebase_interactive,
+    rebase_skip, rebase_status, RebaseAction, RebaseEntry,
+    RebaseProgress, RebaseStatus,
+};
+pub use reflog::{get_reflog, ReflogEntry};
+pub use refs::{get_refs, tags_of, CommitRef, RefKind, Refs};
+pub use remotes::{
+    fetch, get_remotes, push_upstream, RemoteProgress,
+};
+pub use reset::{
+    reset_head, reset_head_preview, reset_stage, reset_workdir,
+    ResetMode, ResetPreview,
+};
+pub use sign::{is_signed, verify_commit, SignatureStatus};
 pub use stash::{get_stashes, stash_apply, stash_drop, stash_save};
+pub use state::{repo_state, RepoState};
 pub use tags::{get_tags, CommitTags, Tags};
 pub use utils::{
     get_head, get_head_tuple, is_bare_repo, is_repo, stage_add_all,
@@ -39,10 +96,14 @@ pub use utils::{
 
 #[cfg(test)]
 mod tests {
-    use super::status::{get_status, StatusType};
+    use super::{
+        commit,
+        stage_add_file,
+        status::{get_status, StatusType},
+    };
     use crate::error::Result;
     use git2::Repository;
-    use std::process::Command;
+    use std::{fs::File, io::Write, path::Path, process::Command};
     use tempfile::TempDir;
 
     ///
@@ -83,6 +144,46 @@ mod tests {
         Ok((td, repo))
     }
 
+    ///
+    pub fn repo_init_bare() -> (TempDir, Repository) {
+        let td = TempDir::new().unwrap();
+        let repo = Repository::init_bare(td.path()).unwrap();
+        (td, repo)
+    }
+
+    /// clone of the repo at `url` with a user configured
+    pub fn repo_clone(url: &str) -> (TempDir, Repository) {
+        let td = TempDir::new().unwrap();
+        let repo = Repository::clone(url, td.path()).unwrap();
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("user.name", "name").unwrap();
+            config.set_str("user.email", "email").unwrap();
+        }
+        (td, repo)
+    }
+
+    /// working directory of `repo` as used by the sync functions
+    pub fn path(repo: &Repository) -> &str {
+        repo.path().parent().unwrap().to_str().unwrap()
+    }
+
+    /// url to use `repo` as a remote
+    pub fn bare_url(repo: &Repository) -> String {
+        format!("file://{}", repo.path().to_str().unwrap())
+    }
+
+    /// writes `msg` into `file` and commits it using `msg`
+    pub fn write_commit(root: &Path, file: &str, msg: &str) {
+        let repo_path = root.to_str().unwrap();
+        File::create(root.join(file))
+            .unwrap()
+            .write_all(msg.as_bytes())
+            .unwrap();
+        stage_add_file(repo_path, Path::new(file)).unwrap();
+        commit(repo_path, msg).unwrap();
+    }
+
     /// helper returning amount of files with changes in the (wd,stage)
     pub fn get_statuses(repo_path: &str) -> (usize, usize) {
         (
@@ -0,0 +1,212 @@
+//! content search through the diffs of commits
+
+use crate::error::{Error, Result};
+use git2::{Commit, DiffLine, Oid, Repository};
+use regex::Regex;
+
+#[derive(Debug, Clone)]
+enum PickaxeKind {
+    /// occurrences of the string changed (`git log -S`)
+    Occurrences(String),
+    /// an added or removed line matches (`git log -G`)
+    Lines(Regex),
+}
+
+/// finds commits whose diff adds or removes content, parsed from
+/// a query like `config_key` or, wrapped in slashes, `/key\s*=/`
+///
+/// a plain string matches commits changing the number of its
+/// occurrences in a file, a regex matches commits adding or
+/// removing a matching line. merge commits never match
+#[derive(Debug, Clone)]
+pub struct Pickaxe {
+    kind: PickaxeKind,
+}
+
+impl Pickaxe {
+    ///
+    pub fn parse(query: &str) -> Result<Self> {
+        let kind = if query.len() > 2
+            && query.starts_with('/')
+            && query.ends_with('/')
+        {
+            PickaxeKind::Lines(
+                Regex::new(&query[1..query.len() - 1])
+                    .map_err(|e| Error::Generic(e.to_string()))?,
+            )
+        } else if query.is_empty() {
+            return Err(Error::Generic("empty search".to_string()));
+        } else {
+            PickaxeKind::Occurrences(query.to_string())
+        };
+
+        Ok(Self { kind })
+    }
+
+    /// diffs `commit` against its parent to check for a match
+    pub fn matches(
+        &self,
+        repo: &Repository,
+        commit: &Commit,
+    ) -> Result<bool> {
+        if commit.parent_count() > 1 {
+            return Ok(false);
+        }
+
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree()?),
+            None => None,
+        };
+
+        let diff = repo.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&tree),
+            None,
+        )?;
+
+        match &self.kind {
+            PickaxeKind::Occurrences(needle) => {
+                for delta in diff.deltas() {
+                    let old = count_in_blob(
+                        repo,
+                        delta.old_file().id(),
+                        needle,
+                    )?;
+                    let new = count_in_blob(
+                        repo,
+                        delta.new_file().id(),
+                        needle,
+                    )?;
+
+                    if old != new {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+            PickaxeKind::Lines(re) => {
+                let mut found = false;
+                let res = diff.foreach(
+                    &mut |_, _| true,
+                    None,
+                    None,
+                    Some(&mut |_, _, line: DiffLine| {
+                        if matches!(line.origin(), '+' | '-')
+                            && re.is_match(&String::from_utf8_lossy(
+                                line.content(),
+                            ))
+                        {
+                            found = true;
+                        }
+
+                        // stops the diff after the first match
+                        !found
+                    }),
+                );
+
+                if found {
+                    Ok(true)
+                } else {
+                    res?;
+                    Ok(false)
+                }
+            }
+        }
+    }
+}
+
+/// occurrences of `needle` in a text blob, binary blobs and
+/// missing sides of a diff count as none
+fn count_in_blob(
+    repo: &Repository,
+    id: Oid,
+    needle: &str,
+) -> Result<usize> {
+    if id.is_zero() {
+        return Ok(0);
+    }
+
+    let blob = repo.find_blob(id)?;
+    if blob.is_binary() {
+        return Ok(0);
+    }
+
+    Ok(count_occurrences(blob.content(), needle.as_bytes()))
+}
+
+fn count_occurrences(haystack: &[u8], needle: &[u8]) -> usize {
+    let mut count = 0;
+    let mut pos = 0;
+
+    while pos + needle.len() <= haystack.len() {
+        if haystack[pos..].starts_with(needle) {
+            count += 1;
+            pos += needle.len();
+        } else {
+            pos += 1;
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        tests::{repo_init, write_commit},
+        utils::get_head,
+    };
+
+    fn head_matches(repo: &Repository, query: &str) -> bool {
+        let path = repo.path().parent().unwrap().to_str().unwrap();
+        let head = repo.find_commit(get_head(path).unwrap().into());
+
+        Pickaxe::parse(query)
+            .unwrap()
+            .matches(repo, &head.unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_count_occurrences() {
+        assert_eq!(count_occurrences(b"aaaa", b"aa"), 2);
+        assert_eq!(count_occurrences(b"a key=1\nkey", b"key"), 2);
+        assert_eq!(count_occurrences(b"", b"key"), 0);
+    }
+
+    #[test]
+    fn test_occurrences() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+
+        write_commit(root, "a.txt", "add key=1\nother=2\n");
+        assert!(head_matches(&repo, "key=1"));
+        assert!(!head_matches(&repo, "missing"));
+
+        // moving the string around keeps its count
+        write_commit(root, "a.txt", "other=2\nkey=1\n");
+        assert!(!head_matches(&repo, "key=1"));
+        assert!(head_matches(&repo, "/key=1/"));
+
+        write_commit(root, "a.txt", "other=2\n");
+        assert!(head_matches(&repo, "key=1"));
+        assert!(!head_matches(&repo, "other"));
+    }
+
+    #[test]
+    fn test_lines() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+
+        write_commit(root, "a.txt", "timeout = 5\n");
+        write_commit(root, "a.txt", "timeout = 10\n");
+
+        assert!(head_matches(&repo, r"/timeout\s*=\s*\d+/"));
+        assert!(!head_matches(&repo, "/retries/"));
+        assert!(Pickaxe::parse("/(/").is_err());
+        assert!(Pickaxe::parse("").is_err());
+    }
+}
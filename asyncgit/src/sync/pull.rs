@@ -0,0 +1,397 @@
+//! sync git api for pulling the upstream of the current branch
+
+use super::{
+    branch::get_branch_name,
+    commit::{create_commit, signature_allow_undefined_name},
+    remotes::{fetch, get_branch_upstream},
+    utils,
+};
+use crate::error::{Error, Result};
+use git2::{
+    build::CheckoutBuilder, AnnotatedCommit, ErrorCode, Index, Oid,
+    RebaseOptions, Repository,
+};
+use scopetime::scope_time;
+
+/// how the fetched upstream got integrated into the local branch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PullResult {
+    /// local branch already contained everything from upstream
+    UpToDate,
+    /// local branch was fast-forwarded to upstream
+    FastForward,
+    /// upstream was merged into the local branch
+    Merged,
+    /// local commits were rebased on top of upstream
+    Rebased,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FastForward {
+    Allow,
+    Never,
+    Only,
+}
+
+/// `pull.rebase`/`branch.<name>.rebase` and `pull.ff` settings
+struct PullConfig {
+    rebase: bool,
+    ff: FastForward,
+}
+
+fn is_false(value: &str) -> bool {
+    matches!(
+        value.to_lowercase().as_str(),
+        "false" | "no" | "off" | "0"
+    )
+}
+
+fn pull_config(
+    repo: &Repository,
+    branch: &str,
+) -> Result<PullConfig> {
+    let config = repo.config()?;
+
+    let rebase = config
+        .get_string(&format!("branch.{}.rebase", branch))
+        .or_else(|_| config.get_string("pull.rebase"))
+        .map_or(false, |v| !is_false(&v));
+
+    let ff = match config.get_string("pull.ff") {
+        Ok(v) if v.to_lowercase() == "only" => FastForward::Only,
+        Ok(v) if is_false(&v) => FastForward::Never,
+        _ => FastForward::Allow,
+    };
+
+    Ok(PullConfig { rebase, ff })
+}
+
+/// fetches the upstream of the branch HEAD points to and integrates it.
+///
+/// fast-forwards when possible, otherwise merges or rebases following
+/// `pull.rebase` and `pull.ff`. if this would result in conflicts or
+/// overwrite local changes an `Err` is returned and neither the
+/// branch nor the working dir are touched.
+pub fn pull(repo_path: &str) -> Result<PullResult> {
+    scope_time!("pull");
+
+    let repo = utils::repo(repo_path)?;
+
+    let branch = get_branch_name(repo_path)?;
+    let upstream = get_branch_upstream(&repo, &branch)?;
+
+    fetch(repo_path, &upstream.remote, |_| ())?;
+
+    let branch_ref = format!("refs/heads/{}", branch);
+    let upstream_ref = repo.branch_upstream_name(&branch_ref)?;
+    let upstream_ref = upstream_ref.as_str().ok_or_else(|| {
+        Error::Generic("invalid upstream ref name".to_string())
+    })?;
+
+    let theirs = repo.reference_to_annotated_commit(
+        &repo.find_reference(upstream_ref)?,
+    )?;
+
+    let (analysis, _) = repo.merge_analysis(&[&theirs])?;
+    let config = pull_config(&repo, &branch)?;
+
+    if analysis.is_up_to_date() {
+        return Ok(PullResult::UpToDate);
+    }
+
+    if analysis.is_fast_forward()
+        && (config.rebase || config.ff != FastForward::Never)
+    {
+        checkout_and_update(
+            &repo,
+            &branch_ref,
+            theirs.id(),
+            "pull: fast-forward",
+        )?;
+        return Ok(PullResult::FastForward);
+    }
+
+    if config.rebase {
+        rebase(&repo, &branch_ref, &theirs)?;
+        return Ok(PullResult::Rebased);
+    }
+
+    if config.ff == FastForward::Only {
+        return Err(Error::Generic(format!(
+            "'{}' and '{}' have diverged, not possible to fast-forward (pull.ff=only)",
+            branch,
+            upstream_ref.trim_start_matches("refs/remotes/")
+        )));
+    }
+
+    merge(&repo, &branch_ref, upstream_ref, theirs.id())?;
+
+    Ok(PullResult::Merged)
+}
+
+/// checks out `target` (refusing to overwrite local changes)
+/// and points `branch_ref` to it
+fn checkout_and_update(
+    repo: &Repository,
+    branch_ref: &str,
+    target: Oid,
+    log_msg: &str,
+) -> Result<()> {
+    let commit = repo.find_commit(target)?;
+
+    repo.checkout_tree(
+        commit.as_object(),
+        Some(CheckoutBuilder::new().safe()),
+    )
+    .map_err(|e| {
+        Error::Generic(format!(
+            "pull aborted, local changes would be overwritten: {}",
+            e.message()
+        ))
+    })?;
+
+    repo.find_reference(branch_ref)?
+        .set_target(target, log_msg)?;
+
+    Ok(())
+}
+
+fn conflicts_error(index: &Index, operation: &str) -> Result<Error> {
+    let paths = index
+        .conflicts()?
+        .flatten()
+        .filter_map(|c| c.our.or(c.their).or(c.ancestor))
+        .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+        .collect::<Vec<_>>();
+
+    Ok(Error::Generic(format!(
+        "pull aborted, {} would conflict in:\n{}",
+        operation,
+        paths.join("\n")
+    )))
+}
+
+fn merge(
+    repo: &Repository,
+    branch_ref: &str,
+    upstream_ref: &str,
+    theirs: Oid,
+) -> Result<()> {
+    let head = repo.find_reference(branch_ref)?.peel_to_commit()?;
+    let theirs = repo.find_commit(theirs)?;
+
+    let mut index = repo.merge_commits(&head, &theirs, None)?;
+    if index.has_conflicts() {
+        return Err(conflicts_error(&index, "merge")?);
+    }
+
+    let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+    let signature = signature_allow_undefined_name(repo)?;
+    let msg = format!(
+        "Merge remote-tracking branch '{}'",
+        upstream_ref.trim_start_matches("refs/remotes/")
+    );
+
+    let id = create_commit(
+        repo,
+        &signature,
+        &signature,
+        &msg,
+        &tree,
+        &[&head, &theirs],
+    )?;
+
+    checkout_and_update(repo, branch_ref, id, "pull: merge")
+}
+
+/// rebases in memory first so nothing changes if a commit conflicts
+fn rebase(
+    repo: &Repository,
+    branch_ref: &str,
+    theirs: &AnnotatedCommit,
+) -> Result<()> {
+    let local = repo.reference_to_annotated_commit(
+        &repo.find_reference(branch_ref)?,
+    )?;
+
+    let mut options = RebaseOptions::new();
+    options.inmemory(true);
+
+    let mut rebase = repo.rebase(
+        Some(&local),
+        Some(theirs),
+        None,
+        Some(&mut options),
+    )?;
+
+    let signature = signature_allow_undefined_name(repo)?;
+    let mut last_id = theirs.id();
+
+    while let Some(op) = rebase.next() {
+        op?;
+
+        let index = rebase.inmemory_index()?;
+        if index.has_conflicts() {
+            let err = conflicts_error(&index, "rebase")?;
+            rebase.abort()?;
+            return Err(err);
+        }
+
+        match rebase.commit(None, &signature, None) {
+            Ok(id) => last_id = id,
+            // change is already part of upstream
+            Err(e) if e.code() == ErrorCode::Applied => (),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    rebase.finish(Some(&signature))?;
+
+    checkout_and_update(repo, branch_ref, last_id, "pull: rebase")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        push_upstream,
+        tests::{
+            bare_url, path, repo_clone, repo_init_bare, write_commit,
+        },
+        utils::get_head,
+        CommitId,
+    };
+    use std::fs;
+
+    /// returns (remote, pushing clone, pulling clone)
+    fn setup() -> (
+        (tempfile::TempDir, Repository),
+        (tempfile::TempDir, Repository),
+        (tempfile::TempDir, Repository),
+    ) {
+        let remote = repo_init_bare();
+        let upstream = repo_clone(&bare_url(&remote.1));
+        let root = upstream.1.path().parent().unwrap();
+        write_commit(root, "a.txt", "initial");
+        push_upstream(root.to_str().unwrap()).unwrap();
+
+        let local = repo_clone(&bare_url(&remote.1));
+
+        (remote, upstream, local)
+    }
+
+    #[test]
+    fn test_pull_up_to_date() {
+        let (_remote, _upstream, (_td, local)) = setup();
+
+        assert_eq!(pull(path(&local)).unwrap(), PullResult::UpToDate);
+    }
+
+    #[test]
+    fn test_pull_fast_forward() {
+        let (_remote, (_td1, upstream), (_td2, local)) = setup();
+
+        write_commit(upstream.path().parent().unwrap(), "b.txt", "b");
+        push_upstream(path(&upstream)).unwrap();
+
+        assert_eq!(
+            pull(path(&local)).unwrap(),
+            PullResult::FastForward
+        );
+        assert_eq!(
+            get_head(path(&local)).unwrap(),
+            get_head(path(&upstream)).unwrap()
+        );
+        assert!(local
+            .path()
+            .parent()
+            .unwrap()
+            .join("b.txt")
+            .exists());
+    }
+
+    #[test]
+    fn test_pull_merge() {
+        let (_remote, (_td1, upstream), (_td2, local)) = setup();
+
+        write_commit(upstream.path().parent().unwrap(), "b.txt", "b");
+        push_upstream(path(&upstream)).unwrap();
+        write_commit(local.path().parent().unwrap(), "c.txt", "c");
+
+        assert_eq!(pull(path(&local)).unwrap(), PullResult::Merged);
+
+        let head = local.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2);
+        assert!(local
+            .path()
+            .parent()
+            .unwrap()
+            .join("b.txt")
+            .exists());
+    }
+
+    #[test]
+    fn test_pull_rebase() {
+        let (_remote, (_td1, upstream), (_td2, local)) = setup();
+        local
+            .config()
+            .unwrap()
+            .set_bool("pull.rebase", true)
+            .unwrap();
+
+        write_commit(upstream.path().parent().unwrap(), "b.txt", "b");
+        push_upstream(path(&upstream)).unwrap();
+        write_commit(local.path().parent().unwrap(), "c.txt", "c");
+
+        assert_eq!(pull(path(&local)).unwrap(), PullResult::Rebased);
+
+        let head = local.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 1);
+        assert_eq!(head.message(), Some("c"));
+        assert_eq!(
+            CommitId::from(head.parent_id(0).unwrap()),
+            get_head(path(&upstream)).unwrap()
+        );
+        assert!(local
+            .path()
+            .parent()
+            .unwrap()
+            .join("b.txt")
+            .exists());
+    }
+
+    #[test]
+    fn test_pull_ff_only_diverged() {
+        let (_remote, (_td1, upstream), (_td2, local)) = setup();
+        local.config().unwrap().set_str("pull.ff", "only").unwrap();
+
+        write_commit(upstream.path().parent().unwrap(), "b.txt", "b");
+        push_upstream(path(&upstream)).unwrap();
+        write_commit(local.path().parent().unwrap(), "c.txt", "c");
+        let head = get_head(path(&local)).unwrap();
+
+        assert!(pull(path(&local)).is_err());
+        assert_eq!(get_head(path(&local)).unwrap(), head);
+    }
+
+    #[test]
+    fn test_pull_conflict_keeps_repo_untouched() {
+        let (_remote, (_td1, upstream), (_td2, local)) = setup();
+
+        write_commit(upstream.path().parent().unwrap(), "a.txt", "b");
+        push_upstream(path(&upstream)).unwrap();
+        let local_root = local.path().parent().unwrap();
+        write_commit(local_root, "a.txt", "c");
+        let head = get_head(path(&local)).unwrap();
+
+        let err = pull(path(&local)).unwrap_err().to_string();
+
+        assert!(err.contains("a.txt"));
+        assert_eq!(get_head(path(&local)).unwrap(), head);
+        assert_eq!(
+            fs::read_to_string(local_root.join("a.txt")).unwrap(),
+            "c"
+        );
+        assert_eq!(local.state(), git2::RepositoryState::Clean);
+    }
+}
@@ -0,0 +1,859 @@
+//! sync git api for interactive rebasing
+//!
+//! every entry of the plan is applied by its own single commit
+//! `git2::Rebase` onto the current (detached) HEAD, which allows
+//! reordering. the plan itself is kept in `.git/gitui-rebase` so the
+//! rebase can be stopped on conflicts or `edit` entries and continued
+//! later on.
+
+use super::{
+    commit::{
+        create_commit, signature_allow_undefined_name, update_head,
+    },
+    utils, CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{
+    build::CheckoutBuilder, Oid, Rebase, Repository, ResetType, Sort,
+};
+use scopetime::scope_time;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const STATE_DIR: &str = "gitui-rebase";
+const DETACHED: &str = "detached HEAD";
+
+/// what to do with a commit of the rebase plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RebaseAction {
+    /// use commit
+    Pick,
+    /// use commit with the new message of the entry
+    Reword,
+    /// meld into previous commit, keeping both messages
+    Squash,
+    /// meld into previous commit, dropping this message
+    Fixup,
+    /// remove commit
+    Drop,
+    /// use commit, but stop to amend it
+    Edit,
+}
+
+impl RebaseAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+            Self::Edit => "edit",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        Some(match s {
+            "pick" => Self::Pick,
+            "reword" => Self::Reword,
+            "squash" => Self::Squash,
+            "fixup" => Self::Fixup,
+            "drop" => Self::Drop,
+            "edit" => Self::Edit,
+            _ => return None,
+        })
+    }
+}
+
+/// single commit of the rebase plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebaseEntry {
+    ///
+    pub action: RebaseAction,
+    ///
+    pub id: CommitId,
+    /// first line of the commit message
+    pub summary: String,
+    /// new message for `RebaseAction::Reword`
+    pub message: Option<String>,
+}
+
+/// why `rebase_interactive` and friends returned
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RebaseProgress {
+    /// all entries applied, the branch points to the result
+    Done,
+    /// stopped on conflicts, resolve and stage them to continue
+    Conflict,
+    /// stopped on an `edit` entry
+    Edit,
+}
+
+/// state of the rebase in progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebaseStatus {
+    /// entries applied so far
+    pub done: usize,
+    /// entries in the plan
+    pub total: usize,
+    /// entry the rebase stopped on (action, commit)
+    pub stopped: Option<(RebaseAction, CommitId)>,
+    /// the stop is due to conflicts
+    pub conflict: bool,
+}
+
+fn state_dir(repo: &Repository) -> PathBuf {
+    repo.path().join(STATE_DIR)
+}
+
+fn read_state(dir: &Path, file: &str) -> Result<String> {
+    Ok(fs::read_to_string(dir.join(file))?.trim_end().to_string())
+}
+
+fn parse_line(line: &str) -> Result<(RebaseAction, CommitId)> {
+    let mut parts = line.split_whitespace();
+    let action = parts.next().and_then(RebaseAction::from_str);
+    let id = parts.next().map(Oid::from_str);
+
+    match (action, id) {
+        (Some(action), Some(Ok(id))) => Ok((action, id.into())),
+        _ => Err(Error::Generic(format!(
+            "invalid rebase plan line: '{}'",
+            line
+        ))),
+    }
+}
+
+fn format_line(action: RebaseAction, id: CommitId) -> String {
+    format!("{} {}\n", action.as_str(), id.to_string())
+}
+
+fn read_todo(dir: &Path) -> Result<Vec<(RebaseAction, CommitId)>> {
+    read_state(dir, "todo")?
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(parse_line)
+        .collect()
+}
+
+fn write_todo(
+    dir: &Path,
+    todo: &[(RebaseAction, CommitId)],
+) -> Result<()> {
+    let content = todo
+        .iter()
+        .map(|(action, id)| format_line(*action, *id))
+        .collect::<String>();
+    fs::write(dir.join("todo"), content)?;
+    Ok(())
+}
+
+fn mark_done(
+    dir: &Path,
+    action: RebaseAction,
+    id: CommitId,
+) -> Result<()> {
+    let mut done = fs::read_to_string(dir.join("done"))?;
+    done.push_str(&format_line(action, id));
+    fs::write(dir.join("done"), done)?;
+    Ok(())
+}
+
+/// commits between `base` (exclusive) and HEAD, oldest first,
+/// all set to `RebaseAction::Pick`
+pub fn get_rebase_plan(
+    repo_path: &str,
+    base: CommitId,
+) -> Result<Vec<RebaseEntry>> {
+    scope_time!("get_rebase_plan");
+
+    let repo = utils::repo(repo_path)?;
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    walk.push_head()?;
+    walk.hide(base.into())?;
+
+    walk.map(|id| {
+        let commit = repo.find_commit(id?)?;
+
+        if commit.parent_count() > 1 {
+            return Err(Error::Generic(format!(
+                "rebasing merge commits is not supported ({})",
+                commit.id()
+            )));
+        }
+
+        Ok(RebaseEntry {
+            action: RebaseAction::Pick,
+            id: commit.id().into(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+            message: None,
+        })
+    })
+    .collect()
+}
+
+/// starts rebasing the commits of `plan` onto `base`
+pub fn rebase_interactive(
+    repo_path: &str,
+    base: CommitId,
+    plan: &[RebaseEntry],
+) -> Result<RebaseProgress> {
+    scope_time!("rebase_interactive");
+
+    let repo = utils::repo(repo_path)?;
+    let dir = state_dir(&repo);
+
+    if dir.exists() || repo.state() != git2::RepositoryState::Clean {
+        return Err(Error::Generic(
+            "another operation is in progress".to_string(),
+        ));
+    }
+
+    if !super::is_workdir_clean(repo_path)? {
+        return Err(Error::Generic(
+            "commit or stash your changes before rebasing"
+                .to_string(),
+        ));
+    }
+
+    let first_used =
+        plan.iter().find(|e| e.action != RebaseAction::Drop);
+    if let Some(first) = first_used {
+        if matches!(
+            first.action,
+            RebaseAction::Squash | RebaseAction::Fixup
+        ) {
+            return Err(Error::Generic(
+                "cannot squash without a previous commit".to_string(),
+            ));
+        }
+    }
+
+    let head = repo.head()?;
+    let head_name = if head.is_branch() {
+        head.name().unwrap_or(DETACHED).to_string()
+    } else {
+        DETACHED.to_string()
+    };
+    let orig_head = head.peel_to_commit()?.id();
+
+    fs::create_dir(&dir)?;
+    fs::write(dir.join("head-name"), head_name)?;
+    fs::write(dir.join("orig-head"), orig_head.to_string())?;
+    fs::write(dir.join("done"), "")?;
+    write_todo(
+        &dir,
+        &plan.iter().map(|e| (e.action, e.id)).collect::<Vec<_>>(),
+    )?;
+    for entry in plan {
+        if let Some(msg) = &entry.message {
+            fs::write(
+                dir.join(format!("message-{}", entry.id.to_string())),
+                msg,
+            )?;
+        }
+    }
+
+    // untracked files are not covered by `is_workdir_clean`,
+    // a safe checkout refuses to overwrite them
+    let base_commit = repo.find_commit(base.into())?;
+    if let Err(e) = repo.checkout_tree(
+        base_commit.as_object(),
+        Some(CheckoutBuilder::new().safe()),
+    ) {
+        fs::remove_dir_all(&dir)?;
+        return Err(Error::Generic(format!(
+            "rebase aborted, local files would be overwritten: {}",
+            e.message()
+        )));
+    }
+    repo.set_head_detached(base.into())?;
+
+    run_todo(&repo)
+}
+
+/// applies the remaining entries until done or stopped
+fn run_todo(repo: &Repository) -> Result<RebaseProgress> {
+    let dir = state_dir(repo);
+
+    loop {
+        let mut todo = read_todo(&dir)?;
+
+        if todo.is_empty() {
+            finish(repo)?;
+            return Ok(RebaseProgress::Done);
+        }
+
+        let (action, id) = todo.remove(0);
+        write_todo(&dir, &todo)?;
+
+        if action == RebaseAction::Drop {
+            mark_done(&dir, action, id)?;
+            continue;
+        }
+
+        fs::write(dir.join("stopped"), format_line(action, id))?;
+
+        let commit = repo.find_commit(id.into())?;
+        let head = repo.head()?.peel_to_commit()?.id();
+
+        let mut rebase = repo.rebase(
+            Some(&repo.find_annotated_commit(commit.id())?),
+            Some(&repo.find_annotated_commit(commit.parent_id(0)?)?),
+            Some(&repo.find_annotated_commit(head)?),
+            None,
+        )?;
+
+        if let Some(op) = rebase.next() {
+            op?;
+        }
+
+        if repo.index()?.has_conflicts() {
+            return Ok(RebaseProgress::Conflict);
+        }
+
+        commit_step(repo, &mut rebase, action, id)?;
+        mark_done(&dir, action, id)?;
+
+        if action == RebaseAction::Edit {
+            return Ok(RebaseProgress::Edit);
+        }
+
+        fs::remove_file(dir.join("stopped"))?;
+    }
+}
+
+/// commits the applied entry (signed when `commit.gpgsign` is set)
+/// and melds it into the previous commit for squash/fixup
+fn commit_step(
+    repo: &Repository,
+    rebase: &mut Rebase,
+    action: RebaseAction,
+    id: CommitId,
+) -> Result<()> {
+    let signature = signature_allow_undefined_name(repo)?;
+
+    let commit = repo.find_commit(id.into())?;
+    let head = repo.head()?.peel_to_commit()?;
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+    let message = if action == RebaseAction::Reword {
+        fs::read_to_string(
+            state_dir(repo)
+                .join(format!("message-{}", id.to_string())),
+        )
+        .ok()
+    } else {
+        None
+    };
+
+    // nothing left to commit if the change is already applied
+    let committed = tree.id() != head.tree_id();
+    if committed {
+        let new_id = create_commit(
+            repo,
+            &commit.author(),
+            &signature,
+            message.as_deref().unwrap_or_else(|| {
+                commit.message().unwrap_or_default()
+            }),
+            &tree,
+            &[&head],
+        )?;
+        update_head(repo, new_id, "rebase")?;
+    }
+
+    rebase.finish(Some(&signature))?;
+
+    if committed
+        && matches!(
+            action,
+            RebaseAction::Squash | RebaseAction::Fixup
+        )
+    {
+        squash_head(repo, action == RebaseAction::Squash)?;
+    }
+
+    Ok(())
+}
+
+/// replaces HEAD and its parent by a single commit
+fn squash_head(repo: &Repository, keep_message: bool) -> Result<()> {
+    let head = repo.head()?.peel_to_commit()?;
+    let prev = head.parent(0)?;
+
+    let prev_msg = prev.message().unwrap_or_default().trim_end();
+    let msg = if keep_message {
+        format!(
+            "{}\n\n{}",
+            prev_msg,
+            head.message().unwrap_or_default().trim_end()
+        )
+    } else {
+        prev_msg.to_string()
+    };
+
+    let parents = prev.parents().collect::<Vec<_>>();
+    let parents = parents.iter().collect::<Vec<_>>();
+
+    let id = create_commit(
+        repo,
+        &prev.author(),
+        &signature_allow_undefined_name(repo)?,
+        &msg,
+        &head.tree()?,
+        parents.as_slice(),
+    )?;
+
+    update_head(repo, id, "rebase (squash)")?;
+
+    Ok(())
+}
+
+/// points the rebased branch to the result and checks it out
+fn finish(repo: &Repository) -> Result<()> {
+    let dir = state_dir(repo);
+    let head_name = read_state(&dir, "head-name")?;
+
+    if head_name != DETACHED {
+        let head = repo.head()?.peel_to_commit()?.id();
+        repo.reference(&head_name, head, true, "rebase: finish")?;
+        repo.set_head(&head_name)?;
+    }
+
+    fs::remove_dir_all(dir)?;
+
+    Ok(())
+}
+
+fn check_in_progress(repo: &Repository) -> Result<PathBuf> {
+    let dir = state_dir(repo);
+    if dir.exists() {
+        Ok(dir)
+    } else {
+        Err(Error::Generic("no rebase in progress".to_string()))
+    }
+}
+
+/// continues after conflicts got resolved or
+/// an `edit` entry got amended
+pub fn rebase_continue(repo_path: &str) -> Result<RebaseProgress> {
+    scope_time!("rebase_continue");
+
+    let repo = utils::repo(repo_path)?;
+    let dir = check_in_progress(&repo)?;
+
+    let stopped = read_state(&dir, "stopped").ok();
+
+    if let Some(stopped) = stopped {
+        let (action, id) = parse_line(&stopped)?;
+
+        if let Ok(mut rebase) = repo.open_rebase(None) {
+            if repo.index()?.has_conflicts() {
+                return Err(Error::Generic(
+                    "resolve and stage all conflicts first"
+                        .to_string(),
+                ));
+            }
+
+            commit_step(&repo, &mut rebase, action, id)?;
+            mark_done(&dir, action, id)?;
+
+            if action == RebaseAction::Edit {
+                return Ok(RebaseProgress::Edit);
+            }
+        } else {
+            amend_head_with_index(&repo)?;
+        }
+
+        fs::remove_file(dir.join("stopped"))?;
+    }
+
+    run_todo(&repo)
+}
+
+/// folds staged changes into HEAD
+fn amend_head_with_index(repo: &Repository) -> Result<()> {
+    let head = repo.head()?.peel_to_commit()?;
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+    if tree.id() != head.tree_id() {
+        let parents = head.parents().collect::<Vec<_>>();
+        let parents = parents.iter().collect::<Vec<_>>();
+
+        let id = create_commit(
+            repo,
+            &head.author(),
+            &signature_allow_undefined_name(repo)?,
+            head.message().unwrap_or_default(),
+            &tree,
+            parents.as_slice(),
+        )?;
+        update_head(repo, id, "rebase (amend)")?;
+    }
+
+    Ok(())
+}
+
+/// drops the entry the rebase stopped on and continues
+pub fn rebase_skip(repo_path: &str) -> Result<RebaseProgress> {
+    scope_time!("rebase_skip");
+
+    let repo = utils::repo(repo_path)?;
+    let dir = check_in_progress(&repo)?;
+
+    if let Ok(stopped) = read_state(&dir, "stopped") {
+        let (_, id) = parse_line(&stopped)?;
+
+        if repo.open_rebase(None).is_ok() {
+            // throw away the half applied commit
+            repo.cleanup_state()?;
+            let head = repo.head()?.peel_to_commit()?;
+            repo.reset(head.as_object(), ResetType::Hard, None)?;
+            mark_done(&dir, RebaseAction::Drop, id)?;
+        }
+
+        fs::remove_file(dir.join("stopped"))?;
+    }
+
+    run_todo(&repo)
+}
+
+/// stops the rebase and restores the branch as it was before
+pub fn rebase_abort(repo_path: &str) -> Result<()> {
+    scope_time!("rebase_abort");
+
+    let repo = utils::repo(repo_path)?;
+    let dir = check_in_progress(&repo)?;
+
+    let head_name = read_state(&dir, "head-name")?;
+    let orig_head = Oid::from_str(&read_state(&dir, "orig-head")?)?;
+
+    repo.cleanup_state()?;
+
+    if head_name == DETACHED {
+        repo.set_head_detached(orig_head)?;
+    } else {
+        repo.set_head(&head_name)?;
+    }
+
+    let commit = repo.find_commit(orig_head)?;
+    repo.reset(commit.as_object(), ResetType::Hard, None)?;
+
+    fs::remove_dir_all(dir)?;
+
+    Ok(())
+}
+
+/// progress of the rebase in progress, `None` if there is none
+pub fn rebase_status(
+    repo_path: &str,
+) -> Result<Option<RebaseStatus>> {
+    scope_time!("rebase_status");
+
+    let repo = utils::repo(repo_path)?;
+    let dir = state_dir(&repo);
+
+    if !dir.exists() {
+        return Ok(None);
+    }
+
+    let done = read_state(&dir, "done")?.lines().count();
+    let todo = read_todo(&dir)?.len();
+    let stopped = read_state(&dir, "stopped")
+        .ok()
+        .map(|l| parse_line(&l))
+        .transpose()?;
+    let conflict =
+        stopped.is_some() && repo.open_rebase(None).is_ok();
+
+    // an entry stopped on conflicts is not done yet
+    let total = done + todo + if conflict { 1 } else { 0 };
+
+    Ok(Some(RebaseStatus {
+        done,
+        total,
+        stopped,
+        conflict,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        stage_add_file,
+        tests::{path, repo_init, write_commit},
+        utils::get_head,
+    };
+
+    /// base commit and three commits `a`,`b`,`c` on top
+    fn setup(repo: &Repository) -> CommitId {
+        let root = repo.path().parent().unwrap();
+        write_commit(root, "base.txt", "base");
+        let base = get_head(path(repo)).unwrap();
+        write_commit(root, "a.txt", "a");
+        write_commit(root, "b.txt", "b");
+        write_commit(root, "c.txt", "c");
+        base
+    }
+
+    fn messages(repo: &Repository, count: usize) -> Vec<String> {
+        let mut res = Vec::new();
+        let mut commit =
+            repo.head().unwrap().peel_to_commit().unwrap();
+        for _ in 0..count {
+            res.push(commit.message().unwrap().to_string());
+            commit = commit.parent(0).unwrap();
+        }
+        res
+    }
+
+    fn with_actions(
+        plan: Vec<RebaseEntry>,
+        actions: &[RebaseAction],
+    ) -> Vec<RebaseEntry> {
+        plan.into_iter()
+            .zip(actions)
+            .map(|(e, a)| RebaseEntry { action: *a, ..e })
+            .collect()
+    }
+
+    #[test]
+    fn test_plan() {
+        let (_td, repo) = repo_init().unwrap();
+        let base = setup(&repo);
+
+        let plan = get_rebase_plan(path(&repo), base).unwrap();
+
+        assert_eq!(
+            plan.iter()
+                .map(|e| e.summary.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert!(plan.iter().all(|e| e.action == RebaseAction::Pick));
+    }
+
+    #[test]
+    fn test_untracked_file_in_base() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        write_commit(root, "x.txt", "x");
+        let base = get_head(path(&repo)).unwrap();
+        fs::remove_file(root.join("x.txt")).unwrap();
+        crate::sync::stage_addremoved(
+            path(&repo),
+            Path::new("x.txt"),
+        )
+        .unwrap();
+        crate::sync::commit(path(&repo), "remove x").unwrap();
+        let head = get_head(path(&repo)).unwrap();
+
+        fs::write(root.join("x.txt"), "untracked").unwrap();
+
+        let plan = get_rebase_plan(path(&repo), base).unwrap();
+        assert!(rebase_interactive(path(&repo), base, &plan).is_err());
+
+        assert_eq!(
+            fs::read_to_string(root.join("x.txt")).unwrap(),
+            "untracked"
+        );
+        assert_eq!(get_head(path(&repo)).unwrap(), head);
+        assert_eq!(repo.head().unwrap().shorthand(), Some("master"));
+        assert_eq!(rebase_status(path(&repo)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_reorder_drop_reword() {
+        let (_td, repo) = repo_init().unwrap();
+        let base = setup(&repo);
+
+        let mut plan = get_rebase_plan(path(&repo), base).unwrap();
+        plan.swap(0, 2);
+        plan[1].action = RebaseAction::Drop;
+        plan[2].action = RebaseAction::Reword;
+        plan[2].message = Some("a2".to_string());
+
+        assert_eq!(
+            rebase_interactive(path(&repo), base, &plan).unwrap(),
+            RebaseProgress::Done
+        );
+
+        assert_eq!(messages(&repo, 3), vec!["a2", "c", "base"]);
+        assert_eq!(repo.head().unwrap().shorthand(), Some("master"));
+        assert!(!repo
+            .path()
+            .parent()
+            .unwrap()
+            .join("b.txt")
+            .exists());
+        assert_eq!(rebase_status(path(&repo)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_squash_fixup() {
+        let (_td, repo) = repo_init().unwrap();
+        let base = setup(&repo);
+
+        let plan = with_actions(
+            get_rebase_plan(path(&repo), base).unwrap(),
+            &[
+                RebaseAction::Pick,
+                RebaseAction::Squash,
+                RebaseAction::Fixup,
+            ],
+        );
+
+        rebase_interactive(path(&repo), base, &plan).unwrap();
+
+        assert_eq!(messages(&repo, 2), vec!["a\n\nb", "base"]);
+        let root = repo.path().parent().unwrap();
+        assert!(root.join("c.txt").exists());
+    }
+
+    #[test]
+    fn test_edit_continue() {
+        let (_td, repo) = repo_init().unwrap();
+        let base = setup(&repo);
+        let root = repo.path().parent().unwrap();
+
+        let plan = with_actions(
+            get_rebase_plan(path(&repo), base).unwrap(),
+            &[
+                RebaseAction::Edit,
+                RebaseAction::Pick,
+                RebaseAction::Pick,
+            ],
+        );
+
+        assert_eq!(
+            rebase_interactive(path(&repo), base, &plan).unwrap(),
+            RebaseProgress::Edit
+        );
+
+        let status = rebase_status(path(&repo)).unwrap().unwrap();
+        assert_eq!(status.done, 1);
+        assert_eq!(status.total, 3);
+        assert_eq!(
+            status.stopped,
+            Some((RebaseAction::Edit, plan[0].id))
+        );
+        assert!(!status.conflict);
+
+        fs::write(root.join("a.txt"), "amended").unwrap();
+        stage_add_file(path(&repo), Path::new("a.txt")).unwrap();
+
+        assert_eq!(
+            rebase_continue(path(&repo)).unwrap(),
+            RebaseProgress::Done
+        );
+
+        assert_eq!(messages(&repo, 4), vec!["c", "b", "a", "base"]);
+        let a = repo
+            .head()
+            .unwrap()
+            .peel_to_commit()
+            .unwrap()
+            .parent(0)
+            .unwrap()
+            .parent(0)
+            .unwrap();
+        let blob = a
+            .tree()
+            .unwrap()
+            .get_name("a.txt")
+            .unwrap()
+            .to_object(&repo)
+            .unwrap();
+        assert_eq!(blob.as_blob().unwrap().content(), b"amended");
+    }
+
+    #[test]
+    fn test_conflict_skip_abort() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        write_commit(root, "a.txt", "base");
+        let base = get_head(path(&repo)).unwrap();
+        write_commit(root, "a.txt", "1");
+        write_commit(root, "a.txt", "2");
+        let orig = get_head(path(&repo)).unwrap();
+
+        // applying "2" without "1" conflicts
+        let plan = with_actions(
+            get_rebase_plan(path(&repo), base).unwrap(),
+            &[RebaseAction::Drop, RebaseAction::Pick],
+        );
+
+        assert_eq!(
+            rebase_interactive(path(&repo), base, &plan).unwrap(),
+            RebaseProgress::Conflict
+        );
+        assert!(
+            rebase_status(path(&repo)).unwrap().unwrap().conflict
+        );
+
+        rebase_abort(path(&repo)).unwrap();
+
+        assert_eq!(get_head(path(&repo)).unwrap(), orig);
+        assert_eq!(repo.head().unwrap().shorthand(), Some("master"));
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "2"
+        );
+        assert_eq!(rebase_status(path(&repo)).unwrap(), None);
+
+        rebase_interactive(path(&repo), base, &plan).unwrap();
+
+        assert_eq!(
+            rebase_skip(path(&repo)).unwrap(),
+            RebaseProgress::Done
+        );
+        assert_eq!(get_head(path(&repo)).unwrap(), base);
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "base"
+        );
+    }
+
+    #[test]
+    fn test_conflict_continue() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        write_commit(root, "a.txt", "base");
+        let base = get_head(path(&repo)).unwrap();
+        write_commit(root, "a.txt", "1");
+        write_commit(root, "a.txt", "2");
+
+        let mut plan = get_rebase_plan(path(&repo), base).unwrap();
+        plan.swap(0, 1);
+
+        assert_eq!(
+            rebase_interactive(path(&repo), base, &plan).unwrap(),
+            RebaseProgress::Conflict
+        );
+        assert!(rebase_continue(path(&repo)).is_err());
+
+        fs::write(root.join("a.txt"), "2").unwrap();
+        stage_add_file(path(&repo), Path::new("a.txt")).unwrap();
+
+        // "1" conflicts again on top of "2"
+        assert_eq!(
+            rebase_continue(path(&repo)).unwrap(),
+            RebaseProgress::Conflict
+        );
+
+        fs::write(root.join("a.txt"), "1").unwrap();
+        stage_add_file(path(&repo), Path::new("a.txt")).unwrap();
+
+        assert_eq!(
+            rebase_continue(path(&repo)).unwrap(),
+            RebaseProgress::Done
+        );
+        assert_eq!(messages(&repo, 3), vec!["1", "2", "base"]);
+    }
+}
@@ -0,0 +1,103 @@
+//! sync git api for reading the reflog
+
+use super::{utils::repo, CommitId};
+use crate::error::Result;
+use scopetime::scope_time;
+
+/// single entry of a reflog
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReflogEntry {
+    /// commit the ref pointed to after the operation
+    pub id: CommitId,
+    /// commit the ref pointed to before the operation
+    pub previous: CommitId,
+    /// operation message, like `commit (amend): ..`
+    pub message: String,
+    /// seconds since epoch
+    pub time: i64,
+    ///
+    pub committer: String,
+}
+
+/// returns the reflog of `refname` (like `HEAD`), newest first
+pub fn get_reflog(
+    repo_path: &str,
+    refname: &str,
+) -> Result<Vec<ReflogEntry>> {
+    scope_time!("get_reflog");
+
+    let repo = repo(repo_path)?;
+    let reflog = repo.reflog(refname)?;
+
+    let entries = reflog
+        .iter()
+        .map(|e| {
+            let committer = e.committer();
+            ReflogEntry {
+                id: e.id_new().into(),
+                previous: e.id_old().into(),
+                message: e.message().unwrap_or_default().to_string(),
+                time: committer.when().seconds(),
+                committer: committer
+                    .name()
+                    .unwrap_or("<unknown>")
+                    .to_string(),
+            }
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        reset_head,
+        tests::{repo_init, write_commit},
+        utils::get_head,
+        ResetMode,
+    };
+
+    #[test]
+    fn test_reflog_order() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = get_head(repo_path).unwrap();
+        write_commit(root, "a.txt", "a");
+        let a = get_head(repo_path).unwrap();
+
+        let reflog = get_reflog(repo_path, "HEAD").unwrap();
+
+        assert_eq!(reflog[0].id, a);
+        assert_eq!(reflog[0].previous, first);
+        assert!(reflog[0].message.starts_with("commit"));
+        assert_eq!(reflog.last().unwrap().id, first);
+    }
+
+    #[test]
+    fn test_reflog_after_reset() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = get_head(repo_path).unwrap();
+        write_commit(root, "a.txt", "a");
+        let a = get_head(repo_path).unwrap();
+
+        reset_head(repo_path, first, ResetMode::Hard).unwrap();
+
+        let reflog = get_reflog(repo_path, "HEAD").unwrap();
+
+        assert_eq!(reflog[0].id, first);
+        assert_eq!(reflog[0].previous, a);
+        assert!(reflog[0].message.starts_with("reset: moving to"));
+
+        // undo the reset using the entry before it
+        reset_head(repo_path, reflog[1].id, ResetMode::Hard).unwrap();
+        assert_eq!(get_head(repo_path).unwrap(), a);
+        assert!(root.join("a.txt").exists());
+    }
+}
@@ -0,0 +1,204 @@
+//! refs decorating commits in the log
+
+use super::{get_tags, utils::repo, CommitId};
+use crate::error::Result;
+use scopetime::scope_time;
+use std::collections::BTreeMap;
+
+/// kind of a ref pointing to a commit, in the order decorations
+/// of a commit are listed
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum RefKind {
+    /// `HEAD`, naming the checked out branch unless detached
+    Head,
+    ///
+    LocalBranch,
+    ///
+    RemoteBranch,
+    ///
+    Tag,
+    ///
+    Stash,
+}
+
+/// a single ref pointing to a commit
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommitRef {
+    ///
+    pub kind: RefKind,
+    /// short name like `master`, `origin/master` or `HEAD -> master`
+    pub name: String,
+}
+
+/// hashmap of target commit hash to all refs pointing to it
+pub type Refs = BTreeMap<CommitId, Vec<CommitRef>>;
+
+/// returns `Refs` filled with HEAD, branches, remote-tracking
+/// branches, tags and the stash
+pub fn get_refs(repo_path: &str) -> Result<Refs> {
+    scope_time!("get_refs");
+
+    let mut res = Refs::new();
+    let mut adder = |id, kind, name: String| {
+        res.entry(id).or_default().push(CommitRef { kind, name });
+    };
+
+    let repo = repo(repo_path)?;
+
+    // unborn or broken HEAD gets no decoration
+    let head = repo.head().ok();
+    let head_branch = head
+        .as_ref()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.name().map(String::from));
+
+    if let Some(head) = &head {
+        if let Ok(commit) = head.peel_to_commit() {
+            let name = match head.shorthand() {
+                Some(branch) if head.is_branch() => {
+                    format!("HEAD -> {}", branch)
+                }
+                _ => String::from("HEAD"),
+            };
+            adder(CommitId::new(commit.id()), RefKind::Head, name);
+        }
+    }
+
+    for reference in repo.references()? {
+        let reference = reference?;
+
+        // symbolic refs like `origin/HEAD` just repeat a branch
+        if reference.symbolic_target().is_some() {
+            continue;
+        }
+
+        let (name, short) =
+            match (reference.name(), reference.shorthand()) {
+                (Some(name), Some(short)) => (name, short),
+                _ => continue,
+            };
+
+        let kind = if head_branch.as_deref() == Some(name) {
+            continue;
+        } else if reference.is_branch() {
+            RefKind::LocalBranch
+        } else if reference.is_remote() {
+            RefKind::RemoteBranch
+        } else if name == "refs/stash" {
+            RefKind::Stash
+        } else {
+            continue;
+        };
+
+        if let Ok(commit) = reference.peel_to_commit() {
+            adder(
+                CommitId::new(commit.id()),
+                kind,
+                short.to_string(),
+            );
+        }
+    }
+
+    for (id, tags) in get_tags(repo_path)? {
+        for tag in tags {
+            adder(id, RefKind::Tag, tag);
+        }
+    }
+
+    for refs in res.values_mut() {
+        refs.sort();
+    }
+
+    Ok(res)
+}
+
+/// names of the tags among `refs`
+pub fn tags_of(refs: &[CommitRef]) -> Vec<String> {
+    refs.iter()
+        .filter(|r| r.kind == RefKind::Tag)
+        .map(|r| r.name.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch, stash_save,
+        tests::{repo_init, write_commit},
+        utils::get_head,
+    };
+    use git2::ObjectType;
+    use std::{fs::File, io::Write};
+
+    fn names(refs: &Refs, id: CommitId) -> Vec<(RefKind, &str)> {
+        refs[&id]
+            .iter()
+            .map(|r| (r.kind, r.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_head_and_branches() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = get_head(repo_path).unwrap();
+        create_branch(repo_path, "feature").unwrap();
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit(root, "a.txt", "a");
+        let second = get_head(repo_path).unwrap();
+
+        let target =
+            repo.find_object(first.into(), Some(ObjectType::Commit));
+        repo.tag_lightweight("v1", &target.unwrap(), false).unwrap();
+
+        let refs = get_refs(repo_path).unwrap();
+
+        assert_eq!(
+            names(&refs, second),
+            vec![(RefKind::Head, "HEAD -> master")]
+        );
+        assert_eq!(
+            names(&refs, first),
+            vec![
+                (RefKind::LocalBranch, "feature"),
+                (RefKind::Tag, "v1")
+            ]
+        );
+        assert_eq!(tags_of(&refs[&first]), vec!["v1"]);
+    }
+
+    #[test]
+    fn test_detached_and_stash() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let head = get_head(repo_path).unwrap();
+        repo.set_head_detached(head.into()).unwrap();
+
+        File::create(root.join("foo.txt"))
+            .unwrap()
+            .write_all(b"test")
+            .unwrap();
+        let stash = stash_save(repo_path, None, true, false).unwrap();
+
+        let refs = get_refs(repo_path).unwrap();
+
+        assert_eq!(
+            names(&refs, head),
+            vec![
+                (RefKind::Head, "HEAD"),
+                (RefKind::LocalBranch, "master")
+            ]
+        );
+        assert_eq!(
+            names(&refs, stash),
+            vec![(RefKind::Stash, "stash")]
+        );
+    }
+}
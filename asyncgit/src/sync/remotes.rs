@@ -0,0 +1,412 @@
+//! sync git api for interacting with remotes
+
+use super::{
+    branch::get_branch_name,
+    hooks::{hooks_pre_push, HookResult},
+    utils,
+};
+use crate::error::{Error, Result};
+use git2::{
+    Cred, CredentialType, FetchOptions, Oid, PushOptions, Remote,
+    RemoteCallbacks, Repository,
+};
+use scopetime::scope_time;
+
+/// remote and remote branch a local branch is tracking
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchUpstream {
+    /// name of the remote (e.g. `origin`)
+    pub remote: String,
+    /// full ref name on the remote (e.g. `refs/heads/master`)
+    pub merge: String,
+}
+
+/// transfer progress of a fetch
+#[derive(Debug, Default, Clone, Copy, PartialEq, Hash)]
+pub struct RemoteProgress {
+    /// objects received so far
+    pub received_objects: usize,
+    /// objects the remote is going to send
+    pub total_objects: usize,
+    /// bytes received so far
+    pub received_bytes: usize,
+}
+
+impl RemoteProgress {
+    /// progress in percent (0..=100) based on received objects
+    pub fn percent(&self) -> u8 {
+        if self.total_objects == 0 {
+            return 100;
+        }
+
+        let percent = self.received_objects.saturating_mul(100)
+            / self.total_objects;
+
+        percent.min(100) as u8
+    }
+}
+
+/// returns the names of all configured remotes
+pub fn get_remotes(repo_path: &str) -> Result<Vec<String>> {
+    scope_time!("get_remotes");
+
+    let repo = utils::repo(repo_path)?;
+    let remotes = repo.remotes()?;
+
+    Ok(remotes.iter().flatten().map(String::from).collect())
+}
+
+/// returns the configured upstream of the local branch `branch`
+pub(crate) fn get_branch_upstream(
+    repo: &Repository,
+    branch: &str,
+) -> Result<BranchUpstream> {
+    let no_upstream = || {
+        Error::Generic(format!(
+            "branch '{}' has no upstream configured",
+            branch
+        ))
+    };
+
+    let remote = repo
+        .branch_upstream_remote(&format!("refs/heads/{}", branch))
+        .map_err(|_| no_upstream())?;
+    let remote = remote.as_str().ok_or_else(no_upstream)?.to_string();
+
+    let merge = repo
+        .config()?
+        .get_string(&format!("branch.{}.merge", branch))
+        .map_err(|_| no_upstream())?;
+
+    Ok(BranchUpstream { remote, merge })
+}
+
+/// callbacks used for all remote operations.
+/// credentials are looked up in the ssh agent and the configured
+/// credential helper, each tried once because libgit2 keeps asking
+/// as long as we hand out (wrong) credentials.
+pub(crate) fn remote_callbacks<'a>() -> RemoteCallbacks<'a> {
+    let mut tried_ssh_agent = false;
+    let mut tried_cred_helper = false;
+
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username_from_url, allowed| {
+        if allowed.contains(CredentialType::SSH_KEY)
+            && !tried_ssh_agent
+        {
+            tried_ssh_agent = true;
+            return Cred::ssh_key_from_agent(
+                username_from_url.unwrap_or("git"),
+            );
+        }
+
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT)
+            && !tried_cred_helper
+        {
+            tried_cred_helper = true;
+            let config = git2::Config::open_default()?;
+            return Cred::credential_helper(
+                &config,
+                url,
+                username_from_url,
+            );
+        }
+
+        if allowed.contains(CredentialType::DEFAULT) {
+            return Cred::default();
+        }
+
+        Err(git2::Error::from_str("no valid credentials available"))
+    });
+
+    callbacks
+}
+
+/// fetches `remote` using its configured refspecs.
+/// `progress` is called whenever new objects arrive.
+pub fn fetch<F>(
+    repo_path: &str,
+    remote: &str,
+    progress: F,
+) -> Result<()>
+where
+    F: FnMut(RemoteProgress),
+{
+    scope_time!("fetch");
+
+    let repo = utils::repo(repo_path)?;
+    let mut remote = repo.find_remote(remote)?;
+
+    let mut progress = progress;
+    let mut callbacks = remote_callbacks();
+    callbacks.transfer_progress(|p| {
+        progress(RemoteProgress {
+            received_objects: p.received_objects(),
+            total_objects: p.total_objects(),
+            received_bytes: p.received_bytes(),
+        });
+        true
+    });
+
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(callbacks);
+
+    remote.fetch(&[] as &[&str], Some(&mut options), None)?;
+
+    Ok(())
+}
+
+/// runs the `pre-push` hook, a failing hook rejects the push
+fn pre_push(
+    repo: &Repository,
+    remote: &Remote,
+    local_ref: &str,
+    upstream: &BranchUpstream,
+) -> Result<()> {
+    let local = repo.refname_to_id(local_ref)?;
+    let tracking = format!(
+        "refs/remotes/{}/{}",
+        upstream.remote,
+        upstream
+            .merge
+            .strip_prefix("refs/heads/")
+            .unwrap_or(&upstream.merge)
+    );
+    // what the remote had when last fetched, unknown if never fetched
+    let remote_id = repo
+        .refname_to_id(&tracking)
+        .unwrap_or_else(|_| Oid::zero());
+
+    let updates = format!(
+        "{} {} {} {}\n",
+        local_ref, local, upstream.merge, remote_id
+    );
+
+    match hooks_pre_push(
+        repo,
+        &upstream.remote,
+        remote.url().unwrap_or_default(),
+        &updates,
+    )? {
+        HookResult::Ok => Ok(()),
+        HookResult::NotOk(e) => Err(Error::Generic(format!(
+            "pre-push hook rejected push:\n{}",
+            e
+        ))),
+    }
+}
+
+/// pushes the branch HEAD points to into its configured upstream.
+///
+/// returns an `Err` if the branch has no upstream or the remote
+/// rejected the update (e.g. non-fast-forward, a refusing remote hook
+/// or a failing local `pre-push` hook)
+pub fn push_upstream(repo_path: &str) -> Result<()> {
+    scope_time!("push_upstream");
+
+    let repo = utils::repo(repo_path)?;
+
+    let branch = get_branch_name(repo_path)?;
+    let upstream = get_branch_upstream(&repo, &branch)?;
+
+    let mut remote = repo.find_remote(&upstream.remote)?;
+
+    let local_ref = format!("refs/heads/{}", branch);
+    let refspec = format!("{}:{}", local_ref, upstream.merge);
+
+    pre_push(&repo, &remote, &local_ref, &upstream)?;
+
+    let mut remote_msg = String::new();
+    let mut rejected = None;
+
+    let res = {
+        let mut callbacks = remote_callbacks();
+        callbacks.sideband_progress(|data| {
+            remote_msg.push_str(&String::from_utf8_lossy(data));
+            true
+        });
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(status) = status {
+                rejected = Some(format!("{}: {}", refname, status));
+            }
+            Ok(())
+        });
+
+        let mut options = PushOptions::new();
+        options.remote_callbacks(callbacks);
+
+        remote.push(&[refspec.as_str()], Some(&mut options))
+    };
+
+    let remote_msg = remote_msg.trim();
+    let with_remote_msg = |msg: String| {
+        if remote_msg.is_empty() {
+            msg
+        } else {
+            format!("{}\n{}", msg, remote_msg)
+        }
+    };
+
+    if let Err(e) = res {
+        return Err(Error::Generic(with_remote_msg(format!(
+            "push to '{}' failed: {}",
+            upstream.remote,
+            e.message()
+        ))));
+    }
+
+    if let Some(rejected) = rejected {
+        return Err(Error::Generic(with_remote_msg(format!(
+            "push to '{}' rejected: {}",
+            upstream.remote, rejected
+        ))));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        tests::{
+            bare_url, repo_clone, repo_init, repo_init_bare,
+            write_commit,
+        },
+        utils::get_head,
+    };
+    use tempfile::TempDir;
+
+    fn set_upstream(repo: &Repository) {
+        let mut config = repo.config().unwrap();
+        config.set_str("branch.master.remote", "origin").unwrap();
+        config
+            .set_str("branch.master.merge", "refs/heads/master")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_push_upstream() {
+        let (_remote_td, remote) = repo_init_bare();
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo.remote("origin", &bare_url(&remote)).unwrap();
+        set_upstream(&repo);
+
+        write_commit(root, "foo", "commit1");
+
+        push_upstream(repo_path).unwrap();
+
+        let remote_head =
+            remote.refname_to_id("refs/heads/master").unwrap();
+        assert_eq!(remote_head, get_head(repo_path).unwrap().into());
+    }
+
+    #[test]
+    fn test_push_no_upstream() {
+        let (_remote_td, remote) = repo_init_bare();
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo.remote("origin", &bare_url(&remote)).unwrap();
+
+        assert!(push_upstream(repo_path).is_err());
+        assert!(remote.refname_to_id("refs/heads/master").is_err());
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (_remote_td, remote) = repo_init_bare();
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo.remote("origin", &bare_url(&remote)).unwrap();
+        set_upstream(&repo);
+        write_commit(root, "foo", "commit1");
+        push_upstream(repo_path).unwrap();
+
+        let other_td = TempDir::new().unwrap();
+        let other = Repository::init(other_td.path()).unwrap();
+        other.remote("origin", &bare_url(&remote)).unwrap();
+        let other_path = other_td.path().to_str().unwrap();
+
+        assert_eq!(get_remotes(other_path).unwrap(), vec!["origin"]);
+
+        let mut last_progress = RemoteProgress::default();
+        fetch(other_path, "origin", |p| last_progress = p).unwrap();
+
+        assert_eq!(last_progress.percent(), 100);
+        assert!(last_progress.received_objects > 0);
+        assert_eq!(
+            other
+                .refname_to_id("refs/remotes/origin/master")
+                .unwrap(),
+            get_head(repo_path).unwrap().into()
+        );
+    }
+
+    #[test]
+    fn test_push_rejected_non_fast_forward() {
+        let (_remote_td, remote) = repo_init_bare();
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo.remote("origin", &bare_url(&remote)).unwrap();
+        set_upstream(&repo);
+        push_upstream(repo_path).unwrap();
+
+        let (_other_td, other) = repo_clone(&bare_url(&remote));
+        let other_root = other.path().parent().unwrap();
+        write_commit(other_root, "bar", "other commit");
+        push_upstream(other_root.to_str().unwrap()).unwrap();
+
+        write_commit(root, "foo", "diverging commit");
+
+        assert!(push_upstream(repo_path).is_err());
+
+        let remote_head =
+            remote.refname_to_id("refs/heads/master").unwrap();
+        assert_eq!(
+            remote_head,
+            get_head(other_root.to_str().unwrap()).unwrap().into()
+        );
+    }
+
+    #[test]
+    fn test_push_rejected_by_pre_push_hook() {
+        let (_remote_td, remote) = repo_init_bare();
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo.remote("origin", &bare_url(&remote)).unwrap();
+        set_upstream(&repo);
+
+        let hook = repo.path().join("hooks").join("pre-push");
+        std::fs::create_dir_all(hook.parent().unwrap()).unwrap();
+        std::fs::write(
+            &hook,
+            "#!/bin/sh\necho 'no pushing'\nexit 1\n",
+        )
+        .unwrap();
+        #[cfg(not(windows))]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(
+                &hook,
+                std::fs::Permissions::from_mode(0o755),
+            )
+            .unwrap();
+        }
+
+        let err = push_upstream(repo_path).unwrap_err();
+
+        assert!(err.to_string().contains("no pushing"));
+        assert!(remote.refname_to_id("refs/heads/master").is_err());
+    }
+}
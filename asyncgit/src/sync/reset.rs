@@ -1,7 +1,90 @@
-use super::utils::{get_head_repo, repo};
+use super::{
+    utils::{get_head_repo, repo},
+    CommitId,
+};
 use crate::error::Result;
 use git2::{build::CheckoutBuilder, ObjectType};
 use scopetime::scope_time;
+use std::fs;
+
+/// how much `reset_head` resets besides moving HEAD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetMode {
+    /// keep index and working tree
+    Soft,
+    /// reset the index, keep the working tree
+    Mixed,
+    /// reset index and working tree
+    Hard,
+}
+
+impl From<ResetMode> for git2::ResetType {
+    fn from(mode: ResetMode) -> Self {
+        match mode {
+            ResetMode::Soft => Self::Soft,
+            ResetMode::Mixed => Self::Mixed,
+            ResetMode::Hard => Self::Hard,
+        }
+    }
+}
+
+/// what resetting HEAD to a commit affects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetPreview {
+    /// commits that will no longer be on the branch
+    pub commits: usize,
+    /// tracked files differing from the target commit,
+    /// their changes get lost on a hard reset
+    pub files: usize,
+}
+
+/// counts what resetting HEAD to `id` affects
+pub fn reset_head_preview(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<ResetPreview> {
+    scope_time!("reset_head_preview");
+
+    let repo = repo(repo_path)?;
+
+    let mut walk = repo.revwalk()?;
+    walk.push_head()?;
+    walk.hide(id.into())?;
+    let commits = walk.count();
+
+    let tree = repo.find_commit(id.into())?.tree()?;
+    let files = repo
+        .diff_tree_to_workdir_with_index(Some(&tree), None)?
+        .deltas()
+        .len();
+
+    Ok(ResetPreview { commits, files })
+}
+
+/// moves HEAD (and the branch it points to) to `id`.
+///
+/// the previous HEAD is kept in `ORIG_HEAD` and
+/// the reflog so the reset can be undone
+pub fn reset_head(
+    repo_path: &str,
+    id: CommitId,
+    mode: ResetMode,
+) -> Result<()> {
+    scope_time!("reset_head");
+
+    let repo = repo(repo_path)?;
+
+    let orig_head = get_head_repo(&repo)?;
+    fs::write(
+        repo.path().join("ORIG_HEAD"),
+        format!("{}\n", orig_head.to_string()),
+    )?;
+
+    let commit = repo.find_commit(id.into())?;
+    repo.reset(commit.as_object(), mode.into(), None)?;
+
+    Ok(())
+}
 
 ///
 pub fn reset_stage(repo_path: &str, path: &str) -> Result<()> {
@@ -40,15 +123,19 @@ pub fn reset_workdir(repo_path: &str, path: &str) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::{reset_stage, reset_workdir};
+    use super::{
+        reset_head, reset_head_preview, reset_stage, reset_workdir,
+        ResetMode, ResetPreview,
+    };
     use crate::error::Result;
     use crate::sync::{
         commit,
         status::{get_status, StatusType},
+        tests::write_commit,
         tests::{
             debug_cmd_print, get_statuses, repo_init, repo_init_empty,
         },
-        utils::{stage_add_all, stage_add_file},
+        utils::{get_head, stage_add_all, stage_add_file},
     };
     use std::{
         fs::{self, File},
@@ -311,4 +398,71 @@ mod tests {
 
         assert_eq!(get_statuses(repo_path), (0, 0));
     }
+
+    #[test]
+    fn test_reset_head_modes() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit(root, "a.txt", "a");
+        let base = get_head(repo_path).unwrap();
+        write_commit(root, "b.txt", "b");
+        write_commit(root, "c.txt", "c");
+        let orig = get_head(repo_path).unwrap();
+
+        fs::write(root.join("a.txt"), "changed").unwrap();
+
+        assert_eq!(
+            reset_head_preview(repo_path, base).unwrap(),
+            ResetPreview {
+                commits: 2,
+                files: 3
+            }
+        );
+
+        reset_head(repo_path, base, ResetMode::Soft).unwrap();
+        assert_eq!(get_head(repo_path).unwrap(), base);
+        assert_eq!(get_statuses(repo_path), (1, 2));
+        assert_eq!(
+            fs::read_to_string(repo.path().join("ORIG_HEAD"))
+                .unwrap()
+                .trim(),
+            orig.to_string()
+        );
+
+        reset_head(repo_path, base, ResetMode::Mixed).unwrap();
+        assert_eq!(get_statuses(repo_path), (3, 0));
+
+        reset_head(repo_path, base, ResetMode::Hard).unwrap();
+        // files of the dropped commits are untracked by now
+        assert_eq!(get_statuses(repo_path), (2, 0));
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "a"
+        );
+    }
+
+    #[test]
+    fn test_reset_head_reflog() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit(root, "a.txt", "a");
+        let base = get_head(repo_path).unwrap();
+        write_commit(root, "b.txt", "b");
+        let orig = get_head(repo_path).unwrap();
+
+        reset_head(repo_path, base, ResetMode::Hard).unwrap();
+
+        let reflog = repo.reflog("HEAD").unwrap();
+        let entry = reflog.get(0).unwrap();
+        assert_eq!(entry.id_old(), orig.into());
+        assert_eq!(entry.id_new(), base.into());
+        assert!(entry
+            .message()
+            .unwrap()
+            .starts_with("reset: moving to"));
+    }
 }
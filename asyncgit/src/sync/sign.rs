@@ -0,0 +1,593 @@
+//! signing commits and tags with gpg or ssh and verifying them,
+//! configured like git via `gpg.format`, `gpg.program` and
+//! `user.signingkey`
+
+use super::{utils::repo, CommitId};
+use crate::error::{Error, Result};
+use git2::{Config, ErrorCode, Repository, Signature};
+use scopetime::scope_time;
+use std::{
+    env,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+use tempfile::NamedTempFile;
+
+const SSH_SIGNATURE_BEGIN: &str = "-----BEGIN SSH SIGNATURE-----";
+const X509_SIGNATURE_BEGIN: &str = "-----BEGIN SIGNED MESSAGE-----";
+
+/// state of the signature of a commit
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureStatus {
+    /// valid signature of a trusted key, names the signer
+    Good(String),
+    /// the signature does not match the commit
+    Bad,
+    /// the key is unknown or the signature could not be checked
+    UnknownKey,
+    ///
+    Unsigned,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SignFormat {
+    OpenPgp,
+    X509,
+    Ssh,
+}
+
+impl SignFormat {
+    fn from_config(config: &Config) -> Result<Self> {
+        match config.get_string("gpg.format").ok().as_deref() {
+            None | Some("openpgp") => Ok(Self::OpenPgp),
+            Some("x509") => Ok(Self::X509),
+            Some("ssh") => Ok(Self::Ssh),
+            Some(other) => Err(Error::Generic(format!(
+                "unsupported gpg.format: {}",
+                other
+            ))),
+        }
+    }
+
+    fn from_signature(signature: &str) -> Self {
+        if signature.starts_with(SSH_SIGNATURE_BEGIN) {
+            Self::Ssh
+        } else if signature.starts_with(X509_SIGNATURE_BEGIN) {
+            Self::X509
+        } else {
+            Self::OpenPgp
+        }
+    }
+
+    fn program(self, config: &Config) -> String {
+        let (keys, default): (&[&str], _) = match self {
+            Self::OpenPgp => {
+                (&["gpg.openpgp.program", "gpg.program"], "gpg")
+            }
+            Self::X509 => (&["gpg.x509.program"], "gpgsm"),
+            Self::Ssh => (&["gpg.ssh.program"], "ssh-keygen"),
+        };
+
+        keys.iter()
+            .find_map(|key| config.get_string(key).ok())
+            .unwrap_or_else(|| default.to_string())
+    }
+}
+
+/// signs payloads the way the repo config asks for
+pub(crate) struct Signer {
+    format: SignFormat,
+    program: String,
+    key: String,
+}
+
+impl Signer {
+    /// signer for commits if `commit.gpgsign` is set
+    pub fn for_commits(repo: &Repository) -> Result<Option<Self>> {
+        Self::from_config(repo, "commit.gpgsign")
+    }
+
+    /// signer for tags if `tag.gpgsign` is set
+    pub fn for_tags(repo: &Repository) -> Result<Option<Self>> {
+        Self::from_config(repo, "tag.gpgsign")
+    }
+
+    fn from_config(
+        repo: &Repository,
+        enabled_key: &str,
+    ) -> Result<Option<Self>> {
+        let config = repo.config()?;
+
+        if !config.get_bool(enabled_key).unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let format = SignFormat::from_config(&config)?;
+        let key = match config.get_string("user.signingkey") {
+            Ok(key) if format == SignFormat::Ssh => expand_home(&key),
+            Ok(key) => key,
+            Err(e) if e.code() == ErrorCode::NotFound => {
+                if format == SignFormat::Ssh {
+                    return Err(Error::Generic(
+                        "ssh signing needs user.signingkey"
+                            .to_string(),
+                    ));
+                }
+
+                // gpg picks a key by the committer identity
+                let committer = repo.signature()?;
+                format!(
+                    "{} <{}>",
+                    committer.name().unwrap_or_default(),
+                    committer.email().unwrap_or_default()
+                )
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if format == SignFormat::Ssh
+            && (key.starts_with("key::") || key.starts_with("ssh-"))
+        {
+            return Err(Error::Generic(
+                "user.signingkey has to be the path of a key file"
+                    .to_string(),
+            ));
+        }
+
+        Ok(Some(Self {
+            format,
+            program: format.program(&config),
+            key,
+        }))
+    }
+
+    /// detached armored signature of `payload`
+    pub fn sign(&self, payload: &str) -> Result<String> {
+        scope_time!("sign");
+
+        let output = match self.format {
+            SignFormat::OpenPgp | SignFormat::X509 => run(
+                &self.program,
+                &["--status-fd=2", "-bsau", &self.key],
+                payload.as_bytes(),
+            )?,
+            SignFormat::Ssh => run(
+                &self.program,
+                &["-Y", "sign", "-n", "git", "-f", &self.key],
+                payload.as_bytes(),
+            )?,
+        };
+
+        if !output.status.success() || output.stdout.is_empty() {
+            return Err(Error::Generic(format!(
+                "signing failed:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+/// whether commit `id` carries a signature, unlike
+/// `verify_commit` this runs no external program
+pub fn is_signed(repo_path: &str, id: CommitId) -> Result<bool> {
+    scope_time!("is_signed");
+
+    let repo = repo(repo_path)?;
+
+    match repo.extract_signature(&id.into(), None) {
+        Ok(_) => Ok(true),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// checks the signature of commit `id`
+pub fn verify_commit(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<SignatureStatus> {
+    scope_time!("verify_commit");
+
+    let repo = repo(repo_path)?;
+
+    let (signature, payload) =
+        match repo.extract_signature(&id.into(), None) {
+            Ok(extracted) => extracted,
+            Err(e) if e.code() == ErrorCode::NotFound => {
+                return Ok(SignatureStatus::Unsigned)
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+    let signature = String::from_utf8_lossy(&signature).to_string();
+    let config = repo.config()?;
+    let format = SignFormat::from_signature(&signature);
+    let program = format.program(&config);
+
+    let status = if format == SignFormat::Ssh {
+        let allowed = config
+            .get_string("gpg.ssh.allowedSignersFile")
+            .ok()
+            .map(|path| expand_home(&path));
+
+        verify_ssh(&program, allowed.as_deref(), &signature, &payload)
+    } else {
+        verify_gpg(&program, &signature, &payload)
+    };
+
+    // a missing program can not tell anything about the key
+    Ok(status.unwrap_or(SignatureStatus::UnknownKey))
+}
+
+fn verify_gpg(
+    program: &str,
+    signature: &str,
+    payload: &[u8],
+) -> Result<SignatureStatus> {
+    let sig_file = signature_file(signature)?;
+    let output = run(
+        program,
+        &[
+            "--status-fd=1",
+            "--verify",
+            &sig_file.path().to_string_lossy(),
+            "-",
+        ],
+        payload,
+    )?;
+
+    Ok(gpg_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// reads the `--status-fd` output of gpg
+fn gpg_status(status: &str) -> SignatureStatus {
+    for line in status.lines() {
+        let mut words = line.splitn(4, ' ');
+        if words.next() != Some("[GNUPG:]") {
+            continue;
+        }
+
+        match words.next() {
+            Some("GOODSIG") => {
+                let signer = words.nth(1).unwrap_or_default();
+                return SignatureStatus::Good(signer.to_string());
+            }
+            Some("BADSIG") => return SignatureStatus::Bad,
+            _ => (),
+        }
+    }
+
+    SignatureStatus::UnknownKey
+}
+
+fn verify_ssh(
+    program: &str,
+    allowed_signers: Option<&str>,
+    signature: &str,
+    payload: &[u8],
+) -> Result<SignatureStatus> {
+    let sig_file = signature_file(signature)?;
+    let sig_path = sig_file.path().to_string_lossy();
+
+    if let Some(allowed) = allowed_signers {
+        let output = run(
+            program,
+            &[
+                "-Y",
+                "find-principals",
+                "-f",
+                allowed,
+                "-s",
+                &sig_path,
+            ],
+            &[],
+        )?;
+        let principals = String::from_utf8_lossy(&output.stdout);
+
+        if let (true, Some(principal)) =
+            (output.status.success(), principals.lines().next())
+        {
+            let output = run(
+                program,
+                &[
+                    "-Y", "verify", "-f", allowed, "-I", principal,
+                    "-n", "git", "-s", &sig_path,
+                ],
+                payload,
+            )?;
+
+            return Ok(if output.status.success() {
+                SignatureStatus::Good(principal.to_string())
+            } else {
+                SignatureStatus::Bad
+            });
+        }
+    }
+
+    let output = run(
+        program,
+        &["-Y", "check-novalidate", "-n", "git", "-s", &sig_path],
+        payload,
+    )?;
+
+    Ok(if output.status.success() {
+        SignatureStatus::UnknownKey
+    } else {
+        SignatureStatus::Bad
+    })
+}
+
+/// raw tag object for `tag` with the signature appended to its
+/// message
+pub(crate) fn signed_tag_buffer(
+    signer: &Signer,
+    target: &git2::Object,
+    tag: &str,
+    tagger: &Signature,
+    msg: &str,
+) -> Result<String> {
+    let when = tagger.when();
+    let offset = when.offset_minutes();
+
+    let mut buffer = format!(
+        "object {}\ntype {}\ntag {}\ntagger {} <{}> {} {}{:02}{:02}\n\n{}",
+        target.id(),
+        target.kind().map(|k| k.str()).unwrap_or("commit"),
+        tag,
+        tagger.name().unwrap_or_default(),
+        tagger.email().unwrap_or_default(),
+        when.seconds(),
+        if offset < 0 { '-' } else { '+' },
+        offset.abs() / 60,
+        offset.abs() % 60,
+        msg,
+    );
+    if !buffer.ends_with('\n') {
+        buffer.push('\n');
+    }
+
+    let signature = signer.sign(&buffer)?;
+    buffer.push_str(&signature);
+
+    Ok(buffer)
+}
+
+fn run(program: &str, args: &[&str], input: &[u8]) -> Result<Output> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            Error::Generic(format!("running {}: {}", program, e))
+        })?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input)?;
+    }
+
+    Ok(child.wait_with_output()?)
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), env::var("HOME")) {
+        (Some(rest), Ok(home)) => {
+            PathBuf::from(home).join(rest).to_string_lossy().into()
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// signature checks need the signature in a file, it gets
+/// removed when the returned file is dropped
+fn signature_file(signature: &str) -> Result<NamedTempFile> {
+    let mut file = NamedTempFile::new()?;
+    file.write_all(signature.as_bytes())?;
+    file.flush()?;
+
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        amend, commit, get_rebase_plan, rebase_continue,
+        rebase_interactive, stage_add_file, tag,
+        tests::{repo_init, write_commit},
+        utils::get_head,
+        RebaseAction, RebaseProgress,
+    };
+    use std::{fs, path::Path};
+
+    /// throwaway ssh key signing the commits of `repo`
+    fn setup_ssh_signing(repo: &Repository, dir: &Path) -> String {
+        let key = dir.join("signing_key");
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", "me"])
+            .arg("-f")
+            .arg(&key)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let mut config = repo.config().unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config
+            .set_str("user.signingkey", key.to_str().unwrap())
+            .unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_bool("tag.gpgsign", true).unwrap();
+
+        let public =
+            fs::read_to_string(key.with_extension("pub")).unwrap();
+        let allowed = dir.join("allowed_signers");
+        fs::write(&allowed, format!("me@gitui {}", public)).unwrap();
+
+        allowed.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_gpg_status() {
+        assert_eq!(
+            gpg_status(
+                "[GNUPG:] NEWSIG\n[GNUPG:] GOODSIG ABCD Me <me@gitui>\n"
+            ),
+            SignatureStatus::Good("Me <me@gitui>".to_string())
+        );
+        assert_eq!(
+            gpg_status("[GNUPG:] BADSIG ABCD Me <me@gitui>\n"),
+            SignatureStatus::Bad
+        );
+        assert_eq!(
+            gpg_status("[GNUPG:] ERRSIG ABCD 22 8 00 1 9\n[GNUPG:] NO_PUBKEY ABCD\n"),
+            SignatureStatus::UnknownKey
+        );
+    }
+
+    #[test]
+    fn test_ssh_signed_commit() {
+        let (td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let unsigned = get_head(repo_path).unwrap();
+        let allowed = setup_ssh_signing(&repo, td.path());
+
+        fs::write(root.join("a.txt"), "a").unwrap();
+        stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+        let id = commit(repo_path, "signed").unwrap();
+
+        assert_eq!(get_head(repo_path).unwrap(), id);
+        assert!(!is_signed(repo_path, unsigned).unwrap());
+        assert!(is_signed(repo_path, id).unwrap());
+        assert_eq!(
+            verify_commit(repo_path, unsigned).unwrap(),
+            SignatureStatus::Unsigned
+        );
+        assert_eq!(
+            verify_commit(repo_path, id).unwrap(),
+            SignatureStatus::UnknownKey
+        );
+
+        repo.config()
+            .unwrap()
+            .set_str("gpg.ssh.allowedSignersFile", &allowed)
+            .unwrap();
+        assert_eq!(
+            verify_commit(repo_path, id).unwrap(),
+            SignatureStatus::Good("me@gitui".to_string())
+        );
+
+        let amended = amend(repo_path, id, "amended").unwrap();
+        assert_eq!(get_head(repo_path).unwrap(), amended);
+        assert_eq!(
+            verify_commit(repo_path, amended).unwrap(),
+            SignatureStatus::Good("me@gitui".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ssh_bad_signature() {
+        let (td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let allowed = setup_ssh_signing(&repo, td.path());
+        repo.config()
+            .unwrap()
+            .set_str("gpg.ssh.allowedSignersFile", &allowed)
+            .unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let sig = repo.signature().unwrap();
+        let buffer = repo
+            .commit_create_buffer(
+                &sig,
+                &sig,
+                "original",
+                &head.tree().unwrap(),
+                &[&head],
+            )
+            .unwrap();
+        let buffer = buffer.as_str().unwrap();
+
+        let signer = Signer::for_commits(&repo).unwrap().unwrap();
+        let signature = signer.sign(buffer).unwrap();
+
+        let forged = buffer.replace("original", "forged");
+        let id =
+            repo.commit_signed(&forged, &signature, None).unwrap();
+
+        assert_eq!(
+            verify_commit(repo_path, id.into()).unwrap(),
+            SignatureStatus::Bad
+        );
+    }
+
+    #[test]
+    fn test_ssh_signed_tag() {
+        let (td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup_ssh_signing(&repo, td.path());
+        write_commit(root, "a.txt", "a");
+        let head = get_head(repo_path).unwrap();
+
+        tag(repo_path, &head, "v1").unwrap();
+        assert!(tag(repo_path, &head, "v1").is_err());
+
+        let reference = repo.find_reference("refs/tags/v1").unwrap();
+        let tag = reference.peel_to_tag().unwrap();
+
+        assert_eq!(tag.target_id(), head.into());
+        assert!(tag.message().unwrap().contains(SSH_SIGNATURE_BEGIN));
+    }
+
+    #[test]
+    fn test_ssh_signed_rebase() {
+        let (td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit(root, "base.txt", "base");
+        let base = get_head(repo_path).unwrap();
+        write_commit(root, "a.txt", "a");
+        write_commit(root, "b.txt", "b");
+        write_commit(root, "c.txt", "c");
+
+        setup_ssh_signing(&repo, td.path());
+
+        let mut plan = get_rebase_plan(repo_path, base).unwrap();
+        plan[0].action = RebaseAction::Edit;
+        plan[1].action = RebaseAction::Squash;
+
+        assert_eq!(
+            rebase_interactive(repo_path, base, &plan).unwrap(),
+            RebaseProgress::Edit
+        );
+        assert!(is_signed(repo_path, get_head(repo_path).unwrap())
+            .unwrap());
+
+        fs::write(root.join("a.txt"), "amended").unwrap();
+        stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+
+        assert_eq!(
+            rebase_continue(repo_path).unwrap(),
+            RebaseProgress::Done
+        );
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let squashed = head.parent(0).unwrap();
+
+        assert_eq!(squashed.message(), Some("a\n\nb"));
+        assert_eq!(squashed.parent_id(0).unwrap(), base.into());
+        assert!(is_signed(repo_path, head.id().into()).unwrap());
+        assert!(is_signed(repo_path, squashed.id().into()).unwrap());
+    }
+}
@@ -0,0 +1,75 @@
+//! sync git api for the state of the repo
+
+use super::utils;
+use crate::error::Result;
+use git2::RepositoryState;
+use scopetime::scope_time;
+
+/// operation currently in progress in the repo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepoState {
+    /// nothing in progress
+    Clean,
+    ///
+    Merge,
+    ///
+    Rebase,
+    ///
+    CherryPick,
+    ///
+    Revert,
+    ///
+    Bisect,
+    /// applying patches from a mailbox (`git am`)
+    ApplyMailbox,
+}
+
+impl From<RepositoryState> for RepoState {
+    fn from(state: RepositoryState) -> Self {
+        match state {
+            RepositoryState::Clean => Self::Clean,
+            RepositoryState::Merge => Self::Merge,
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => Self::Rebase,
+            RepositoryState::CherryPick
+            | RepositoryState::CherryPickSequence => Self::CherryPick,
+            RepositoryState::Revert
+            | RepositoryState::RevertSequence => Self::Revert,
+            RepositoryState::Bisect => Self::Bisect,
+            RepositoryState::ApplyMailbox
+            | RepositoryState::ApplyMailboxOrRebase => {
+                Self::ApplyMailbox
+            }
+        }
+    }
+}
+
+/// returns the operation currently in progress in the repo
+pub fn repo_state(repo_path: &str) -> Result<RepoState> {
+    scope_time!("repo_state");
+
+    let repo = utils::repo(repo_path)?;
+
+    Ok(repo.state().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::repo_init;
+    use std::fs::File;
+
+    #[test]
+    fn test_repo_state() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+
+        File::create(repo.path().join("MERGE_HEAD")).unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Merge);
+    }
+}
@@ -56,6 +56,24 @@ pub(crate) fn work_dir(repo: &Repository) -> &Path {
     repo.workdir().expect("unable to query workdir")
 }
 
+/// fails if the index differs from HEAD, operations committing the
+/// whole index would pick up these staged changes otherwise
+pub(crate) fn check_index_matches_head(
+    repo: &Repository,
+) -> Result<()> {
+    let head = repo.head()?.peel_to_tree()?;
+    let diff = repo.diff_tree_to_index(Some(&head), None, None)?;
+
+    if diff.deltas().len() == 0 {
+        Ok(())
+    } else {
+        Err(Error::Generic(
+            "index has staged changes, commit or unstage them first"
+                .to_string(),
+        ))
+    }
+}
+
 ///
 pub fn repo_work_dir(repo_path: &str) -> Result<String> {
     let repo = repo(repo_path)?;
@@ -0,0 +1,171 @@
+//! highlighting of diff content by the syntax of the file
+
+use crate::sync::diff::{DiffLineType, FileDiff, SyntaxSpan};
+use scopetime::scope_time;
+use std::{ffi::OsStr, path::Path, sync::OnceLock};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::ThemeSet,
+    parsing::{SyntaxReference, SyntaxSet},
+};
+
+/// longer lines are left uncoloured, they slow down highlighting
+const MAX_LINE_LENGTH: usize = 1000;
+const THEME: &str = "base16-eighties.dark";
+
+static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+
+/// colours the lines of `diff` by the syntax belonging to the
+/// extension of `path`. old and new side of a hunk are highlighted
+/// separately, context lines being part of both
+pub fn highlight_diff(mut diff: FileDiff, path: &str) -> FileDiff {
+    scope_time!("highlight_diff");
+
+    let syntaxes =
+        SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines);
+    let themes = THEMES.get_or_init(ThemeSet::load_defaults);
+
+    let (syntax, theme) =
+        match (find_syntax(syntaxes, path), themes.themes.get(THEME))
+        {
+            (Some(syntax), Some(theme)) => (syntax, theme),
+            _ => return diff,
+        };
+
+    for hunk in &mut diff.hunks {
+        let mut old = HighlightLines::new(syntax, theme);
+        let mut new = HighlightLines::new(syntax, theme);
+
+        for line in &mut hunk.lines {
+            let content = line.content.as_str();
+
+            line.syntax = match line.line_type {
+                DiffLineType::Header => continue,
+                DiffLineType::Delete => {
+                    highlight_line(&mut old, syntaxes, content)
+                }
+                DiffLineType::Add => {
+                    highlight_line(&mut new, syntaxes, content)
+                }
+                DiffLineType::None => {
+                    highlight_line(&mut old, syntaxes, content);
+                    highlight_line(&mut new, syntaxes, content)
+                }
+            };
+        }
+    }
+
+    diff
+}
+
+/// by extension, or by file name for files like `Makefile`
+fn find_syntax<'a>(
+    syntaxes: &'a SyntaxSet,
+    path: &str,
+) -> Option<&'a SyntaxReference> {
+    let path = Path::new(path);
+
+    path.extension()
+        .and_then(OsStr::to_str)
+        .and_then(|ext| syntaxes.find_syntax_by_extension(ext))
+        .or_else(|| {
+            path.file_name().and_then(OsStr::to_str).and_then(
+                |name| syntaxes.find_syntax_by_extension(name),
+            )
+        })
+}
+
+fn highlight_line(
+    highlighter: &mut HighlightLines,
+    syntaxes: &SyntaxSet,
+    content: &str,
+) -> Vec<SyntaxSpan> {
+    if content.len() > MAX_LINE_LENGTH {
+        return Vec::new();
+    }
+
+    let ranges = match highlighter.highlight_line(content, syntaxes) {
+        Ok(ranges) => ranges,
+        Err(e) => {
+            log::error!("highlighting failed: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut start = 0;
+    ranges
+        .into_iter()
+        .map(|(style, text)| {
+            let range = start..start + text.len();
+            start = range.end;
+
+            SyntaxSpan {
+                range,
+                color: (
+                    style.foreground.r,
+                    style.foreground.g,
+                    style.foreground.b,
+                ),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::diff::{DiffLine, Hunk};
+
+    fn diff(lines: &[(&str, DiffLineType)]) -> FileDiff {
+        FileDiff {
+            hunks: vec![Hunk {
+                header_hash: 0,
+                lines: lines
+                    .iter()
+                    .map(|(content, line_type)| DiffLine {
+                        content: content.to_string(),
+                        line_type: *line_type,
+                        ..DiffLine::default()
+                    })
+                    .collect(),
+            }],
+            lines: lines.len(),
+            ..FileDiff::default()
+        }
+    }
+
+    #[test]
+    fn test_highlight_by_extension() {
+        let lines = [
+            ("@@ -1 +1 @@\n", DiffLineType::Header),
+            ("fn main() {\n", DiffLineType::None),
+            ("    let a = 1;\n", DiffLineType::Delete),
+            ("    let a = 2;\n", DiffLineType::Add),
+        ];
+
+        let res = highlight_diff(diff(&lines), "src/main.rs");
+        let res = &res.hunks[0].lines;
+
+        assert!(res[0].syntax.is_empty());
+
+        for line in &res[1..] {
+            let colors: Vec<_> =
+                line.syntax.iter().map(|s| s.color).collect();
+            assert!(colors.windows(2).any(|c| c[0] != c[1]));
+            assert_eq!(
+                line.syntax.last().unwrap().range.end,
+                line.content.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_extension() {
+        let lines = [("some text\n", DiffLineType::Add)];
+
+        let res = highlight_diff(diff(&lines), "notes.unknownext");
+
+        assert!(res.hunks[0].lines[0].syntax.is_empty());
+    }
+}
@@ -2,32 +2,46 @@ use crate::{
     accessors,
     cmdbar::CommandBar,
     components::{
-        event_pump, CommandBlocking, CommandInfo, CommitComponent,
-        Component, CreateBranchComponent, DrawableComponent,
-        ExternalEditorComponent, HelpComponent,
-        InspectCommitComponent, MsgComponent, ResetComponent,
-        StashMsgComponent, TagCommitComponent,
+        event_pump, BlameFileComponent, CommandBlocking, CommandInfo,
+        CommitComponent, Component, CreateBranchComponent,
+        DiffOptionsComponent, DrawableComponent,
+        ExternalEditorComponent, FetchComponent, FileRevlogComponent,
+        HelpComponent, InspectCommitComponent, LogOptionsComponent,
+        LogSearchComponent, MsgComponent, PickaxeComponent,
+        PullComponent, PushComponent, RebasePlanComponent,
+        ReflogComponent, RenameBranchComponent, ResetComponent,
+        ResetHeadComponent, SetUpstreamComponent, StashMsgComponent,
+        TagCommitComponent,
     },
     input::{Input, InputEvent, InputState},
     keys::{KeyConfig, SharedKeyConfig},
+    options::{Options, SharedOptions},
     queue::{Action, InternalEvent, NeedsUpdate, Queue},
     strings::{self, order},
-    tabs::{Revlog, StashList, Stashing, Status},
+    tabs::{BranchList, Revlog, StashList, Stashing, Status},
     ui::style::{SharedTheme, Theme},
 };
 use anyhow::{anyhow, Result};
-use asyncgit::{sync, AsyncNotification, CWD};
+use asyncgit::{
+    cached,
+    sync::{
+        self, BranchStatus, CommitId, PickResult, RebaseStatus,
+        RepoState,
+    },
+    AsyncNotification, CWD,
+};
 use crossbeam_channel::Sender;
 use crossterm::event::{Event, KeyEvent};
 use std::{
     cell::{Cell, RefCell},
+    convert::TryFrom,
     path::Path,
     rc::Rc,
 };
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Margin, Rect},
-    widgets::{Block, Borders, Tabs},
+    widgets::{Block, Borders, Paragraph, Tabs, Text},
     Frame,
 };
 
@@ -42,17 +56,36 @@ pub struct App {
     inspect_commit_popup: InspectCommitComponent,
     external_editor_popup: ExternalEditorComponent,
     tag_commit_popup: TagCommitComponent,
+    log_search_popup: LogSearchComponent,
+    log_options_popup: LogOptionsComponent,
+    diff_options_popup: DiffOptionsComponent,
     create_branch_popup: CreateBranchComponent,
+    rename_branch_popup: RenameBranchComponent,
+    set_upstream_popup: SetUpstreamComponent,
+    push_popup: PushComponent,
+    fetch_popup: FetchComponent,
+    pull_popup: PullComponent,
+    rebase_plan_popup: RebasePlanComponent,
+    reset_head_popup: ResetHeadComponent,
+    reflog_popup: ReflogComponent,
+    blame_file_popup: BlameFileComponent,
+    file_revlog_popup: FileRevlogComponent,
+    pickaxe_popup: PickaxeComponent,
     cmdbar: RefCell<CommandBar>,
     tab: usize,
     revlog: Revlog,
     status_tab: Status,
     stashing_tab: Stashing,
     stashlist_tab: StashList,
+    branchlist_tab: BranchList,
     queue: Queue,
+    options: SharedOptions,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
     input: Input,
+    branch_state: cached::BranchState,
+    branch_status: Option<BranchStatus>,
+    rebase_status: Option<RebaseStatus>,
 
     // "Flags"
     requires_redraw: Cell<bool>,
@@ -62,6 +95,7 @@ pub struct App {
 // public interface
 impl App {
     ///
+    #[allow(clippy::too_many_lines)]
     pub fn new(
         sender: &Sender<AsyncNotification>,
         input: Input,
@@ -70,6 +104,8 @@ impl App {
 
         let theme = Rc::new(Theme::init());
         let key_config = Rc::new(KeyConfig::init());
+        let options = Rc::new(RefCell::new(Options::init()));
+        let fetch_interval = options.borrow().fetch_interval();
 
         Self {
             input,
@@ -91,6 +127,7 @@ impl App {
             inspect_commit_popup: InspectCommitComponent::new(
                 &queue,
                 sender,
+                options.clone(),
                 theme.clone(),
                 key_config.clone(),
             ),
@@ -103,11 +140,90 @@ impl App {
                 theme.clone(),
                 key_config.clone(),
             ),
+            log_search_popup: LogSearchComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            log_options_popup: LogOptionsComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            diff_options_popup: DiffOptionsComponent::new(
+                queue.clone(),
+                options.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
             create_branch_popup: CreateBranchComponent::new(
                 queue.clone(),
                 theme.clone(),
                 key_config.clone(),
             ),
+            rename_branch_popup: RenameBranchComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            set_upstream_popup: SetUpstreamComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            push_popup: PushComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            fetch_popup: FetchComponent::new(
+                &queue,
+                sender,
+                fetch_interval,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            pull_popup: PullComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            rebase_plan_popup: RebasePlanComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            reset_head_popup: ResetHeadComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            reflog_popup: ReflogComponent::new(
+                &queue,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            blame_file_popup: BlameFileComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            file_revlog_popup: FileRevlogComponent::new(
+                &queue,
+                sender,
+                options.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            pickaxe_popup: PickaxeComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
             do_quit: false,
             cmdbar: RefCell::new(CommandBar::new(
                 theme.clone(),
@@ -128,6 +244,7 @@ impl App {
             status_tab: Status::new(
                 &queue,
                 sender,
+                options.clone(),
                 theme.clone(),
                 key_config.clone(),
             ),
@@ -142,7 +259,16 @@ impl App {
                 theme.clone(),
                 key_config.clone(),
             ),
+            branchlist_tab: BranchList::new(
+                &queue,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            branch_state: cached::BranchState::new(CWD),
+            branch_status: None,
+            rebase_status: None,
             queue,
+            options,
             theme,
             key_config,
             requires_redraw: Cell::new(false),
@@ -156,11 +282,14 @@ impl App {
 
         self.cmdbar.borrow_mut().refresh_width(fsize.width);
 
+        let banner_height = u16::from(self.rebase_status.is_some());
+
         let chunks_main = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
                 [
                     Constraint::Length(2),
+                    Constraint::Length(banner_height),
                     Constraint::Min(2),
                     Constraint::Length(self.cmdbar.borrow().height()),
                 ]
@@ -168,16 +297,18 @@ impl App {
             )
             .split(fsize);
 
-        self.cmdbar.borrow().draw(f, chunks_main[2]);
+        self.cmdbar.borrow().draw(f, chunks_main[3]);
 
         self.draw_tabs(f, chunks_main[0]);
+        self.draw_rebase_banner(f, chunks_main[1]);
 
         //TODO: macro because of generic draw call
         match self.tab {
-            0 => self.status_tab.draw(f, chunks_main[1])?,
-            1 => self.revlog.draw(f, chunks_main[1])?,
-            2 => self.stashing_tab.draw(f, chunks_main[1])?,
-            3 => self.stashlist_tab.draw(f, chunks_main[1])?,
+            0 => self.status_tab.draw(f, chunks_main[2])?,
+            1 => self.revlog.draw(f, chunks_main[2])?,
+            2 => self.stashing_tab.draw(f, chunks_main[2])?,
+            3 => self.stashlist_tab.draw(f, chunks_main[2])?,
+            4 => self.branchlist_tab.draw(f, chunks_main[2])?,
             _ => return Err(anyhow!("unknown tab")),
         };
 
@@ -212,6 +343,7 @@ impl App {
                     || k == self.key_config.tab_log
                     || k == self.key_config.tab_stashing
                     || k == self.key_config.tab_stashes
+                    || k == self.key_config.tab_branches
                 {
                     self.switch_tab(k)?;
                     NeedsUpdate::COMMANDS
@@ -236,6 +368,7 @@ impl App {
             if flags.contains(NeedsUpdate::DIFF) {
                 self.status_tab.update_diff()?;
                 self.inspect_commit_popup.update_diff()?;
+                self.file_revlog_popup.update_diff()?;
             }
             if flags.contains(NeedsUpdate::COMMANDS) {
                 self.update_commands();
@@ -276,12 +409,24 @@ impl App {
         self.revlog.update()?;
         self.stashing_tab.update()?;
         self.stashlist_tab.update()?;
+        self.branchlist_tab.update()?;
+        self.reflog_popup.update()?;
+        self.file_revlog_popup.update()?;
+        self.pickaxe_popup.update()?;
 
+        self.update_branch_status();
         self.update_commands();
 
         Ok(())
     }
 
+    /// periodic update triggered by the main loop
+    pub fn tick(&mut self) -> Result<()> {
+        self.fetch_popup.tick()?;
+
+        self.update()
+    }
+
     ///
     pub fn update_git(
         &mut self,
@@ -293,6 +438,21 @@ impl App {
         self.stashing_tab.update_git(ev)?;
         self.revlog.update_git(ev)?;
         self.inspect_commit_popup.update_git(ev)?;
+        self.push_popup.update_git(ev)?;
+        self.fetch_popup.update_git(ev)?;
+        self.pull_popup.update_git(ev)?;
+        self.blame_file_popup.update_git(ev)?;
+        self.file_revlog_popup.update_git(ev)?;
+        self.pickaxe_popup.update_git(ev)?;
+
+        if ev == AsyncNotification::Status {
+            self.update_branch_status();
+        }
+
+        let flags = self.process_queue()?;
+        if flags.contains(NeedsUpdate::ALL) {
+            self.update()?;
+        }
 
         //TODO: better system for this
         // can we simply process the queue here and everyone just uses the queue to schedule a cmd update?
@@ -312,6 +472,12 @@ impl App {
             || self.revlog.any_work_pending()
             || self.stashing_tab.anything_pending()
             || self.inspect_commit_popup.any_work_pending()
+            || self.push_popup.any_work_pending()
+            || self.fetch_popup.any_work_pending()
+            || self.pull_popup.any_work_pending()
+            || self.blame_file_popup.any_work_pending()
+            || self.file_revlog_popup.any_work_pending()
+            || self.pickaxe_popup.any_work_pending()
             || self.input.is_state_changing()
     }
 
@@ -332,18 +498,33 @@ impl App {
         self,
         [
             msg,
+            diff_options_popup,
             reset,
             commit,
             stashmsg_popup,
             inspect_commit_popup,
             external_editor_popup,
             tag_commit_popup,
+            log_search_popup,
+            log_options_popup,
             create_branch_popup,
+            rename_branch_popup,
+            set_upstream_popup,
+            push_popup,
+            fetch_popup,
+            pull_popup,
+            rebase_plan_popup,
+            reset_head_popup,
+            reflog_popup,
+            blame_file_popup,
+            file_revlog_popup,
+            pickaxe_popup,
             help,
             revlog,
             status_tab,
             stashing_tab,
-            stashlist_tab
+            stashlist_tab,
+            branchlist_tab
         ]
     );
 
@@ -363,6 +544,7 @@ impl App {
             &mut self.revlog,
             &mut self.stashing_tab,
             &mut self.stashlist_tab,
+            &mut self.branchlist_tab,
         ]
     }
 
@@ -386,6 +568,8 @@ impl App {
             self.set_tab(2)?
         } else if k == self.key_config.tab_stashes {
             self.set_tab(3)?
+        } else if k == self.key_config.tab_branches {
+            self.set_tab(4)?
         }
 
         Ok(())
@@ -406,6 +590,18 @@ impl App {
         Ok(())
     }
 
+    fn update_branch_status(&mut self) {
+        self.branch_status = self
+            .branch_state
+            .lookup()
+            .map_err(|e| log::trace!("branch status: {}", e))
+            .ok();
+        self.rebase_status = sync::rebase_status(CWD)
+            .map_err(|e| log::trace!("rebase status: {}", e))
+            .ok()
+            .flatten();
+    }
+
     fn update_commands(&mut self) {
         self.help.set_cmds(self.commands(true));
         self.cmdbar.borrow_mut().set_cmds(self.commands(false));
@@ -427,28 +623,74 @@ impl App {
         Ok(flags)
     }
 
-    fn process_internal_event(
+    fn process_confirmed_action(
         &mut self,
-        ev: InternalEvent,
+        action: Action,
     ) -> Result<NeedsUpdate> {
         let mut flags = NeedsUpdate::empty();
-        match ev {
-            InternalEvent::ConfirmedAction(action) => match action {
-                Action::Reset(r) => {
-                    if self.status_tab.reset(&r) {
-                        flags.insert(NeedsUpdate::ALL);
-                    }
-                }
-                Action::StashDrop(s) => {
-                    if StashList::drop(s) {
-                        flags.insert(NeedsUpdate::ALL);
-                    }
+        match action {
+            Action::Reset(r) => {
+                if self.status_tab.reset(&r) {
+                    flags.insert(NeedsUpdate::ALL);
                 }
-                Action::ResetHunk(path, hash) => {
-                    sync::reset_hunk(CWD, path, hash)?;
+            }
+            Action::StashDrop(s) => {
+                if StashList::drop(s) {
                     flags.insert(NeedsUpdate::ALL);
                 }
-            },
+            }
+            Action::ResetHunk(path, hash) => {
+                let options = self.options.borrow().diff;
+                sync::reset_hunk(CWD, path, hash, Some(options))?;
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::ResetLines(path, lines) => {
+                let options = self.options.borrow().diff;
+                sync::reset_lines(CWD, &path, &lines, Some(options))?;
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::DeleteBranch(branch_ref) => {
+                BranchList::delete_branch(&self.queue, &branch_ref);
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::StashAndCheckout(branch_ref) => {
+                BranchList::stash_and_checkout(
+                    &self.queue,
+                    &branch_ref,
+                );
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::AbortMerge => {
+                self.status_tab.abort_merge();
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::AbortRebase => {
+                self.status_tab.abort_rebase();
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::ResetHead(id, mode, _) => {
+                if let Err(e) = sync::reset_head(CWD, id, mode) {
+                    self.msg.show_msg(
+                        format!("reset error:\n{}", e).as_str(),
+                    )?;
+                }
+                flags.insert(NeedsUpdate::ALL);
+            }
+        }
+
+        Ok(flags)
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn process_internal_event(
+        &mut self,
+        ev: InternalEvent,
+    ) -> Result<NeedsUpdate> {
+        let mut flags = NeedsUpdate::empty();
+        match ev {
+            InternalEvent::ConfirmedAction(action) => {
+                flags.insert(self.process_confirmed_action(action)?);
+            }
             InternalEvent::ConfirmAction(action) => {
                 self.reset.open(action)?;
                 flags.insert(NeedsUpdate::COMMANDS);
@@ -470,6 +712,14 @@ impl App {
             InternalEvent::CreateBranch => {
                 self.create_branch_popup.open()?;
             }
+            InternalEvent::RenameBranch(branch_ref, name) => {
+                self.rename_branch_popup.open(branch_ref, name)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::SetUpstream(branch_ref, upstream) => {
+                self.set_upstream_popup.open(branch_ref, upstream)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
             InternalEvent::TabSwitch => self.set_tab(0)?,
             InternalEvent::InspectCommit(id, tags) => {
                 self.inspect_commit_popup.open(id, tags)?;
@@ -481,11 +731,117 @@ impl App {
                 self.file_to_open = path;
                 flags.insert(NeedsUpdate::COMMANDS)
             }
+            InternalEvent::Push => {
+                self.push_popup.start()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::Fetch => {
+                self.fetch_popup.fetch()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::Pull => {
+                self.pull_popup.start()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::BlameFile(path, commit) => {
+                self.inspect_commit_popup.hide();
+                self.blame_file_popup.open(&path, commit)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenFileRevlog(path) => {
+                self.inspect_commit_popup.hide();
+                self.file_revlog_popup.open(&path)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenLogSearch(query) => {
+                self.log_search_popup.open(query)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenLogOptions(options) => {
+                self.log_options_popup.open(options)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::SetLogOptions(options) => {
+                self.revlog.set_options(options)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenDiffOptions => {
+                self.diff_options_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::SearchLog(query, search) => {
+                self.revlog.search(query, search)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenPickaxe => {
+                self.pickaxe_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenReflog => {
+                self.reflog_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::ResetHead(id) => {
+                self.reset_head_popup.open(id)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::CherryPick(ids) => {
+                self.apply_commits(&ids, false)?;
+                flags.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::Revert(ids) => {
+                self.apply_commits(&ids, true)?;
+                flags.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenRebasePlan(base) => {
+                if let Err(e) = self.rebase_plan_popup.open(base) {
+                    self.msg.show_msg(
+                        format!("rebase error:\n{}", e).as_str(),
+                    )?;
+                }
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
         };
 
         Ok(flags)
     }
 
+    /// cherry-picks or reverts `ids`, conflicts get
+    /// resolved in the status tab like for merges
+    fn apply_commits(
+        &mut self,
+        ids: &[CommitId],
+        revert: bool,
+    ) -> Result<()> {
+        let res = if revert {
+            sync::revert(CWD, ids)
+        } else {
+            sync::cherry_pick(CWD, ids)
+        };
+
+        match res {
+            Ok(PickResult::Committed) => (),
+            Ok(PickResult::Prepared) => self.commit.show()?,
+            Ok(PickResult::Conflict(not_applied)) => {
+                self.set_tab(0)?;
+                if not_applied > 0 {
+                    self.msg.show_msg(&strings::pick_not_applied(
+                        &self.key_config,
+                        not_applied,
+                    ))?;
+                }
+            }
+            Err(e) => {
+                let action =
+                    if revert { "revert" } else { "cherry-pick" };
+                self.msg
+                    .show_msg(&format!("{} error:\n{}", action, e))?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn commands(&self, force_all: bool) -> Vec<CommandInfo> {
         let mut res = Vec::new();
 
@@ -538,7 +894,21 @@ impl App {
             || self.inspect_commit_popup.is_visible()
             || self.external_editor_popup.is_visible()
             || self.tag_commit_popup.is_visible()
+            || self.log_search_popup.is_visible()
+            || self.log_options_popup.is_visible()
+            || self.diff_options_popup.is_visible()
             || self.create_branch_popup.is_visible()
+            || self.rename_branch_popup.is_visible()
+            || self.set_upstream_popup.is_visible()
+            || self.push_popup.is_visible()
+            || self.fetch_popup.is_visible()
+            || self.pull_popup.is_visible()
+            || self.rebase_plan_popup.is_visible()
+            || self.reset_head_popup.is_visible()
+            || self.reflog_popup.is_visible()
+            || self.blame_file_popup.is_visible()
+            || self.file_revlog_popup.is_visible()
+            || self.pickaxe_popup.is_visible()
     }
 
     fn draw_popups<B: Backend>(
@@ -556,15 +926,29 @@ impl App {
             )
             .split(f.size())[0];
 
+        self.reflog_popup.draw(f, size)?;
+        self.blame_file_popup.draw(f, size)?;
+        self.file_revlog_popup.draw(f, size)?;
+        self.pickaxe_popup.draw(f, size)?;
         self.commit.draw(f, size)?;
         self.stashmsg_popup.draw(f, size)?;
         self.reset.draw(f, size)?;
         self.help.draw(f, size)?;
         self.inspect_commit_popup.draw(f, size)?;
+        self.log_search_popup.draw(f, size)?;
+        self.log_options_popup.draw(f, size)?;
+        self.diff_options_popup.draw(f, size)?;
         self.msg.draw(f, size)?;
         self.external_editor_popup.draw(f, size)?;
         self.tag_commit_popup.draw(f, size)?;
         self.create_branch_popup.draw(f, size)?;
+        self.rename_branch_popup.draw(f, size)?;
+        self.set_upstream_popup.draw(f, size)?;
+        self.push_popup.draw(f, size)?;
+        self.fetch_popup.draw(f, size)?;
+        self.pull_popup.draw(f, size)?;
+        self.rebase_plan_popup.draw(f, size)?;
+        self.reset_head_popup.draw(f, size)?;
 
         Ok(())
     }
@@ -581,6 +965,7 @@ impl App {
             strings::tab_log(&self.key_config),
             strings::tab_stashing(&self.key_config),
             strings::tab_stashes(&self.key_config),
+            strings::tab_branches(&self.key_config),
         ];
 
         f.render_widget(
@@ -597,5 +982,86 @@ impl App {
                 .select(self.tab),
             r,
         );
+
+        if let Some(status) = &self.branch_status {
+            let txt = self.branch_status_text(status);
+            let width = txt
+                .iter()
+                .map(|t| match t {
+                    Text::Raw(s) | Text::Styled(s, _) => {
+                        s.chars().count()
+                    }
+                })
+                .sum::<usize>();
+            let width =
+                u16::try_from(width).unwrap_or(r.width).min(r.width);
+
+            f.render_widget(
+                Paragraph::new(txt.iter()),
+                Rect::new(r.x + r.width - width, r.y, width, 1),
+            );
+        }
+    }
+
+    fn draw_rebase_banner<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        r: Rect,
+    ) {
+        if let Some(status) = &self.rebase_status {
+            let txt = [Text::Styled(
+                strings::rebase_banner(&self.key_config, status)
+                    .into(),
+                self.theme.text_danger(),
+            )];
+
+            f.render_widget(
+                Paragraph::new(txt.iter()),
+                r.inner(&Margin {
+                    vertical: 0,
+                    horizontal: 1,
+                }),
+            );
+        }
+    }
+
+    fn branch_status_text(&self, status: &BranchStatus) -> Vec<Text> {
+        let mut txt = Vec::with_capacity(3);
+
+        let head = status.branch.clone().unwrap_or_else(|| {
+            strings::branch_status_detached(
+                &self.key_config,
+                &status.head.to_string(),
+            )
+        });
+        txt.push(Text::Styled(head.into(), self.theme.tab(false)));
+
+        if let Some(upstream) = &status.upstream {
+            let (ahead, behind) =
+                status.ahead_behind.unwrap_or_default();
+            txt.push(Text::Styled(
+                strings::branch_status_upstream(
+                    &self.key_config,
+                    upstream,
+                    ahead,
+                    behind,
+                )
+                .into(),
+                self.theme.tags(false),
+            ));
+        }
+
+        if status.state != RepoState::Clean {
+            txt.push(Text::Styled(
+                strings::branch_status_state(
+                    &self.key_config,
+                    status.state,
+                )
+                .into(),
+                self.theme.text_danger(),
+            ));
+        }
+
+        txt
     }
 }
@@ -0,0 +1,415 @@
+use super::{
+    utils::time_to_age, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{calc_scroll_top, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{BlameHunk, CommitId, FileBlame},
+    AsyncBlame, AsyncNotification, BlameParams,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use std::{borrow::Cow, cell::Cell, cmp};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    widgets::{Block, Borders, Clear, Paragraph, Text},
+    Frame,
+};
+
+const AUTHOR_WIDTH: usize = 12;
+
+/// shows who last changed each line of a file
+pub struct BlameFileComponent {
+    params: Option<BlameParams>,
+    file_blame: Option<(BlameParams, FileBlame)>,
+    git_blame: AsyncBlame,
+    selection: usize,
+    visible: bool,
+    current_height: Cell<usize>,
+    scroll_top: Cell<usize>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for BlameFileComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let height = rect.height.saturating_sub(2) as usize;
+            self.current_height.set(height);
+
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, rect);
+            f.render_widget(
+                Paragraph::new(self.get_text(height).iter())
+                    .block(
+                        Block::default()
+                            .title(self.title().as_str())
+                            .borders(Borders::ALL)
+                            .border_style(self.theme.block(true))
+                            .title_style(self.theme.title(true)),
+                    )
+                    .alignment(Alignment::Left),
+                rect,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for BlameFileComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            let hunk = self.selected_hunk();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::log_details_open(&self.key_config),
+                hunk.is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::blame_parent(&self.key_config),
+                hunk.map_or(false, |h| h.parent_id.is_some()),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_up {
+                    self.move_selection(ScrollType::Up);
+                } else if e == self.key_config.move_down {
+                    self.move_selection(ScrollType::Down);
+                } else if e == self.key_config.page_up {
+                    self.move_selection(ScrollType::PageUp);
+                } else if e == self.key_config.page_down {
+                    self.move_selection(ScrollType::PageDown);
+                } else if e == self.key_config.home
+                    || e == self.key_config.shift_up
+                {
+                    self.move_selection(ScrollType::Home);
+                } else if e == self.key_config.end
+                    || e == self.key_config.shift_down
+                {
+                    self.move_selection(ScrollType::End);
+                } else if e == self.key_config.enter
+                    || e == self.key_config.focus_right
+                {
+                    self.inspect_selected();
+                } else if e == self.key_config.blame_parent {
+                    self.blame_parent()?;
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl BlameFileComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            params: None,
+            file_blame: None,
+            git_blame: AsyncBlame::new(sender),
+            selection: 0,
+            visible: false,
+            current_height: Cell::new(0),
+            scroll_top: Cell::new(0),
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    /// blames `file_path` at `commit_id` (`HEAD` if `None`)
+    pub fn open(
+        &mut self,
+        file_path: &str,
+        commit_id: Option<CommitId>,
+    ) -> Result<()> {
+        self.file_blame = None;
+        self.selection = 0;
+        self.scroll_top.set(0);
+        self.request(BlameParams {
+            file_path: file_path.to_string(),
+            commit_id,
+        })?;
+        self.show()
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_blame.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.visible && ev == AsyncNotification::Blame {
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) -> Result<()> {
+        if let Some(err) = self.git_blame.last_error()? {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "blame error:\n{}",
+                    err
+                )),
+            );
+
+            // go back to what is still on screen
+            self.params = self
+                .file_blame
+                .as_ref()
+                .map(|(params, _)| params.clone());
+            if self.params.is_none() {
+                self.hide();
+            }
+        } else if let Some((params, blame)) = self.git_blame.last()? {
+            if Some(&params) == self.params.as_ref() {
+                self.selection = cmp::min(
+                    self.selection,
+                    blame.lines.len().saturating_sub(1),
+                );
+                self.file_blame = Some((params, blame));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn request(&mut self, params: BlameParams) -> Result<()> {
+        self.git_blame.request(params.clone())?;
+        self.params = Some(params);
+
+        Ok(())
+    }
+
+    fn title(&self) -> String {
+        let params = self.params.as_ref();
+        let path = params.map_or("", |p| p.file_path.as_str());
+        let commit: String =
+            params.and_then(|p| p.commit_id).map_or_else(
+                || "HEAD".to_string(),
+                |id| id.to_string().chars().take(7).collect(),
+            );
+        let loading =
+            self.file_blame.as_ref().map(|(p, _)| p) != params;
+
+        strings::blame_title(&self.key_config, path, &commit, loading)
+    }
+
+    fn selected_hunk(&self) -> Option<&BlameHunk> {
+        self.file_blame
+            .as_ref()
+            .and_then(|(_, b)| b.lines.get(self.selection))
+            .and_then(|(hunk, _)| hunk.as_ref())
+    }
+
+    fn inspect_selected(&self) {
+        if let Some(hunk) = self.selected_hunk() {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::InspectCommit(hunk.commit_id, None),
+            );
+        }
+    }
+
+    /// blames the file again right before the selected line
+    /// was last changed
+    fn blame_parent(&mut self) -> Result<()> {
+        let params = match (self.selected_hunk(), &self.params) {
+            (Some(hunk), Some(params)) => {
+                hunk.parent_id.map(|parent| BlameParams {
+                    file_path: params.file_path.clone(),
+                    commit_id: Some(parent),
+                })
+            }
+            _ => None,
+        };
+
+        if let Some(params) = params {
+            self.request(params)?;
+        }
+
+        Ok(())
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) {
+        let max = self
+            .file_blame
+            .as_ref()
+            .map_or(0, |(_, b)| b.lines.len().saturating_sub(1));
+        let page = self.current_height.get().saturating_sub(1).max(1);
+
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_sub(1),
+            ScrollType::Down => self.selection.saturating_add(1),
+            ScrollType::PageUp => self.selection.saturating_sub(page),
+            ScrollType::PageDown => {
+                self.selection.saturating_add(page)
+            }
+            ScrollType::Home => 0,
+            ScrollType::End => max,
+        };
+
+        self.selection = cmp::min(new_selection, max);
+    }
+
+    fn get_text(&self, height: usize) -> Vec<Text> {
+        let blame = match &self.file_blame {
+            Some((_, blame)) => blame,
+            None => return Vec::new(),
+        };
+
+        let scroll_top = self.scroll_top.get();
+        let line_no_width = blame.lines.len().to_string().len();
+
+        let mut txt = Vec::with_capacity(height * 6);
+        let mut odd_band = false;
+        let mut last_commit = None;
+
+        // bands are counted from the top so they do not flip
+        // while scrolling
+        for (idx, (hunk, line)) in
+            blame.lines.iter().enumerate().take(scroll_top + height)
+        {
+            let commit = hunk.as_ref().map(|h| h.commit_id);
+            if idx > 0 && commit != last_commit {
+                odd_band = !odd_band;
+            }
+            last_commit = commit;
+
+            if idx < scroll_top {
+                continue;
+            }
+
+            let selected = idx == self.selection;
+
+            self.add_gutter(
+                &mut txt,
+                hunk.as_ref(),
+                odd_band,
+                selected,
+            );
+
+            txt.push(Text::Styled(
+                Cow::from(format!(
+                    " {:>w$} ",
+                    idx + 1,
+                    w = line_no_width
+                )),
+                self.theme.text(false, selected),
+            ));
+            txt.push(Text::Styled(
+                Cow::from(format!(
+                    "{}\n",
+                    line.replace('\t', "    ")
+                )),
+                self.theme.text(true, selected),
+            ));
+        }
+
+        txt
+    }
+
+    fn add_gutter(
+        &self,
+        txt: &mut Vec<Text>,
+        hunk: Option<&BlameHunk>,
+        odd_band: bool,
+        selected: bool,
+    ) {
+        let (hash, author, age) = hunk.map_or_else(
+            || (String::new(), String::new(), String::new()),
+            |h| {
+                (
+                    h.commit_id.to_string().chars().take(7).collect(),
+                    h.author.chars().take(AUTHOR_WIDTH).collect(),
+                    time_to_age(h.time),
+                )
+            },
+        );
+
+        let band =
+            |style| self.theme.blame_band(style, odd_band, selected);
+
+        txt.push(Text::Styled(
+            Cow::from(format!("{:7} ", hash)),
+            band(self.theme.commit_hash(selected)),
+        ));
+        txt.push(Text::Styled(
+            Cow::from(format!("{:w$} ", author, w = AUTHOR_WIDTH)),
+            band(self.theme.commit_author(selected)),
+        ));
+        txt.push(Text::Styled(
+            Cow::from(format!("{:>4}", age)),
+            band(self.theme.commit_time(selected)),
+        ));
+    }
+}
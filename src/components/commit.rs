@@ -12,7 +12,10 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{self, CommitId, HookResult},
+    sync::{
+        self, CommitId, CommitMessage, HookResult,
+        PrepareCommitMsgSource, RepoState,
+    },
     CWD,
 };
 use crossterm::event::Event;
@@ -26,6 +29,7 @@ use tui::{backend::Backend, layout::Rect, Frame};
 pub struct CommitComponent {
     input: TextInputComponent,
     amend: Option<CommitId>,
+    merging: bool,
     queue: Queue,
     key_config: SharedKeyConfig,
 }
@@ -113,10 +117,35 @@ impl Component for CommitComponent {
 
     fn show(&mut self) -> Result<()> {
         self.amend = None;
+        let state = sync::repo_state(CWD)?;
+        self.merging = matches!(
+            state,
+            RepoState::Merge
+                | RepoState::CherryPick
+                | RepoState::Revert
+        );
 
         self.input.clear();
-        self.input
-            .set_title(strings::commit_title(&self.key_config));
+        self.input.set_title(match state {
+            RepoState::Merge => {
+                strings::commit_title_merge(&self.key_config)
+            }
+            RepoState::CherryPick => {
+                strings::commit_title_cherry_pick(&self.key_config)
+            }
+            RepoState::Revert => {
+                strings::commit_title_revert(&self.key_config)
+            }
+            _ => strings::commit_title(&self.key_config),
+        });
+        let (source, msg) = if self.merging {
+            (PrepareCommitMsgSource::Merge, sync::merge_msg(CWD)?)
+        } else {
+            (PrepareCommitMsgSource::Message, String::new())
+        };
+        if !self.prepare_msg(source, &msg)? {
+            return Ok(());
+        }
         self.input.show()?;
 
         Ok(())
@@ -133,6 +162,7 @@ impl CommitComponent {
         Self {
             queue,
             amend: None,
+            merging: false,
             input: TextInputComponent::new(
                 theme,
                 key_config.clone(),
@@ -170,20 +200,7 @@ impl CommitComponent {
         drop(file);
         std::fs::remove_file(&config_path)?;
 
-        let message: String = message
-            .lines()
-            .flat_map(|l| {
-                if l.starts_with('#') {
-                    vec![]
-                } else {
-                    vec![l, "\n"]
-                }
-            })
-            .collect();
-
-        let message = message.trim().to_string();
-
-        self.input.set_text(message);
+        self.input.set_text(strip_comments(&message));
         self.input.show()?;
 
         Ok(())
@@ -194,6 +211,17 @@ impl CommitComponent {
     }
 
     fn commit_msg(&mut self, msg: String) -> Result<()> {
+        if let HookResult::NotOk(e) = sync::hooks_pre_commit(CWD)? {
+            log::error!("pre-commit hook error: {}", e);
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "pre-commit hook error:\n{}",
+                    e
+                )),
+            );
+            return Ok(());
+        }
+
         let mut msg = msg;
         if let HookResult::NotOk(e) =
             sync::hooks_commit_msg(CWD, &mut msg)?
@@ -208,10 +236,14 @@ impl CommitComponent {
             return Ok(());
         }
 
-        let res = self.amend.map_or_else(
-            || sync::commit(CWD, &msg),
-            |amend| sync::amend(CWD, amend, &msg),
-        );
+        let res = if self.merging {
+            sync::merge_commit(CWD, &msg)
+        } else {
+            self.amend.map_or_else(
+                || sync::commit(CWD, &msg),
+                |amend| sync::amend(CWD, amend, &msg),
+            )
+        };
         if let Err(e) = res {
             log::error!("commit error: {}", &e);
             self.queue.borrow_mut().push_back(
@@ -248,6 +280,7 @@ impl CommitComponent {
 
     fn can_amend(&self) -> bool {
         self.amend.is_none()
+            && !self.merging
             && sync::get_head(CWD).is_ok()
             && self.input.get_text().is_empty()
     }
@@ -261,10 +294,102 @@ impl CommitComponent {
         self.input
             .set_title(strings::commit_title_amend(&self.key_config));
 
-        if let Some(msg) = details.message {
-            self.input.set_text(msg.combine());
+        let msg = details
+            .message
+            .map(CommitMessage::combine)
+            .unwrap_or_default();
+        if !self
+            .prepare_msg(PrepareCommitMsgSource::Commit(id), &msg)?
+        {
+            self.hide();
         }
 
         Ok(())
     }
+
+    /// seeds the input with `msg` as altered by the
+    /// `prepare-commit-msg` hook, returns false if the hook failed
+    fn prepare_msg(
+        &mut self,
+        source: PrepareCommitMsgSource,
+        original: &str,
+    ) -> Result<bool> {
+        let mut msg = original.to_string();
+        if let HookResult::NotOk(e) =
+            sync::hooks_prepare_commit_msg(CWD, source, &mut msg)?
+        {
+            log::error!("prepare-commit-msg hook error: {}", e);
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "prepare-commit-msg hook error:\n{}",
+                    e
+                )),
+            );
+            return Ok(false);
+        }
+
+        self.input.set_text(prepared_msg(
+            source == PrepareCommitMsgSource::Merge,
+            original,
+            msg,
+        ));
+
+        Ok(true)
+    }
+}
+
+/// only messages git or a hook wrote can contain comments,
+/// any other message (like the one amended) is kept as is
+fn prepared_msg(
+    merging: bool,
+    original: &str,
+    msg: String,
+) -> String {
+    if merging || msg != original {
+        strip_comments(&msg)
+    } else {
+        msg
+    }
+}
+
+/// drops the `#` comment lines git tools add to commit messages
+fn strip_comments(msg: &str) -> String {
+    let msg: String = msg
+        .lines()
+        .flat_map(|l| {
+            if l.starts_with('#') {
+                vec![]
+            } else {
+                vec![l, "\n"]
+            }
+        })
+        .collect();
+
+    msg.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amend_keeps_comment_lines() {
+        let msg = "title\n\n# not a comment\n".to_string();
+
+        assert_eq!(prepared_msg(false, &msg, msg.clone()), msg);
+        assert_eq!(
+            prepared_msg(false, &msg, format!("{}# hook\n", msg)),
+            "title"
+        );
+    }
+
+    #[test]
+    fn test_merge_msg_drops_comment_lines() {
+        let msg = "Merge branch 'a'\n\n# Conflicts:\n#\ta.txt\n";
+
+        assert_eq!(
+            prepared_msg(true, msg, msg.to_string()),
+            "Merge branch 'a'"
+        );
+    }
 }
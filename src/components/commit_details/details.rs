@@ -9,7 +9,9 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{self, CommitDetails, CommitId, CommitMessage},
+    sync::{
+        self, CommitDetails, CommitId, CommitMessage, SignatureStatus,
+    },
     CWD,
 };
 use crossterm::event::Event;
@@ -34,6 +36,10 @@ enum Detail {
 pub struct DetailsComponent {
     data: Option<CommitDetails>,
     tags: Vec<String>,
+    /// only signed commits need their signature checked
+    signed: bool,
+    /// `None` while the signature is being checked
+    signature: Option<SignatureStatus>,
     theme: SharedTheme,
     focused: bool,
     current_size: Cell<(u16, u16)>,
@@ -54,6 +60,8 @@ impl DetailsComponent {
         Self {
             data: None,
             tags: Vec::new(),
+            signed: false,
+            signature: None,
             theme,
             focused,
             current_size: Cell::new((0, 0)),
@@ -74,6 +82,10 @@ impl DetailsComponent {
 
         self.scroll_top.set(0);
 
+        self.signed = id.map_or(false, |id| {
+            sync::is_signed(CWD, id).unwrap_or_default()
+        });
+
         if let Some(tags) = tags {
             self.tags.extend(tags)
         }
@@ -81,6 +93,19 @@ impl DetailsComponent {
         Ok(())
     }
 
+    ///
+    pub const fn is_signed(&self) -> bool {
+        self.signed
+    }
+
+    /// result of checking the signature, `None` while pending
+    pub fn set_signature(
+        &mut self,
+        signature: Option<SignatureStatus>,
+    ) {
+        self.signature = signature;
+    }
+
     fn wrap_commit_details(
         message: &CommitMessage,
         width: usize,
@@ -186,6 +211,28 @@ impl DetailsComponent {
         }
     }
 
+    fn signature_text(&self) -> Text<'_> {
+        self.signature.as_ref().map_or_else(
+            || {
+                Text::Styled(
+                    Cow::from(strings::commit::signature_checking(
+                        &self.key_config,
+                    )),
+                    self.theme.text(false, false),
+                )
+            },
+            |status| {
+                Text::Styled(
+                    Cow::from(strings::commit::signature_status(
+                        &self.key_config,
+                        status,
+                    )),
+                    self.theme.signature(status),
+                )
+            },
+        )
+    }
+
     fn get_text_info(&self) -> Vec<Text> {
         let new_line = Text::Raw(Cow::from("\n"));
 
@@ -248,6 +295,17 @@ impl DetailsComponent {
                 new_line.clone(),
             ]);
 
+            res.extend(vec![
+                Text::Styled(
+                    Cow::from(strings::commit::details_signature(
+                        &self.key_config,
+                    )),
+                    self.theme.text(false, false),
+                ),
+                self.signature_text(),
+                new_line.clone(),
+            ]);
+
             if !self.tags.is_empty() {
                 res.push(self.style_detail(&Detail::Sha));
                 res.extend(
@@ -314,7 +372,7 @@ impl DrawableComponent for DetailsComponent {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
-                [Constraint::Length(8), Constraint::Min(10)].as_ref(),
+                [Constraint::Length(9), Constraint::Min(10)].as_ref(),
             )
             .split(rect);
 
@@ -10,8 +10,8 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{CommitId, CommitTags},
-    AsyncCommitFiles, AsyncNotification,
+    sync::{CommitId, CommitTags, SignatureStatus},
+    AsyncCommitFiles, AsyncNotification, AsyncSignature,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
@@ -26,6 +26,7 @@ pub struct CommitDetailsComponent {
     details: DetailsComponent,
     file_tree: FileTreeComponent,
     git_commit_files: AsyncCommitFiles,
+    git_signature: AsyncSignature,
     visible: bool,
     key_config: SharedKeyConfig,
 }
@@ -47,6 +48,7 @@ impl CommitDetailsComponent {
                 false,
             ),
             git_commit_files: AsyncCommitFiles::new(sender),
+            git_signature: AsyncSignature::new(sender),
             file_tree: FileTreeComponent::new(
                 "",
                 false,
@@ -76,6 +78,8 @@ impl CommitDetailsComponent {
         tags: Option<CommitTags>,
     ) -> Result<()> {
         self.details.set_commit(id, tags)?;
+        self.update_signature(id)?;
+        self.file_tree.set_commit(id);
 
         if let Some(id) = id {
             if let Some((fetched_id, res)) =
@@ -98,9 +102,38 @@ impl CommitDetailsComponent {
         Ok(())
     }
 
+    /// checks the signature of a signed commit in the background,
+    /// unsigned commits are shown as such right away
+    fn update_signature(
+        &mut self,
+        id: Option<CommitId>,
+    ) -> Result<()> {
+        let mut signature = None;
+
+        if let Some(id) = id {
+            if self.details.is_signed() {
+                match self.git_signature.current()? {
+                    Some((checked_id, status))
+                        if checked_id == id =>
+                    {
+                        signature = Some(status);
+                    }
+                    _ => self.git_signature.fetch(id)?,
+                }
+            } else {
+                signature = Some(SignatureStatus::Unsigned);
+            }
+        }
+
+        self.details.set_signature(signature);
+
+        Ok(())
+    }
+
     ///
     pub fn any_work_pending(&self) -> bool {
         self.git_commit_files.is_pending()
+            || self.git_signature.is_pending()
     }
 
     ///
@@ -10,10 +10,13 @@ use crate::{
     ui::style::{SharedTheme, Theme},
 };
 use anyhow::Result;
-use asyncgit::sync::Tags;
+use asyncgit::sync::{
+    CommitId, CommitRef, GraphLane, GraphRow, Refs,
+};
 use crossterm::event::Event;
 use std::{
-    borrow::Cow, cell::Cell, cmp, convert::TryFrom, time::Instant,
+    borrow::Cow, cell::Cell, cmp, collections::HashSet,
+    convert::TryFrom, time::Instant,
 };
 use tui::{
     backend::Backend,
@@ -23,7 +26,7 @@ use tui::{
 };
 use unicode_width::UnicodeWidthStr;
 
-const ELEMENTS_PER_LINE: usize = 10;
+const ELEMENTS_PER_LINE: usize = 12;
 
 ///
 pub struct CommitList {
@@ -33,7 +36,9 @@ pub struct CommitList {
     count_total: usize,
     items: ItemBatch,
     scroll_state: (Instant, f32),
-    tags: Option<Tags>,
+    refs: Option<Refs>,
+    marked: Vec<CommitId>,
+    highlighted: Option<HashSet<CommitId>>,
     current_size: Cell<(u16, u16)>,
     scroll_top: Cell<usize>,
     theme: SharedTheme,
@@ -53,7 +58,9 @@ impl CommitList {
             branch: None,
             count_total: 0,
             scroll_state: (Instant::now(), 0_f32),
-            tags: None,
+            refs: None,
+            marked: Vec::new(),
+            highlighted: None,
             current_size: Cell::new((0, 0)),
             scroll_top: Cell::new(0),
             theme,
@@ -67,6 +74,11 @@ impl CommitList {
         &mut self.items
     }
 
+    ///
+    pub fn set_title(&mut self, title: String) {
+        self.title = title;
+    }
+
     ///
     pub fn set_branch(&mut self, name: Option<String>) {
         self.branch = name;
@@ -82,6 +94,11 @@ impl CommitList {
         self.current_size.get()
     }
 
+    /// selects the entry at `position` of the whole log
+    pub fn set_selection(&mut self, position: usize) {
+        self.selection = cmp::min(position, self.selection_max());
+    }
+
     ///
     pub fn set_count_total(&mut self, total: usize) {
         self.count_total = total;
@@ -96,8 +113,8 @@ impl CommitList {
     }
 
     ///
-    pub fn tags(&self) -> Option<&Tags> {
-        self.tags.as_ref()
+    pub fn refs(&self) -> Option<&Refs> {
+        self.refs.as_ref()
     }
 
     ///
@@ -106,8 +123,8 @@ impl CommitList {
     }
 
     ///
-    pub fn set_tags(&mut self, tags: Tags) {
-        self.tags = Some(tags);
+    pub fn set_refs(&mut self, refs: Refs) {
+        self.refs = Some(refs);
     }
 
     ///
@@ -117,6 +134,53 @@ impl CommitList {
         )
     }
 
+    /// commits marked to act on as a range
+    pub fn marked(&self) -> &[CommitId] {
+        &self.marked
+    }
+
+    ///
+    pub fn clear_marked(&mut self) {
+        self.marked.clear();
+    }
+
+    /// commits to highlight (search matches), `None` disables
+    pub fn set_highlighting(
+        &mut self,
+        highlighted: Option<HashSet<CommitId>>,
+    ) {
+        self.highlighted = highlighted;
+    }
+
+    /// adds commits to highlight, if highlighting is enabled
+    pub fn add_highlighting(&mut self, ids: &[CommitId]) {
+        if let Some(highlighted) = self.highlighted.as_mut() {
+            highlighted.extend(ids.iter().copied());
+        }
+    }
+
+    ///
+    pub fn is_highlighted(&self, id: &CommitId) -> bool {
+        self.highlighted
+            .as_ref()
+            .map_or(false, |highlighted| highlighted.contains(id))
+    }
+
+    fn toggle_mark(&mut self) -> bool {
+        if let Some(id) = self.selected_entry().map(|e| e.id) {
+            if let Some(idx) =
+                self.marked.iter().position(|m| *m == id)
+            {
+                self.marked.remove(idx);
+            } else {
+                self.marked.push(id);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
     fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
         self.update_scroll_speed();
 
@@ -178,11 +242,15 @@ impl CommitList {
         self.scroll_state.1 = speed.min(SCROLL_SPEED_MAX);
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn add_entry<'b>(
         e: &'b LogEntry,
         selected: bool,
+        highlighted: bool,
+        marked: Option<bool>,
+        graph_width: usize,
         txt: &mut Vec<Text<'b>>,
-        tags: Option<String>,
+        refs: Option<&'b [CommitRef]>,
         theme: &Theme,
         width: usize,
     ) {
@@ -192,6 +260,22 @@ impl CommitList {
         let splitter =
             Text::Styled(splitter_txt, theme.text(true, selected));
 
+        // mark column, only while any commit is marked
+        if let Some(marked) = marked {
+            txt.push(Text::Styled(
+                Cow::from(if marked { "\u{2713} " } else { "  " }),
+                theme.text(true, selected),
+            ));
+        }
+
+        // commit graph
+        if let Some(graph) = &e.graph {
+            txt.push(Text::Styled(
+                Cow::from(graph_text(graph, graph_width)),
+                theme.text(true, selected),
+            ));
+        }
+
         // commit hash
         txt.push(Text::Styled(
             Cow::from(e.hash_short.as_str()),
@@ -220,22 +304,23 @@ impl CommitList {
 
         txt.push(splitter.clone());
 
-        // commit tags
-        txt.push(Text::Styled(
-            Cow::from(if let Some(tags) = tags {
-                format!(" {}", tags)
-            } else {
-                String::from("")
-            }),
-            theme.tags(selected),
-        ));
-
-        txt.push(splitter);
+        // branches, tags and other refs pointing to the commit
+        for r in refs.unwrap_or_default() {
+            txt.push(Text::Styled(
+                Cow::from(r.name.as_str()),
+                theme.commit_ref(r.kind, selected),
+            ));
+            txt.push(splitter.clone());
+        }
 
         // commit msg
         txt.push(Text::Styled(
             Cow::from(e.msg.as_str()),
-            theme.text(true, selected),
+            if highlighted {
+                theme.search_match(selected)
+            } else {
+                theme.text(true, selected)
+            },
         ));
         txt.push(Text::Raw(Cow::from("\n")));
     }
@@ -245,6 +330,15 @@ impl CommitList {
 
         let mut txt = Vec::with_capacity(height * ELEMENTS_PER_LINE);
 
+        let graph_width = self
+            .items
+            .iter()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .filter_map(|e| e.graph.as_ref().map(|g| g.lanes.len()))
+            .max()
+            .unwrap_or_default();
+
         for (idx, e) in self
             .items
             .iter()
@@ -252,17 +346,26 @@ impl CommitList {
             .take(height)
             .enumerate()
         {
-            let tags = self
-                .tags
+            let refs = self
+                .refs
                 .as_ref()
-                .and_then(|t| t.get(&e.id))
-                .map(|tags| tags.join(" "));
+                .and_then(|r| r.get(&e.id))
+                .map(Vec::as_slice);
+
+            let marked = if self.marked.is_empty() {
+                None
+            } else {
+                Some(self.marked.contains(&e.id))
+            };
 
             Self::add_entry(
                 e,
                 idx + self.scroll_top.get() == selection,
+                self.is_highlighted(&e.id),
+                marked,
+                graph_width,
                 &mut txt,
-                tags,
+                refs,
                 &self.theme,
                 width,
             );
@@ -351,6 +454,8 @@ impl Component for CommitList {
                 self.move_selection(ScrollType::PageUp)?
             } else if k == self.key_config.page_down {
                 self.move_selection(ScrollType::PageDown)?
+            } else if k == self.key_config.log_mark_commit {
+                self.toggle_mark()
             } else {
                 false
             };
@@ -370,10 +475,63 @@ impl Component for CommitList {
             self.selected_entry().is_some(),
             true,
         ));
+        out.push(CommandInfo::new(
+            strings::commands::log_mark_commit(&self.key_config),
+            self.selected_entry().is_some(),
+            true,
+        ));
         CommandBlocking::PassingOn
     }
 }
 
+/// draws the lanes of `row` padded to `width` lanes, each lane
+/// is followed by the connection to its right neighbour
+fn graph_text(row: &GraphRow, width: usize) -> String {
+    // span of the horizontal connections to the commit
+    let (from, to) = row
+        .lanes
+        .iter()
+        .enumerate()
+        .filter(|(_, lane)| {
+            matches!(
+                lane,
+                GraphLane::Join | GraphLane::Fork | GraphLane::Merge
+            )
+        })
+        .fold((row.commit, row.commit), |(from, to), (idx, _)| {
+            (from.min(idx), to.max(idx))
+        });
+
+    let mut txt = String::with_capacity(width * 2 * 3);
+    for idx in 0..width.max(row.lanes.len()) {
+        let lane =
+            row.lanes.get(idx).copied().unwrap_or(GraphLane::Empty);
+        let left = idx < row.commit;
+        let crossed = idx > from && idx < to;
+
+        txt.push(match lane {
+            GraphLane::Commit => '\u{25cf}',
+            GraphLane::Line if crossed => '\u{253c}',
+            GraphLane::Line => '\u{2502}',
+            GraphLane::Empty if crossed => '\u{2500}',
+            GraphLane::Empty => ' ',
+            GraphLane::Join if left => '\u{2570}',
+            GraphLane::Join => '\u{256f}',
+            GraphLane::Fork if left => '\u{256d}',
+            GraphLane::Fork => '\u{256e}',
+            GraphLane::Merge if left => '\u{251c}',
+            GraphLane::Merge => '\u{2524}',
+        });
+        txt.push(if idx >= from && idx < to {
+            '\u{2500}'
+        } else {
+            ' '
+        });
+    }
+
+    txt
+}
+
 #[inline]
 fn string_width_align(s: &str, width: usize) -> String {
     static POSTFIX: &str = "..";
@@ -410,6 +568,29 @@ mod tests {
         assert_eq!(string_width_align("1234556", 4), "12..");
     }
 
+    #[test]
+    fn test_graph_text() {
+        use GraphLane::{Commit, Empty, Fork, Join, Line, Merge};
+
+        let row = |lanes: Vec<GraphLane>, commit| GraphRow {
+            lanes,
+            commit,
+        };
+
+        assert_eq!(
+            graph_text(&row(vec![Line, Commit, Line, Fork], 1), 5),
+            "\u{2502} \u{25cf}\u{2500}\u{253c}\u{2500}\u{256e}   "
+        );
+        assert_eq!(
+            graph_text(&row(vec![Join, Empty, Commit], 2), 3),
+            "\u{2570}\u{2500}\u{2500}\u{2500}\u{25cf} "
+        );
+        assert_eq!(
+            graph_text(&row(vec![Commit, Merge], 0), 1),
+            "\u{25cf}\u{2500}\u{2524} "
+        );
+    }
+
     #[test]
     fn test_string_width_align_unicode() {
         assert_eq!(string_width_align("äste", 3), "ä..");
@@ -1,26 +1,262 @@
 use super::{
+    utils::word_diff::{word_diff, WordSpans},
     CommandBlocking, Direction, DrawableComponent, ScrollType,
 };
 use crate::{
     components::{CommandInfo, Component},
     keys::SharedKeyConfig,
+    options::SharedOptions,
     queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
     strings, try_or_popup,
     ui::{self, calc_scroll_top, style::SharedTheme},
 };
 use anyhow::Result;
-use asyncgit::{hash, sync, DiffLine, DiffLineType, FileDiff, CWD};
+use asyncgit::{
+    hash,
+    sync::{
+        self, diff::Hunk, ConflictChunk, ConflictResolution,
+        DiffLinePosition, DiffOptions, FileConflict,
+    },
+    DiffLine, DiffLineType, FileDiff, CWD,
+};
 use bytesize::ByteSize;
-use crossterm::event::Event;
-use std::{borrow::Cow, cell::Cell, cmp, path::Path};
+use crossterm::event::{Event, KeyEvent};
+use std::{
+    borrow::Cow, cell::Cell, cmp, collections::HashMap, iter,
+    path::Path,
+};
 use tui::{
     backend::Backend,
     layout::Rect,
+    style::{Color, Style},
     symbols,
     widgets::{Block, Borders, Paragraph, Text},
     Frame,
 };
 
+const CONFLICT_OURS: &str = "<<<<<<< ours\n";
+const CONFLICT_BASE: &str = "||||||| base\n";
+const CONFLICT_SEPARATOR: &str = "=======\n";
+const CONFLICT_THEIRS: &str = ">>>>>>> theirs\n";
+
+const fn header_line(content: String) -> DiffLine {
+    DiffLine {
+        content,
+        line_type: DiffLineType::Header,
+        old_lineno: None,
+        new_lineno: None,
+        syntax: Vec::new(),
+    }
+}
+
+fn diff_lines(
+    lines: &[String],
+    line_type: DiffLineType,
+) -> Vec<DiffLine> {
+    lines
+        .iter()
+        .map(|l| DiffLine {
+            content: l.clone(),
+            line_type,
+            ..DiffLine::default()
+        })
+        .collect()
+}
+
+/// row of the side-by-side layout with the indices of the diff
+/// lines shown on the old (left) and the new (right) side
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SplitRow {
+    hunk: usize,
+    old: Option<usize>,
+    new: Option<usize>,
+}
+
+/// pairs up the deleted and added lines of each hunk,
+/// headers and unchanged lines are shown on both sides
+fn split_rows(diff: &FileDiff) -> Vec<SplitRow> {
+    let mut rows = Vec::new();
+    let mut index = 0;
+
+    for (hunk, h) in diff.hunks.iter().enumerate() {
+        let mut deleted = Vec::new();
+        let mut added = Vec::new();
+
+        for line in &h.lines {
+            match line.line_type {
+                DiffLineType::Delete => deleted.push(index),
+                DiffLineType::Add => added.push(index),
+                DiffLineType::None | DiffLineType::Header => {
+                    push_split_pairs(
+                        &mut rows,
+                        hunk,
+                        &mut deleted,
+                        &mut added,
+                    );
+                    rows.push(SplitRow {
+                        hunk,
+                        old: Some(index),
+                        new: Some(index),
+                    });
+                }
+            }
+            index += 1;
+        }
+
+        push_split_pairs(&mut rows, hunk, &mut deleted, &mut added);
+    }
+
+    rows
+}
+
+fn push_split_pairs(
+    rows: &mut Vec<SplitRow>,
+    hunk: usize,
+    deleted: &mut Vec<usize>,
+    added: &mut Vec<usize>,
+) {
+    for i in 0..cmp::max(deleted.len(), added.len()) {
+        rows.push(SplitRow {
+            hunk,
+            old: deleted.get(i).copied(),
+            new: added.get(i).copied(),
+        });
+    }
+
+    deleted.clear();
+    added.clear();
+}
+
+/// changed words of the deleted and added lines paired up by `rows`,
+/// by index of the line in the diff
+fn word_diffs(
+    diff: &FileDiff,
+    rows: &[SplitRow],
+) -> HashMap<usize, WordSpans> {
+    let lines: Vec<&DiffLine> =
+        diff.hunks.iter().flat_map(|h| h.lines.iter()).collect();
+    let mut res = HashMap::new();
+
+    for row in rows {
+        if let (Some(old), Some(new)) = (row.old, row.new) {
+            if old == new {
+                continue;
+            }
+
+            if let Some((old_words, new_words)) = word_diff(
+                trim_line_ending(&lines[old].content),
+                trim_line_ending(&lines[new].content),
+            ) {
+                res.insert(old, old_words);
+                res.insert(new, new_words);
+            }
+        }
+    }
+
+    res
+}
+
+/// digits of the highest line number in `diff`
+fn lineno_width(diff: &FileDiff) -> usize {
+    diff.hunks
+        .iter()
+        .flat_map(|h| h.lines.iter())
+        .flat_map(|l| l.old_lineno.into_iter().chain(l.new_lineno))
+        .max()
+        .map_or(0, |n| n.to_string().len())
+}
+
+fn lineno_text(lineno: Option<u32>, width: usize) -> String {
+    lineno.map_or_else(
+        || " ".repeat(width),
+        |n| format!("{:>w$}", n, w = width),
+    )
+}
+
+fn trim_line_ending(content: &str) -> &str {
+    content.trim_matches(|c| c == '\n' || c == '\r')
+}
+
+/// piece of the content of a diff line and how it gets coloured
+struct ContentSpan {
+    content: String,
+    /// part of the changed words
+    changed: bool,
+    /// colour of the syntax
+    color: Option<Color>,
+}
+
+/// content of `line` split where the changed `words` or the colours
+/// of its syntax change, tabs expanded and cut off after `width` chars
+fn content_spans(
+    line: &DiffLine,
+    words: Option<&WordSpans>,
+    width: usize,
+) -> Vec<ContentSpan> {
+    let trimmed = trim_line_ending(&line.content);
+    // syntax ranges refer to the untrimmed content
+    let offset = line.content.len()
+        - line
+            .content
+            .trim_start_matches(|c| c == '\n' || c == '\r')
+            .len();
+
+    let mut bounds: Vec<usize> = words
+        .into_iter()
+        .flatten()
+        .map(|(range, _)| range.start)
+        .chain(
+            line.syntax
+                .iter()
+                .map(|s| s.range.start.saturating_sub(offset)),
+        )
+        .chain(iter::once(0))
+        .filter(|pos| *pos < trimmed.len())
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    let mut res = Vec::with_capacity(bounds.len());
+    let mut remaining = width;
+
+    for (i, start) in bounds.iter().copied().enumerate() {
+        if remaining == 0 {
+            break;
+        }
+
+        let end = bounds.get(i + 1).copied().unwrap_or(trimmed.len());
+
+        //TODO: allow customize tabsize
+        let content: String = trimmed
+            .get(start..end)
+            .unwrap_or_default()
+            .replace('\t', "  ")
+            .chars()
+            .take(remaining)
+            .collect();
+        remaining -= content.chars().count();
+
+        let changed = words.map_or(false, |words| {
+            words.iter().any(|(range, changed)| {
+                *changed && range.contains(&start)
+            })
+        });
+        let color = line
+            .syntax
+            .iter()
+            .find(|s| s.range.contains(&(start + offset)))
+            .map(|s| Color::Rgb(s.color.0, s.color.1, s.color.2));
+
+        res.push(ContentSpan {
+            content,
+            changed,
+            color,
+        });
+    }
+
+    res
+}
+
 #[derive(Default)]
 struct Current {
     path: String,
@@ -28,6 +264,14 @@ struct Current {
     hash: u64,
 }
 
+/// three-way view of a conflicted file and the choices
+/// made for its conflicts so far
+struct ConflictState {
+    file: FileConflict,
+    hash: u64,
+    choices: Vec<Option<ConflictResolution>>,
+}
+
 ///
 #[derive(Clone, Copy)]
 enum Selection {
@@ -94,6 +338,11 @@ impl Selection {
 ///
 pub struct DiffComponent {
     diff: Option<FileDiff>,
+    split_rows: Vec<SplitRow>,
+    word_diffs: HashMap<usize, WordSpans>,
+    /// digits of the line numbers, no gutter if 0
+    lineno_width: usize,
+    conflict: Option<ConflictState>,
     pending: bool,
     selection: Selection,
     selected_hunk: Option<usize>,
@@ -104,6 +353,7 @@ pub struct DiffComponent {
     queue: Queue,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
     is_immutable: bool,
 }
 
@@ -111,6 +361,7 @@ impl DiffComponent {
     ///
     pub fn new(
         queue: Queue,
+        options: SharedOptions,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
         is_immutable: bool,
@@ -122,11 +373,16 @@ impl DiffComponent {
             pending: false,
             selected_hunk: None,
             diff: None,
+            split_rows: Vec::new(),
+            word_diffs: HashMap::new(),
+            lineno_width: 0,
+            conflict: None,
             current_size: Cell::new((0, 0)),
             selection: Selection::Single(0),
             scroll_top: Cell::new(0),
             theme,
             key_config,
+            options,
             is_immutable,
         }
     }
@@ -145,6 +401,10 @@ impl DiffComponent {
     pub fn clear(&mut self, pending: bool) -> Result<()> {
         self.current = Current::default();
         self.diff = None;
+        self.split_rows.clear();
+        self.word_diffs.clear();
+        self.lineno_width = 0;
+        self.conflict = None;
         self.scroll_top.set(0);
         self.selection = Selection::Single(0);
         self.selected_hunk = None;
@@ -163,7 +423,9 @@ impl DiffComponent {
 
         let hash = hash(&diff);
 
-        if self.current.hash != hash {
+        if self.current.hash != hash || self.conflict.is_some() {
+            self.conflict = None;
+
             self.current = Current {
                 path,
                 is_stage,
@@ -175,6 +437,9 @@ impl DiffComponent {
                 self.selection.get_start(),
             )?;
 
+            self.split_rows = split_rows(&diff);
+            self.word_diffs = word_diffs(&diff, &self.split_rows);
+            self.lineno_width = lineno_width(&diff);
             self.diff = Some(diff);
             self.scroll_top.set(0);
             self.selection = Selection::Single(0);
@@ -183,6 +448,258 @@ impl DiffComponent {
         Ok(())
     }
 
+    /// shows `conflict` as three-way view, choices made so far
+    /// are kept as long as the conflict stays the same
+    pub fn update_conflict(
+        &mut self,
+        path: String,
+        conflict: FileConflict,
+    ) -> Result<()> {
+        self.pending = false;
+
+        let hash = hash(&conflict);
+
+        let unchanged = self.current.path == path
+            && self
+                .conflict
+                .as_ref()
+                .map_or(false, |c| c.hash == hash);
+
+        if !unchanged {
+            self.current = Current {
+                path,
+                is_stage: false,
+                hash,
+            };
+            self.conflict = Some(ConflictState {
+                choices: vec![None; conflict.conflict_count()],
+                file: conflict,
+                hash,
+            });
+            self.scroll_top.set(0);
+            self.selection = Selection::Single(0);
+            self.update_conflict_diff()?;
+        }
+
+        Ok(())
+    }
+
+    /// renders the conflict state into `self.diff`,
+    /// one hunk per `ConflictChunk`
+    fn update_conflict_diff(&mut self) -> Result<()> {
+        if let Some(conflict) = &self.conflict {
+            let mut hunks = Vec::new();
+            let mut choices = conflict.choices.iter();
+
+            if conflict.file.binary {
+                hunks.push(Hunk {
+                    header_hash: 0,
+                    lines: vec![header_line(
+                        strings::conflict_binary(&self.key_config),
+                    )],
+                });
+            }
+
+            for chunk in &conflict.file.chunks {
+                let lines = match chunk {
+                    ConflictChunk::Merged(l) => {
+                        diff_lines(l, DiffLineType::None)
+                    }
+                    ConflictChunk::Conflict { .. } => self
+                        .conflict_lines(
+                            chunk,
+                            choices.next().copied().flatten(),
+                        ),
+                };
+
+                hunks.push(Hunk {
+                    header_hash: hash(chunk),
+                    lines,
+                });
+            }
+
+            let diff = FileDiff {
+                lines: hunks.iter().map(|h| h.lines.len()).sum(),
+                hunks,
+                ..FileDiff::default()
+            };
+
+            let max = diff.lines.saturating_sub(1);
+            self.selection = Selection::Single(cmp::min(
+                self.selection.get_start(),
+                max,
+            ));
+            self.selected_hunk = Self::find_selected_hunk(
+                &diff,
+                self.selection.get_start(),
+            )?;
+            self.word_diffs.clear();
+            self.lineno_width = 0;
+            self.diff = Some(diff);
+        }
+
+        Ok(())
+    }
+
+    /// ours, base and theirs section of an unresolved conflict
+    /// or the resulting lines once it got resolved
+    fn conflict_lines(
+        &self,
+        chunk: &ConflictChunk,
+        resolution: Option<ConflictResolution>,
+    ) -> Vec<DiffLine> {
+        let mut res = Vec::new();
+
+        if let ConflictChunk::Conflict { base, ours, theirs } = chunk
+        {
+            if let Some(resolution) = resolution {
+                res.push(header_line(strings::conflict_resolved(
+                    &self.key_config,
+                    resolution,
+                )));
+                if resolution != ConflictResolution::Theirs {
+                    res.extend(diff_lines(ours, DiffLineType::Add));
+                }
+                if resolution != ConflictResolution::Ours {
+                    res.extend(diff_lines(theirs, DiffLineType::Add));
+                }
+            } else {
+                res.push(header_line(CONFLICT_OURS.to_string()));
+                res.extend(diff_lines(ours, DiffLineType::Add));
+                res.push(header_line(CONFLICT_BASE.to_string()));
+                res.extend(diff_lines(base, DiffLineType::None));
+                res.push(header_line(CONFLICT_SEPARATOR.to_string()));
+                res.extend(diff_lines(theirs, DiffLineType::Delete));
+                res.push(header_line(CONFLICT_THEIRS.to_string()));
+            }
+        }
+
+        res
+    }
+
+    /// index of the conflict (not chunk) the selection is in
+    fn selected_conflict(&self) -> Option<usize> {
+        let conflict = self.conflict.as_ref()?;
+        let hunk = self.selected_hunk?;
+        let is_conflict = |c: &ConflictChunk| {
+            matches!(c, ConflictChunk::Conflict { .. })
+        };
+
+        // binary conflicts have no chunks, just the hint hunk
+        if is_conflict(conflict.file.chunks.get(hunk)?) {
+            Some(
+                conflict.file.chunks[..hunk]
+                    .iter()
+                    .filter(|c| is_conflict(c))
+                    .count(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// resolves the selected conflict, once all of them are
+    /// resolved the file gets written and staged
+    fn take_conflict(
+        &mut self,
+        resolution: ConflictResolution,
+    ) -> Result<()> {
+        if let Some(idx) = self.selected_conflict() {
+            if let Some(conflict) = self.conflict.as_mut() {
+                conflict.choices[idx] = Some(resolution);
+
+                let choices = conflict
+                    .choices
+                    .iter()
+                    .copied()
+                    .collect::<Option<Vec<_>>>();
+
+                if let Some(choices) = choices {
+                    let content = conflict.file.resolve(&choices);
+                    sync::resolve_conflict(
+                        CWD,
+                        &self.current.path,
+                        &content,
+                    )?;
+                    self.queue_update();
+                }
+            }
+
+            self.update_conflict_diff()?;
+        }
+
+        Ok(())
+    }
+
+    /// resolves the whole file and stages it
+    fn take_conflict_file(
+        &mut self,
+        resolution: ConflictResolution,
+    ) -> Result<()> {
+        if let Some(conflict) = &self.conflict {
+            if resolution == ConflictResolution::Both {
+                if !conflict.file.binary {
+                    let content = conflict.file.resolve(&vec![
+                        resolution;
+                        conflict.choices.len()
+                    ]);
+                    sync::resolve_conflict(
+                        CWD,
+                        &self.current.path,
+                        &content,
+                    )?;
+                    self.queue_update();
+                }
+            } else {
+                sync::resolve_conflict_side(
+                    CWD,
+                    &self.current.path,
+                    resolution,
+                )?;
+                self.queue_update();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn conflict_event(&mut self, e: KeyEvent) -> bool {
+        let key_config = &self.key_config;
+
+        let (resolution, whole_file) =
+            if e == key_config.conflict_take_ours {
+                (ConflictResolution::Ours, false)
+            } else if e == key_config.conflict_take_theirs {
+                (ConflictResolution::Theirs, false)
+            } else if e == key_config.conflict_take_both {
+                (ConflictResolution::Both, false)
+            } else if e == key_config.conflict_file_ours {
+                (ConflictResolution::Ours, true)
+            } else if e == key_config.conflict_file_theirs {
+                (ConflictResolution::Theirs, true)
+            } else if e == key_config.conflict_file_both {
+                (ConflictResolution::Both, true)
+            } else {
+                return false;
+            };
+
+        if whole_file {
+            try_or_popup!(
+                self,
+                "resolve error:",
+                self.take_conflict_file(resolution)
+            );
+        } else {
+            try_or_popup!(
+                self,
+                "resolve error:",
+                self.take_conflict(resolution)
+            );
+        }
+
+        true
+    }
+
     fn move_selection(
         &mut self,
         move_type: ScrollType,
@@ -224,11 +741,56 @@ impl DiffComponent {
     }
 
     fn lines_count(&self) -> usize {
+        if self.is_split() {
+            return self.split_rows.len().saturating_sub(1);
+        }
+
         self.diff
             .as_ref()
             .map_or(0, |diff| diff.lines.saturating_sub(1))
     }
 
+    /// side-by-side layout is used for diffs with hunks only,
+    /// conflicts keep their three-way view
+    fn is_split(&self) -> bool {
+        self.options.borrow().diff_split
+            && self.conflict.is_none()
+            && !self.split_rows.is_empty()
+    }
+
+    /// row the selection ends on, differs from the line in split view
+    fn selection_row(&self) -> usize {
+        let line = self.selection.get_end();
+
+        if self.is_split() {
+            self.split_rows
+                .iter()
+                .position(|row| {
+                    row.old == Some(line) || row.new == Some(line)
+                })
+                .unwrap_or_default()
+        } else {
+            line
+        }
+    }
+
+    fn diff_options(&self) -> DiffOptions {
+        self.options.borrow().diff
+    }
+
+    /// hunks and lines of a diff ignoring whitespace
+    /// do not match the file and can not be applied
+    fn can_apply(&self) -> bool {
+        !self.is_immutable
+            && self.conflict.is_none()
+            && !self.diff_options().ignores_whitespace()
+    }
+
+    fn toggle_split(&self) {
+        let split = !self.options.borrow().diff_split;
+        self.options.borrow_mut().set_diff_split(split);
+    }
+
     fn modify_selection(
         &mut self,
         direction: Direction,
@@ -376,6 +938,8 @@ impl DiffComponent {
                                         .contains(line_cursor),
                                     hunk_selected,
                                     i == hunk_len as usize - 1,
+                                    self.lineno_width,
+                                    self.word_diffs.get(&line_cursor),
                                     &self.theme,
                                 );
                                 lines_added += 1;
@@ -392,6 +956,165 @@ impl DiffComponent {
         Ok(res)
     }
 
+    fn get_split_text(&self, width: u16, height: u16) -> Vec<Text> {
+        let mut res = Vec::new();
+        if let Some(diff) = &self.diff {
+            let lines: Vec<&DiffLine> = diff
+                .hunks
+                .iter()
+                .flat_map(|h| h.lines.iter())
+                .collect();
+            // hunk marker and separator take a column each
+            let side_width = (width as usize).saturating_sub(2) / 2;
+
+            let rows = self
+                .split_rows
+                .iter()
+                .enumerate()
+                .skip(self.scroll_top.get())
+                .take(height as usize);
+
+            for (i, row) in rows {
+                let selected_hunk =
+                    self.selected_hunk == Some(row.hunk);
+                let end_of_hunk = self
+                    .split_rows
+                    .get(i + 1)
+                    .map_or(true, |next| next.hunk != row.hunk);
+
+                let old = row.old.map(|idx| (idx, lines[idx]));
+                let new = row.new.map(|idx| (idx, lines[idx]));
+
+                if let Some((idx, line)) = old {
+                    if line.line_type == DiffLineType::Header {
+                        Self::add_line(
+                            &mut res,
+                            width,
+                            line,
+                            self.selection.contains(idx),
+                            selected_hunk,
+                            end_of_hunk,
+                            self.lineno_width,
+                            None,
+                            &self.theme,
+                        );
+                        continue;
+                    }
+                }
+
+                res.push(Self::hunk_marker(
+                    DiffLineType::None,
+                    selected_hunk,
+                    end_of_hunk,
+                    &self.theme,
+                ));
+                res.extend(self.split_side(old, true, side_width));
+                res.push(Text::Styled(
+                    Cow::from(symbols::line::VERTICAL),
+                    self.theme.diff_hunk_marker(false),
+                ));
+                res.extend(self.split_side(new, false, side_width));
+                res.push(Text::Raw(Cow::from("\n")));
+            }
+        }
+        res
+    }
+
+    /// one half of a row in split view, blank without a line
+    fn split_side(
+        &self,
+        line: Option<(usize, &DiffLine)>,
+        old_side: bool,
+        width: usize,
+    ) -> Vec<Text> {
+        let (idx, line) = if let Some(line) = line {
+            line
+        } else {
+            return vec![Text::Raw(Cow::from(" ".repeat(width)))];
+        };
+
+        let selected = self.selection.contains(idx);
+        let mut res = Vec::new();
+        let mut width = width;
+
+        if self.lineno_width > 0 {
+            let lineno = if old_side {
+                line.old_lineno
+            } else {
+                line.new_lineno
+            };
+            let number = format!(
+                "{} ",
+                lineno_text(lineno, self.lineno_width)
+            );
+
+            width = width.saturating_sub(number.len());
+            res.push(Text::Styled(
+                Cow::from(number),
+                self.theme.diff_line_number(selected),
+            ));
+        }
+
+        let mut spans =
+            content_spans(line, self.word_diffs.get(&idx), width);
+        let len: usize =
+            spans.iter().map(|s| s.content.chars().count()).sum();
+        spans.push(ContentSpan {
+            content: " ".repeat(width - len),
+            changed: false,
+            color: spans.last().and_then(|s| s.color),
+        });
+
+        res.extend(spans.iter().map(|span| {
+            Text::Styled(
+                Cow::from(span.content.clone()),
+                Self::span_style(
+                    &self.theme,
+                    line.line_type,
+                    span,
+                    selected,
+                ),
+            )
+        }));
+
+        res
+    }
+
+    fn hunk_marker(
+        line_type: DiffLineType,
+        selected_hunk: bool,
+        end_of_hunk: bool,
+        theme: &SharedTheme,
+    ) -> Text<'static> {
+        let style = theme.diff_hunk_marker(selected_hunk);
+
+        let marker = if end_of_hunk {
+            symbols::line::BOTTOM_LEFT
+        } else if line_type == DiffLineType::Header {
+            symbols::line::TOP_LEFT
+        } else {
+            symbols::line::VERTICAL
+        };
+
+        Text::Styled(Cow::from(marker), style)
+    }
+
+    fn span_style(
+        theme: &SharedTheme,
+        line_type: DiffLineType,
+        span: &ContentSpan,
+        selected: bool,
+    ) -> Style {
+        if span.changed {
+            theme.diff_word(line_type, selected)
+        } else if let Some(color) = span.color {
+            theme.diff_syntax(line_type, color, selected)
+        } else {
+            theme.diff_line(line_type, selected)
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn add_line(
         text: &mut Vec<Text>,
         width: u16,
@@ -399,47 +1122,65 @@ impl DiffComponent {
         selected: bool,
         selected_hunk: bool,
         end_of_hunk: bool,
+        lineno_width: usize,
+        words: Option<&WordSpans>,
         theme: &SharedTheme,
     ) {
-        {
-            let style = theme.diff_hunk_marker(selected_hunk);
+        text.push(Self::hunk_marker(
+            line.line_type,
+            selected_hunk,
+            end_of_hunk,
+            theme,
+        ));
 
-            if end_of_hunk {
-                text.push(Text::Styled(
-                    Cow::from(symbols::line::BOTTOM_LEFT),
-                    style,
-                ));
-            } else {
-                text.push(match line.line_type {
-                    DiffLineType::Header => Text::Styled(
-                        Cow::from(symbols::line::TOP_LEFT),
-                        style,
-                    ),
-                    _ => Text::Styled(
-                        Cow::from(symbols::line::VERTICAL),
-                        style,
-                    ),
-                });
-            }
+        let mut width = width as usize;
+
+        if lineno_width > 0 {
+            let numbers = format!(
+                "{} {} ",
+                lineno_text(line.old_lineno, lineno_width),
+                lineno_text(line.new_lineno, lineno_width)
+            );
+
+            width = width.saturating_sub(numbers.len());
+            text.push(Text::Styled(
+                Cow::from(numbers),
+                theme.diff_line_number(selected),
+            ));
         }
 
-        let trimmed =
-            line.content.trim_matches(|c| c == '\n' || c == '\r');
+        let mut spans = content_spans(line, words, usize::MAX);
 
-        let filled = if selected {
-            // selected line
-            format!("{:w$}\n", trimmed, w = width as usize)
-        } else {
+        // padding and line ending never count as changed
+        if spans.last().map_or(true, |s| s.changed) {
+            spans.push(ContentSpan {
+                content: String::new(),
+                changed: false,
+                color: None,
+            });
+        }
+
+        let len: usize =
+            spans.iter().map(|s| s.content.chars().count()).sum();
+        if let Some(last) = spans.last_mut() {
+            if selected {
+                // selected line
+                last.content
+                    .push_str(&" ".repeat(width.saturating_sub(len)));
+            }
             // weird eof missing eol line
-            format!("{}\n", trimmed)
-        };
-        //TODO: allow customize tabsize
-        let content = Cow::from(filled.replace("\t", "  "));
+            last.content.push('\n');
+        }
 
-        text.push(Text::Styled(
-            content,
-            theme.diff_line(line.line_type, selected),
-        ));
+        text.extend(spans.into_iter().map(|span| {
+            let style = Self::span_style(
+                theme,
+                line.line_type,
+                &span,
+                selected,
+            );
+            Text::Styled(Cow::from(span.content), style)
+        }));
     }
 
     const fn hunk_visible(
@@ -471,6 +1212,7 @@ impl DiffComponent {
                     CWD,
                     self.current.path.clone(),
                     hash,
+                    Some(self.diff_options()),
                 )?;
                 self.queue_update();
             }
@@ -487,7 +1229,12 @@ impl DiffComponent {
                     sync::stage_add_file(CWD, Path::new(&path))?;
                 } else {
                     let hash = diff.hunks[hunk].header_hash;
-                    sync::stage_hunk(CWD, path, hash)?;
+                    sync::stage_hunk(
+                        CWD,
+                        path,
+                        hash,
+                        Some(self.diff_options()),
+                    )?;
                 }
 
                 self.queue_update();
@@ -497,6 +1244,71 @@ impl DiffComponent {
         Ok(())
     }
 
+    /// positions of the selected lines in their hunks
+    fn selected_lines(&self) -> Vec<DiffLinePosition> {
+        self.diff.as_ref().map_or_else(Vec::new, |diff| {
+            diff.hunks
+                .iter()
+                .flat_map(|hunk| {
+                    (0..hunk.lines.len()).map(move |line| {
+                        DiffLinePosition {
+                            hunk: hunk.header_hash,
+                            line,
+                        }
+                    })
+                })
+                .enumerate()
+                .filter_map(|(i, position)| {
+                    if self.selection.contains(i) {
+                        Some(position)
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        })
+    }
+
+    fn can_change_lines(&self) -> bool {
+        self.can_apply()
+            && self
+                .diff
+                .as_ref()
+                .map_or(false, |diff| !diff.hunks.is_empty())
+    }
+
+    fn stage_lines(&mut self) {
+        let lines = self.selected_lines();
+        let path = self.current.path.as_str();
+
+        let options = Some(self.diff_options());
+
+        let res = if self.is_stage() {
+            sync::unstage_lines(CWD, path, &lines, options)
+        } else {
+            sync::stage_lines(CWD, path, &lines, options)
+        };
+
+        match res {
+            Ok(()) => self.queue_update(),
+            Err(e) => self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "stage lines error:\n{}",
+                    e
+                )),
+            ),
+        }
+    }
+
+    fn reset_lines(&self) {
+        self.queue.as_ref().borrow_mut().push_back(
+            InternalEvent::ConfirmAction(Action::ResetLines(
+                self.current.path.clone(),
+                self.selected_lines(),
+            )),
+        );
+    }
+
     fn queue_update(&mut self) {
         self.queue
             .as_ref()
@@ -550,12 +1362,16 @@ impl DrawableComponent for DiffComponent {
         self.scroll_top.set(calc_scroll_top(
             self.scroll_top.get(),
             self.current_size.get().1 as usize,
-            self.selection.get_end(),
+            self.selection_row(),
         ));
 
         let title = format!(
             "{}{}",
-            strings::title_diff(&self.key_config),
+            if self.conflict.is_some() {
+                strings::title_conflict(&self.key_config)
+            } else {
+                strings::title_diff(&self.key_config)
+            },
             self.current.path
         );
 
@@ -564,6 +1380,11 @@ impl DrawableComponent for DiffComponent {
                 Cow::from(strings::loading_text(&self.key_config)),
                 self.theme.text(false, false),
             )]
+        } else if self.is_split() {
+            self.get_split_text(
+                self.current_size.get().0,
+                self.current_size.get().1,
+            )
         } else {
             self.get_text(r.width, self.current_size.get().1)?
         };
@@ -584,7 +1405,7 @@ impl DrawableComponent for DiffComponent {
                 r,
                 &self.theme,
                 self.lines_count(),
-                self.selection.get_end(),
+                self.selection_row(),
             );
         }
 
@@ -593,6 +1414,7 @@ impl DrawableComponent for DiffComponent {
 }
 
 impl Component for DiffComponent {
+    #[allow(clippy::too_many_lines)]
     fn commands(
         &self,
         out: &mut Vec<CommandInfo>,
@@ -621,20 +1443,98 @@ impl Component for DiffComponent {
             .hidden(),
         );
 
-        if !self.is_immutable {
+        out.push(CommandInfo::new(
+            strings::commands::diff_toggle_split(&self.key_config),
+            self.conflict.is_none(),
+            self.focused,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::diff_options(&self.key_config),
+            self.conflict.is_none(),
+            self.focused,
+        ));
+
+        if self.conflict.is_some() {
+            let can_take = self.selected_conflict().is_some();
+            out.push(CommandInfo::new(
+                strings::commands::conflict_take_ours(
+                    &self.key_config,
+                ),
+                can_take,
+                self.focused,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::conflict_take_theirs(
+                    &self.key_config,
+                ),
+                can_take,
+                self.focused,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::conflict_take_both(
+                    &self.key_config,
+                ),
+                can_take,
+                self.focused,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::conflict_file_ours(
+                    &self.key_config,
+                ),
+                true,
+                self.focused,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::conflict_file_theirs(
+                    &self.key_config,
+                ),
+                true,
+                self.focused,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::conflict_file_both(
+                    &self.key_config,
+                ),
+                self.conflict
+                    .as_ref()
+                    .map_or(false, |c| !c.file.binary),
+                self.focused,
+            ));
+        } else if !self.is_immutable {
             out.push(CommandInfo::new(
                 strings::commands::diff_hunk_remove(&self.key_config),
-                self.selected_hunk.is_some(),
+                self.selected_hunk.is_some() && self.can_apply(),
                 self.focused && self.is_stage(),
             ));
             out.push(CommandInfo::new(
                 strings::commands::diff_hunk_add(&self.key_config),
-                self.selected_hunk.is_some(),
+                self.selected_hunk.is_some() && self.can_apply(),
                 self.focused && !self.is_stage(),
             ));
             out.push(CommandInfo::new(
                 strings::commands::diff_hunk_revert(&self.key_config),
-                self.selected_hunk.is_some(),
+                self.selected_hunk.is_some() && self.can_apply(),
+                self.focused && !self.is_stage(),
+            ));
+            out.push(CommandInfo::new(
+                if self.is_stage() {
+                    strings::commands::diff_lines_unstage(
+                        &self.key_config,
+                    )
+                } else {
+                    strings::commands::diff_lines_stage(
+                        &self.key_config,
+                    )
+                },
+                self.can_change_lines(),
+                self.focused,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::diff_lines_revert(
+                    &self.key_config,
+                ),
+                self.can_change_lines(),
                 self.focused && !self.is_stage(),
             ));
         }
@@ -645,6 +1545,10 @@ impl Component for DiffComponent {
     fn event(&mut self, ev: Event) -> Result<bool> {
         if self.focused {
             if let Event::Key(e) = ev {
+                if self.conflict.is_some() && self.conflict_event(e) {
+                    return Ok(true);
+                }
+
                 return if e == self.key_config.move_down {
                     self.move_selection(ScrollType::Down)?;
                     Ok(true)
@@ -670,7 +1574,7 @@ impl Component for DiffComponent {
                     self.move_selection(ScrollType::PageDown)?;
                     Ok(true)
                 } else if e == self.key_config.enter
-                    && !self.is_immutable
+                    && self.can_apply()
                 {
                     if self.current.is_stage {
                         self.unstage_hunk()?;
@@ -679,7 +1583,7 @@ impl Component for DiffComponent {
                     }
                     Ok(true)
                 } else if e == self.key_config.status_reset_item
-                    && !self.is_immutable
+                    && self.can_apply()
                     && !self.is_stage()
                 {
                     if let Some(diff) = &self.diff {
@@ -690,6 +1594,29 @@ impl Component for DiffComponent {
                         }
                     }
                     Ok(true)
+                } else if e == self.key_config.diff_stage_lines
+                    && self.can_change_lines()
+                {
+                    self.stage_lines();
+                    Ok(true)
+                } else if e == self.key_config.diff_reset_lines
+                    && self.can_change_lines()
+                    && !self.is_stage()
+                {
+                    self.reset_lines();
+                    Ok(true)
+                } else if e == self.key_config.diff_toggle_split
+                    && self.conflict.is_none()
+                {
+                    self.toggle_split();
+                    Ok(true)
+                } else if e == self.key_config.diff_options
+                    && self.conflict.is_none()
+                {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenDiffOptions);
+                    Ok(true)
                 } else if e == self.key_config.copy
                     && crate::clipboard::is_supported()
                 {
@@ -725,10 +1652,13 @@ mod tests {
             &DiffLine {
                 content: String::from("line 1\r\n"),
                 line_type: DiffLineType::None,
+                ..DiffLine::default()
             },
             false,
             false,
             false,
+            0,
+            None,
             &SharedTheme::default(),
         );
 
@@ -740,4 +1670,81 @@ mod tests {
             panic!("err")
         }
     }
+
+    #[test]
+    fn test_split_rows() {
+        let line = |line_type| DiffLine {
+            line_type,
+            ..DiffLine::default()
+        };
+        let diff = FileDiff {
+            hunks: vec![Hunk {
+                header_hash: 0,
+                lines: vec![
+                    line(DiffLineType::Header),
+                    line(DiffLineType::None),
+                    line(DiffLineType::Delete),
+                    line(DiffLineType::Delete),
+                    line(DiffLineType::Add),
+                    line(DiffLineType::None),
+                ],
+            }],
+            lines: 6,
+            ..FileDiff::default()
+        };
+
+        let rows: Vec<_> = split_rows(&diff)
+            .iter()
+            .map(|row| (row.old, row.new))
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                (Some(0), Some(0)),
+                (Some(1), Some(1)),
+                (Some(2), Some(4)),
+                (Some(3), None),
+                (Some(5), Some(5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_content_spans() {
+        let syntax =
+            |range, color| asyncgit::SyntaxSpan { range, color };
+        let line = DiffLine {
+            content: String::from("let a = 1;\n"),
+            line_type: DiffLineType::Add,
+            syntax: vec![
+                syntax(0..3, (1, 1, 1)),
+                syntax(3..11, (2, 2, 2)),
+            ],
+            ..DiffLine::default()
+        };
+        let words = vec![(0..8, false), (8..9, true), (9..10, false)];
+
+        let spans: Vec<_> = content_spans(&line, Some(&words), 9)
+            .into_iter()
+            .map(|s| (s.content, s.changed, s.color))
+            .collect();
+
+        assert_eq!(
+            spans,
+            vec![
+                (
+                    String::from("let"),
+                    false,
+                    Some(Color::Rgb(1, 1, 1))
+                ),
+                (
+                    String::from(" a = "),
+                    false,
+                    Some(Color::Rgb(2, 2, 2))
+                ),
+                (String::from("1"), true, Some(Color::Rgb(2, 2, 2))),
+            ]
+        );
+    }
 }
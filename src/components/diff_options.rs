@@ -0,0 +1,373 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    options::SharedOptions,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, ui,
+};
+use anyhow::Result;
+use asyncgit::sync::{
+    diff::DEFAULT_SIMILARITY, DiffAlgorithm, DiffOptions,
+};
+use crossterm::event::Event;
+use std::borrow::Cow;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Text},
+    Frame,
+};
+use ui::style::SharedTheme;
+
+/// rows of the popup in the order they are shown
+#[derive(Clone, Copy, PartialEq)]
+enum DiffOption {
+    IgnoreWhitespace,
+    IgnoreWhitespaceChange,
+    IgnoreWhitespaceEol,
+    Context,
+    Algorithm,
+    Renames,
+    Copies,
+}
+
+const ITEMS: [DiffOption; 7] = [
+    DiffOption::IgnoreWhitespace,
+    DiffOption::IgnoreWhitespaceChange,
+    DiffOption::IgnoreWhitespaceEol,
+    DiffOption::Context,
+    DiffOption::Algorithm,
+    DiffOption::Renames,
+    DiffOption::Copies,
+];
+
+const MAX_CONTEXT: u32 = 100;
+const THRESHOLD_STEP: u16 = 10;
+
+/// lets the user choose how diffs get computed,
+/// every change updates the shown diff right away
+pub struct DiffOptionsComponent {
+    /// thresholds kept while rename or copy detection is off
+    rename_threshold: u16,
+    copy_threshold: u16,
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    options: SharedOptions,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for DiffOptionsComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const SIZE: (u16, u16) = (64, 9);
+
+            let options = self.options.borrow().diff;
+            let txt = ITEMS
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| {
+                    Text::Styled(
+                        Cow::from(format!(
+                            "{}\n",
+                            self.item_text(&options, *item)
+                        )),
+                        self.theme.text(true, idx == self.selection),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let area =
+                ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(txt.iter())
+                    .block(
+                        Block::default()
+                            .title(&strings::diff_options_title(
+                                &self.key_config,
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .title_style(self.theme.title(true)),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for DiffOptionsComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::diff_options_toggle(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::diff_options_change(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup
+                    || e == self.key_config.diff_options
+                {
+                    self.hide();
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.move_down {
+                    self.selection =
+                        (self.selection + 1).min(ITEMS.len() - 1);
+                } else if e == self.key_config.enter {
+                    self.change(Change::Toggle);
+                } else if e == self.key_config.move_left {
+                    self.change(Change::Less);
+                } else if e == self.key_config.move_right {
+                    self.change(Change::More);
+                }
+
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Change {
+    Toggle,
+    Less,
+    More,
+}
+
+impl DiffOptionsComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        options: SharedOptions,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            rename_threshold: DEFAULT_SIMILARITY,
+            copy_threshold: DEFAULT_SIMILARITY,
+            selection: 0,
+            visible: false,
+            queue,
+            options,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self) -> Result<()> {
+        let options = self.options.borrow().diff;
+        if let Some(threshold) = options.rename_threshold {
+            self.rename_threshold = threshold;
+        }
+        if let Some(threshold) = options.copy_threshold {
+            self.copy_threshold = threshold;
+        }
+
+        self.show()
+    }
+
+    fn item_text(
+        &self,
+        options: &DiffOptions,
+        item: DiffOption,
+    ) -> String {
+        let flag = |set: bool, label: String| {
+            format!("[{}] {}", if set { "x" } else { " " }, label)
+        };
+        // aligned with the flags
+        let value = |label: String| format!("    {}", label);
+
+        match item {
+            DiffOption::IgnoreWhitespace => flag(
+                options.ignore_whitespace,
+                strings::diff_option_ignore_whitespace(
+                    &self.key_config,
+                ),
+            ),
+            DiffOption::IgnoreWhitespaceChange => flag(
+                options.ignore_whitespace_change,
+                strings::diff_option_ignore_whitespace_change(
+                    &self.key_config,
+                ),
+            ),
+            DiffOption::IgnoreWhitespaceEol => flag(
+                options.ignore_whitespace_eol,
+                strings::diff_option_ignore_whitespace_eol(
+                    &self.key_config,
+                ),
+            ),
+            DiffOption::Context => {
+                value(strings::diff_option_context(
+                    &self.key_config,
+                    options.context,
+                ))
+            }
+            DiffOption::Algorithm => {
+                value(strings::diff_option_algorithm(
+                    &self.key_config,
+                    options.algorithm,
+                ))
+            }
+            DiffOption::Renames => flag(
+                options.rename_threshold.is_some(),
+                strings::diff_option_renames(
+                    &self.key_config,
+                    self.rename_threshold,
+                ),
+            ),
+            DiffOption::Copies => flag(
+                options.copy_threshold.is_some(),
+                strings::diff_option_copies(
+                    &self.key_config,
+                    self.copy_threshold,
+                ),
+            ),
+        }
+    }
+
+    fn change(&mut self, change: Change) {
+        let mut options = self.options.borrow().diff;
+        let toggle = change == Change::Toggle;
+
+        match ITEMS[self.selection] {
+            DiffOption::IgnoreWhitespace => {
+                options.ignore_whitespace ^= toggle
+            }
+            DiffOption::IgnoreWhitespaceChange => {
+                options.ignore_whitespace_change ^= toggle
+            }
+            DiffOption::IgnoreWhitespaceEol => {
+                options.ignore_whitespace_eol ^= toggle
+            }
+            DiffOption::Context => {
+                options.context = match change {
+                    Change::Less => options.context.saturating_sub(1),
+                    Change::Toggle | Change::More => {
+                        (options.context + 1).min(MAX_CONTEXT)
+                    }
+                }
+            }
+            DiffOption::Algorithm => {
+                options.algorithm = match options.algorithm {
+                    DiffAlgorithm::Myers => DiffAlgorithm::Minimal,
+                    DiffAlgorithm::Minimal => DiffAlgorithm::Patience,
+                    DiffAlgorithm::Patience => DiffAlgorithm::Myers,
+                }
+            }
+            DiffOption::Renames => {
+                options.rename_threshold = Self::change_threshold(
+                    options.rename_threshold,
+                    &mut self.rename_threshold,
+                    change,
+                )
+            }
+            DiffOption::Copies => {
+                options.copy_threshold = Self::change_threshold(
+                    options.copy_threshold,
+                    &mut self.copy_threshold,
+                    change,
+                )
+            }
+        }
+
+        self.apply(options);
+    }
+
+    /// toggles detection on and off or changes the `threshold`
+    /// it uses, the result is the new setting
+    fn change_threshold(
+        current: Option<u16>,
+        threshold: &mut u16,
+        change: Change,
+    ) -> Option<u16> {
+        match change {
+            Change::Toggle => {
+                if current.is_some() {
+                    None
+                } else {
+                    Some(*threshold)
+                }
+            }
+            Change::Less => {
+                *threshold =
+                    threshold.saturating_sub(THRESHOLD_STEP).max(10);
+                current.map(|_| *threshold)
+            }
+            Change::More => {
+                *threshold = (*threshold + THRESHOLD_STEP).min(100);
+                current.map(|_| *threshold)
+            }
+        }
+    }
+
+    fn apply(&self, options: DiffOptions) {
+        if self.options.borrow().diff == options {
+            return;
+        }
+
+        self.options.borrow_mut().diff = options;
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::DIFF));
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    components::{
+        visibility_blocking, CommandBlocking, CommandInfo, Component,
+        DrawableComponent,
+    },
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{AsyncFetch, AsyncNotification, FetchProgress};
+use bytesize::ByteSize;
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use std::time::{Duration, Instant};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    widgets::{Block, BorderType, Borders, Clear, Gauge},
+    Frame,
+};
+
+/// fetches remotes and shows the transfer progress.
+/// optionally fetches all remotes silently in the background
+/// every `auto_fetch_interval`
+pub struct FetchComponent {
+    visible: bool,
+    git_fetch: AsyncFetch,
+    progress: Option<FetchProgress>,
+    background: bool,
+    auto_fetch_interval: Option<Duration>,
+    last_fetch: Instant,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for FetchComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let (label, percent) =
+                self.progress.as_ref().map_or_else(
+                    || (String::new(), 0),
+                    |p| {
+                        (
+                            format!(
+                                "{}: {}% ({}/{} objects, {})",
+                                p.remote,
+                                p.progress.percent(),
+                                p.progress.received_objects,
+                                p.progress.total_objects,
+                                ByteSize::b(
+                                    p.progress.received_bytes as u64
+                                )
+                            ),
+                            p.progress.percent(),
+                        )
+                    },
+                );
+
+            let area = ui::centered_rect_absolute(50, 3, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Gauge::default()
+                    .label(label.as_str())
+                    .block(
+                        Block::default()
+                            .title(&strings::fetch_popup_title(
+                                &self.key_config,
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .title_style(self.theme.title(true))
+                            .border_style(self.theme.block(true)),
+                    )
+                    .style(self.theme.gauge())
+                    .percent(u16::from(percent)),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for FetchComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl FetchComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        auto_fetch_interval: Option<Duration>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            visible: false,
+            git_fetch: AsyncFetch::new(sender),
+            progress: None,
+            background: false,
+            auto_fetch_interval,
+            last_fetch: Instant::now(),
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    /// fetches all remotes showing the progress
+    pub fn fetch(&mut self) -> Result<()> {
+        if self.git_fetch.is_pending() {
+            // a running background fetch now reports its result
+            self.background = false;
+        } else {
+            self.start(false)?;
+        }
+
+        self.show()?;
+
+        Ok(())
+    }
+
+    /// starts a background fetch once the auto fetch interval elapsed
+    pub fn tick(&mut self) -> Result<()> {
+        if let Some(interval) = self.auto_fetch_interval {
+            if !self.git_fetch.is_pending()
+                && self.last_fetch.elapsed() >= interval
+            {
+                self.start(true)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start(&mut self, background: bool) -> Result<()> {
+        self.background = background;
+        self.last_fetch = Instant::now();
+        self.progress = None;
+        self.git_fetch.request(None)?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_fetch.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if let AsyncNotification::Fetch = ev {
+            self.progress = self.git_fetch.progress()?;
+
+            if !self.git_fetch.is_pending() {
+                self.hide();
+
+                match self.git_fetch.last_error()? {
+                    Some(err) if !self.background => {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "fetch failed:\n{}",
+                                err
+                            )),
+                        );
+                    }
+                    Some(err) => {
+                        log::error!(
+                            "background fetch failed: {}",
+                            err
+                        );
+                    }
+                    None => {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::Update(NeedsUpdate::ALL),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
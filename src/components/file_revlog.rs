@@ -0,0 +1,333 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, CommitList,
+    Component, DiffComponent, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    options::SharedOptions,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId},
+    AsyncDiff, AsyncLog, AsyncNotification, DiffParams, DiffType,
+    FetchStatus, CWD,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::Clear,
+    Frame,
+};
+
+const SLICE_SIZE: usize = 1200;
+
+/// log of the commits changing a single file next to the diff of
+/// that file in the selected commit
+pub struct FileRevlogComponent {
+    file_path: Option<String>,
+    git_log: Option<AsyncLog>,
+    git_diff: AsyncDiff,
+    list: CommitList,
+    diff: DiffComponent,
+    visible: bool,
+    queue: Queue,
+    sender: Sender<AsyncNotification>,
+    options: SharedOptions,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl FileRevlogComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        options: SharedOptions,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            file_path: None,
+            git_log: None,
+            git_diff: AsyncDiff::new(sender.clone()),
+            list: CommitList::new(
+                &strings::file_log_title(&key_config, ""),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            diff: DiffComponent::new(
+                queue.clone(),
+                options.clone(),
+                theme.clone(),
+                key_config.clone(),
+                true,
+            ),
+            visible: false,
+            queue: queue.clone(),
+            sender: sender.clone(),
+            options,
+            theme,
+            key_config,
+        }
+    }
+
+    /// shows the history of `file_path`
+    pub fn open(&mut self, file_path: &str) -> Result<()> {
+        self.file_path = Some(file_path.to_string());
+        self.git_log =
+            Some(AsyncLog::with_file(&self.sender, file_path));
+        self.list = CommitList::new(
+            &strings::file_log_title(&self.key_config, file_path),
+            self.theme.clone(),
+            self.key_config.clone(),
+        );
+        self.diff.focus(false);
+        self.show()?;
+        self.update()
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_diff.is_pending()
+            || self
+                .git_log
+                .as_ref()
+                .map_or(false, AsyncLog::is_pending)
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.visible {
+            match ev {
+                AsyncNotification::Log => self.update()?,
+                AsyncNotification::Diff => self.update_diff()?,
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    pub fn update(&mut self) -> Result<()> {
+        if self.visible {
+            if let Some(git_log) = &mut self.git_log {
+                let log_changed =
+                    git_log.fetch()? == FetchStatus::Started;
+
+                self.list.set_count_total(git_log.count()?);
+
+                let selection = self.list.selection();
+                let selection_max = self.list.selection_max();
+                if self
+                    .list
+                    .items()
+                    .needs_data(selection, selection_max)
+                    || log_changed
+                {
+                    self.fetch_commits()?;
+                }
+            }
+
+            self.update_diff()?;
+        }
+
+        Ok(())
+    }
+
+    fn fetch_commits(&mut self) -> Result<()> {
+        if let Some(git_log) = &self.git_log {
+            let want_min =
+                self.list.selection().saturating_sub(SLICE_SIZE / 2);
+
+            let commits = sync::get_commits_info(
+                CWD,
+                &git_log.get_slice(want_min, SLICE_SIZE)?,
+                self.list.current_size().0.into(),
+            );
+
+            if let Ok(commits) = commits {
+                self.list.items().set_items(want_min, commits);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// diff of the file as it was named in the selected commit
+    pub fn update_diff(&mut self) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let path = match &self.git_log {
+            Some(git_log) => {
+                git_log.file_path_at(self.list.selection())?
+            }
+            None => None,
+        };
+
+        if let (Some(id), Some(path)) = (self.selected_commit(), path)
+        {
+            let diff_params = DiffParams {
+                path: path.clone(),
+                diff_type: DiffType::Commit(id),
+                options: self.options.borrow().diff,
+            };
+
+            if let Some((params, last)) = self.git_diff.last()? {
+                if params == diff_params {
+                    self.diff.update(path, false, last)?;
+                    return Ok(());
+                }
+            }
+
+            self.git_diff.request(diff_params)?;
+            self.diff.clear(true)?;
+        } else {
+            self.diff.clear(false)?;
+        }
+
+        Ok(())
+    }
+
+    fn selected_commit(&self) -> Option<CommitId> {
+        self.list.selected_entry().map(|e| e.id)
+    }
+}
+
+impl DrawableComponent for FileRevlogComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let percentages = if self.diff.focused() {
+                (30, 70)
+            } else {
+                (50, 50)
+            };
+
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Percentage(percentages.0),
+                        Constraint::Percentage(percentages.1),
+                    ]
+                    .as_ref(),
+                )
+                .split(rect);
+
+            f.render_widget(Clear, rect);
+
+            self.list.draw(f, chunks[0])?;
+            self.diff.draw(f, chunks[1])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for FileRevlogComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            if self.diff.focused() {
+                self.diff.commands(out, force_all);
+            } else {
+                self.list.commands(out, force_all);
+            }
+
+            let selection_valid = self.selected_commit().is_some();
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::close_popup(&self.key_config),
+                    true,
+                    true,
+                )
+                .order(1),
+            );
+            out.push(CommandInfo::new(
+                strings::commands::log_details_open(&self.key_config),
+                selection_valid,
+                !self.diff.focused() || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_right(&self.key_config),
+                selection_valid,
+                !self.diff.focused() || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_left(&self.key_config),
+                true,
+                self.diff.focused() || force_all,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if self.diff.focused() {
+                if self.diff.event(ev)? {
+                    return Ok(true);
+                }
+            } else if self.list.event(ev)? {
+                self.update()?;
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.enter {
+                    if let Some(id) = self.selected_commit() {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::InspectCommit(id, None),
+                        );
+                    }
+                } else if e == self.key_config.focus_right
+                    && self.selected_commit().is_some()
+                {
+                    self.diff.focus(true);
+                } else if e == self.key_config.focus_left {
+                    self.diff.focus(false);
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.git_log = None;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
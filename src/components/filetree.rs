@@ -14,7 +14,7 @@ use crate::{
     ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::{hash, StatusItem, StatusItemType};
+use asyncgit::{hash, sync::CommitId, StatusItem, StatusItemType};
 use crossterm::event::Event;
 use std::{borrow::Cow, cell::Cell, convert::From, path::Path};
 use tui::{backend::Backend, layout::Rect, widgets::Text, Frame};
@@ -27,6 +27,7 @@ pub struct FileTreeComponent {
     current_hash: u64,
     focused: bool,
     show_selection: bool,
+    commit: Option<CommitId>,
     queue: Option<Queue>,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
@@ -48,6 +49,7 @@ impl FileTreeComponent {
             current_hash: 0,
             focused: focus,
             show_selection: focus,
+            commit: None,
             queue,
             theme,
             key_config,
@@ -99,6 +101,11 @@ impl FileTreeComponent {
         self.tree.tree.file_count()
     }
 
+    /// commit the files belong to, `None` means the workdir
+    pub fn set_commit(&mut self, commit: Option<CommitId>) {
+        self.commit = commit;
+    }
+
     ///
     pub fn set_title(&mut self, title: String) {
         self.title = title;
@@ -121,6 +128,30 @@ impl FileTreeComponent {
         })
     }
 
+    fn open_blame(&self) -> bool {
+        match (&self.queue, self.selection_file()) {
+            (Some(queue), Some(file)) => {
+                queue.borrow_mut().push_back(
+                    InternalEvent::BlameFile(file.path, self.commit),
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn open_file_history(&self) -> bool {
+        match (&self.queue, self.selection_file()) {
+            (Some(queue), Some(file)) => {
+                queue.borrow_mut().push_back(
+                    InternalEvent::OpenFileRevlog(file.path),
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+
     fn move_selection(&mut self, dir: MoveSelection) -> bool {
         let changed = self.tree.move_selection(dir);
 
@@ -212,6 +243,7 @@ impl FileTreeComponent {
             StatusItemType::Deleted => '-',
             StatusItemType::Renamed => 'R',
             StatusItemType::Typechange => ' ',
+            StatusItemType::Conflicted => '!',
         }
     }
 }
@@ -317,6 +349,19 @@ impl Component for FileTreeComponent {
             .order(order::NAV),
         );
 
+        if self.queue.is_some() {
+            out.push(CommandInfo::new(
+                strings::commands::blame_file(&self.key_config),
+                self.is_file_seleted(),
+                self.focused || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::file_history(&self.key_config),
+                self.is_file_seleted(),
+                self.focused || force_all,
+            ));
+        }
+
         CommandBlocking::PassingOn
     }
 
@@ -339,6 +384,10 @@ impl Component for FileTreeComponent {
                     Ok(self.move_selection(MoveSelection::Left))
                 } else if e == self.key_config.move_right {
                     Ok(self.move_selection(MoveSelection::Right))
+                } else if e == self.key_config.blame {
+                    Ok(self.open_blame())
+                } else if e == self.key_config.file_history {
+                    Ok(self.open_file_history())
                 } else {
                     Ok(false)
                 };
@@ -4,7 +4,11 @@ use super::{
     DrawableComponent,
 };
 use crate::{
-    accessors, keys::SharedKeyConfig, queue::Queue, strings,
+    accessors,
+    keys::SharedKeyConfig,
+    options::SharedOptions,
+    queue::{InternalEvent, Queue},
+    strings,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
@@ -28,6 +32,8 @@ pub struct InspectCommitComponent {
     details: CommitDetailsComponent,
     git_diff: AsyncDiff,
     visible: bool,
+    queue: Queue,
+    options: SharedOptions,
     key_config: SharedKeyConfig,
 }
 
@@ -98,6 +104,18 @@ impl Component for InspectCommitComponent {
                 true,
                 self.diff.focused() || force_all,
             ));
+
+            out.push(CommandInfo::new(
+                strings::commands::log_cherry_pick(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::log_revert(&self.key_config),
+                true,
+                true,
+            ));
         }
 
         visibility_blocking(self)
@@ -122,6 +140,10 @@ impl Component for InspectCommitComponent {
                 {
                     self.details.focus(true);
                     self.diff.focus(false);
+                } else if e == self.key_config.log_cherry_pick {
+                    self.apply_commit(false);
+                } else if e == self.key_config.log_revert {
+                    self.apply_commit(true);
                 }
 
                 // stop key event propagation
@@ -155,6 +177,7 @@ impl InspectCommitComponent {
     pub fn new(
         queue: &Queue,
         sender: &Sender<AsyncNotification>,
+        options: SharedOptions,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
@@ -167,6 +190,7 @@ impl InspectCommitComponent {
             ),
             diff: DiffComponent::new(
                 queue.clone(),
+                options.clone(),
                 theme,
                 key_config.clone(),
                 true,
@@ -175,6 +199,8 @@ impl InspectCommitComponent {
             tags: None,
             git_diff: AsyncDiff::new(sender.clone()),
             visible: false,
+            queue: queue.clone(),
+            options,
             key_config,
         }
     }
@@ -203,7 +229,11 @@ impl InspectCommitComponent {
         ev: AsyncNotification,
     ) -> Result<()> {
         if self.is_visible() {
-            if let AsyncNotification::CommitFiles = ev {
+            if matches!(
+                ev,
+                AsyncNotification::CommitFiles
+                    | AsyncNotification::Signature
+            ) {
                 self.update()?
             } else if let AsyncNotification::Diff = ev {
                 self.update_diff()?
@@ -222,6 +252,7 @@ impl InspectCommitComponent {
                     let diff_params = DiffParams {
                         path: f.path.clone(),
                         diff_type: DiffType::Commit(id),
+                        options: self.options.borrow().diff,
                     };
 
                     if let Some((params, last)) =
@@ -252,6 +283,17 @@ impl InspectCommitComponent {
         Ok(())
     }
 
+    fn apply_commit(&mut self, revert: bool) {
+        if let Some(id) = self.commit_id {
+            self.queue.borrow_mut().push_back(if revert {
+                InternalEvent::Revert(vec![id])
+            } else {
+                InternalEvent::CherryPick(vec![id])
+            });
+            self.hide();
+        }
+    }
+
     fn can_focus_diff(&self) -> bool {
         self.details.files().selection_file().is_some()
     }
@@ -0,0 +1,318 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings, ui,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{LogOptions, LogScope, LogSort},
+    CWD,
+};
+use crossterm::event::Event;
+use std::borrow::Cow;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Text},
+    Frame,
+};
+use ui::style::SharedTheme;
+
+/// rows of the popup in the order they are shown
+#[derive(Clone, Copy, PartialEq)]
+enum LogOption {
+    Head,
+    All,
+    Revision,
+    FirstParent,
+    NoMerges,
+    TopoOrder,
+}
+
+const ITEMS: [LogOption; 6] = [
+    LogOption::Head,
+    LogOption::All,
+    LogOption::Revision,
+    LogOption::FirstParent,
+    LogOption::NoMerges,
+    LogOption::TopoOrder,
+];
+
+/// lets the user choose the commits and order of the log,
+/// every change restarts the log right away
+pub struct LogOptionsComponent {
+    options: LogOptions,
+    /// last revision entered, kept while another scope is chosen
+    revision: String,
+    input: TextInputComponent,
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for LogOptionsComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const SIZE: (u16, u16) = (60, 8);
+
+            let txt = ITEMS
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| {
+                    Text::Styled(
+                        Cow::from(format!(
+                            "{}\n",
+                            self.item_text(*item)
+                        )),
+                        self.theme.text(true, idx == self.selection),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let area =
+                ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(txt.iter())
+                    .block(
+                        Block::default()
+                            .title(&strings::log_options_title(
+                                &self.key_config,
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .title_style(self.theme.title(true)),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+
+            self.input.draw(f, area)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for LogOptionsComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.input.is_visible() {
+            self.input.commands(out, force_all);
+        } else if self.visible || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::log_options_toggle(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if self.input.is_visible() {
+                if let Event::Key(e) = ev {
+                    if e == self.key_config.enter {
+                        self.confirm_revision();
+                        return Ok(true);
+                    }
+                }
+
+                self.input.event(ev)?;
+
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup
+                    || e == self.key_config.log_options
+                {
+                    self.hide();
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.move_down {
+                    self.selection =
+                        (self.selection + 1).min(ITEMS.len() - 1);
+                } else if e == self.key_config.enter {
+                    self.toggle()?;
+                }
+
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl LogOptionsComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            options: LogOptions::default(),
+            revision: String::new(),
+            input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::log_revision_popup_title(&key_config),
+                &strings::log_revision_popup_msg(&key_config),
+            ),
+            selection: 0,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, options: LogOptions) -> Result<()> {
+        if let LogScope::Revision(revision) = &options.scope {
+            self.revision = revision.clone();
+        }
+        self.options = options;
+        self.show()
+    }
+
+    fn item_text(&self, item: LogOption) -> String {
+        let scope = |scope: LogScope| {
+            format!(
+                "({}) {}",
+                if self.options.scope == scope {
+                    "x"
+                } else {
+                    " "
+                },
+                strings::log_scope(&self.key_config, &scope)
+            )
+        };
+        let flag = |set: bool, label: String| {
+            format!("[{}] {}", if set { "x" } else { " " }, label)
+        };
+
+        match item {
+            LogOption::Head => scope(LogScope::Head),
+            LogOption::All => scope(LogScope::All),
+            LogOption::Revision => {
+                scope(LogScope::Revision(self.revision.clone()))
+            }
+            LogOption::FirstParent => flag(
+                self.options.first_parent,
+                strings::log_option_first_parent(&self.key_config),
+            ),
+            LogOption::NoMerges => flag(
+                self.options.no_merges,
+                strings::log_option_no_merges(&self.key_config),
+            ),
+            LogOption::TopoOrder => flag(
+                self.options.sort == LogSort::Topological,
+                strings::log_option_topo_order(&self.key_config),
+            ),
+        }
+    }
+
+    fn toggle(&mut self) -> Result<()> {
+        match ITEMS[self.selection] {
+            LogOption::Head => self.options.scope = LogScope::Head,
+            LogOption::All => self.options.scope = LogScope::All,
+            LogOption::Revision => {
+                self.input.set_text(self.revision.clone());
+                return self.input.show();
+            }
+            LogOption::FirstParent => {
+                self.options.first_parent = !self.options.first_parent
+            }
+            LogOption::NoMerges => {
+                self.options.no_merges = !self.options.no_merges
+            }
+            LogOption::TopoOrder => {
+                self.options.sort = match self.options.sort {
+                    LogSort::Date => LogSort::Topological,
+                    LogSort::Topological => LogSort::Date,
+                }
+            }
+        }
+
+        self.apply();
+
+        Ok(())
+    }
+
+    /// switches to the entered revision if it resolves
+    fn confirm_revision(&mut self) {
+        let revision = self.input.get_text().trim().to_string();
+        let options = LogOptions {
+            scope: LogScope::Revision(revision.clone()),
+            ..self.options.clone()
+        };
+
+        match options.validate(CWD) {
+            Ok(()) => {
+                self.input.hide();
+                self.revision = revision;
+                self.options = options;
+                self.apply();
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "log revision error:\n{}",
+                        e,
+                    )),
+                );
+            }
+        }
+    }
+
+    fn apply(&self) {
+        self.queue.borrow_mut().push_back(
+            InternalEvent::SetLogOptions(self.options.clone()),
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod blame_file;
 mod changes;
 mod command;
 mod commit;
@@ -5,12 +6,24 @@ mod commit_details;
 mod commitlist;
 mod create_branch;
 mod diff;
+mod diff_options;
 mod externaleditor;
+mod fetch;
+mod file_revlog;
 mod filetree;
 mod help;
 mod inspect_commit;
+mod log_options;
+mod log_search;
 mod msg;
+mod pickaxe;
+mod rebase_plan;
+mod rename_branch;
+mod reflog;
+mod remote_job;
 mod reset;
+mod reset_head;
+mod set_upstream;
 mod stashmsg;
 mod tag_commit;
 mod textinput;
@@ -19,6 +32,7 @@ mod utils;
 use anyhow::Result;
 use crossterm::event::Event;
 
+pub use blame_file::BlameFileComponent;
 pub use changes::ChangesComponent;
 pub use command::{CommandInfo, CommandText};
 pub use commit::CommitComponent;
@@ -26,12 +40,24 @@ pub use commit_details::CommitDetailsComponent;
 pub use commitlist::CommitList;
 pub use create_branch::CreateBranchComponent;
 pub use diff::DiffComponent;
+pub use diff_options::DiffOptionsComponent;
 pub use externaleditor::ExternalEditorComponent;
+pub use fetch::FetchComponent;
+pub use file_revlog::FileRevlogComponent;
 pub use filetree::FileTreeComponent;
 pub use help::HelpComponent;
 pub use inspect_commit::InspectCommitComponent;
+pub use log_options::LogOptionsComponent;
+pub use log_search::LogSearchComponent;
 pub use msg::MsgComponent;
+pub use pickaxe::PickaxeComponent;
+pub use rebase_plan::RebasePlanComponent;
+pub use rename_branch::RenameBranchComponent;
+pub use reflog::ReflogComponent;
+pub use remote_job::{PullComponent, PushComponent};
 pub use reset::ResetComponent;
+pub use reset_head::ResetHeadComponent;
+pub use set_upstream::SetUpstreamComponent;
 pub use stashmsg::StashMsgComponent;
 pub use tag_commit::TagCommitComponent;
 pub use textinput::TextInputComponent;
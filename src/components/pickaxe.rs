@@ -0,0 +1,302 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, CommitList, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, Pickaxe},
+    AsyncLog, AsyncNotification, FetchStatus, CWD,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, widgets::Clear, Frame};
+
+const SLICE_SIZE: usize = 1200;
+
+/// commits whose diff adds or removes a string, like `git log -S`,
+/// or lines matching a regex, like `git log -G`
+pub struct PickaxeComponent {
+    query: String,
+    input: TextInputComponent,
+    git_log: Option<AsyncLog>,
+    list: CommitList,
+    visible: bool,
+    queue: Queue,
+    sender: Sender<AsyncNotification>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl PickaxeComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            query: String::new(),
+            input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::pickaxe_popup_title(&key_config),
+                &strings::pickaxe_popup_msg(&key_config),
+            ),
+            git_log: None,
+            list: CommitList::new(
+                &strings::pickaxe_title(&key_config, "", false),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            visible: false,
+            queue: queue.clone(),
+            sender: sender.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    /// opens the prompt for the string to find
+    pub fn open(&mut self) -> Result<()> {
+        self.input.set_text(self.query.clone());
+        self.input.show()?;
+        self.show()
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_log.as_ref().map_or(false, AsyncLog::is_pending)
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.visible && ev == AsyncNotification::Log {
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    pub fn update(&mut self) -> Result<()> {
+        if self.visible {
+            if let Some(git_log) = &mut self.git_log {
+                let log_changed =
+                    git_log.fetch()? == FetchStatus::Started;
+
+                self.list.set_count_total(git_log.count()?);
+                self.list.set_title(strings::pickaxe_title(
+                    &self.key_config,
+                    &self.query,
+                    git_log.is_pending(),
+                ));
+
+                let selection = self.list.selection();
+                let selection_max = self.list.selection_max();
+                if self
+                    .list
+                    .items()
+                    .needs_data(selection, selection_max)
+                    || log_changed
+                {
+                    self.fetch_commits()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// starts walking the history for the entered query,
+    /// dropping a previous walk stops it
+    fn find(&mut self) -> Result<()> {
+        let query = self.input.get_text().clone();
+
+        match Pickaxe::parse(&query) {
+            Ok(pickaxe) => {
+                self.input.hide();
+                self.query = query;
+                self.git_log = Some(AsyncLog::with_pickaxe(
+                    &self.sender,
+                    pickaxe,
+                ));
+                self.list = CommitList::new(
+                    &strings::pickaxe_title(
+                        &self.key_config,
+                        &self.query,
+                        true,
+                    ),
+                    self.theme.clone(),
+                    self.key_config.clone(),
+                );
+                self.update()?;
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "find content error:\n{}",
+                        e,
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fetch_commits(&mut self) -> Result<()> {
+        if let Some(git_log) = &self.git_log {
+            let want_min =
+                self.list.selection().saturating_sub(SLICE_SIZE / 2);
+
+            let commits = sync::get_commits_info(
+                CWD,
+                &git_log.get_slice(want_min, SLICE_SIZE)?,
+                self.list.current_size().0.into(),
+            );
+
+            if let Ok(commits) = commits {
+                self.list.items().set_items(want_min, commits);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn selected_commit(&self) -> Option<CommitId> {
+        self.list.selected_entry().map(|e| e.id)
+    }
+}
+
+impl DrawableComponent for PickaxeComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            f.render_widget(Clear, rect);
+
+            self.list.draw(f, rect)?;
+            self.input.draw(f, rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for PickaxeComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.input.is_visible() {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::pickaxe_confirm(&self.key_config),
+                true,
+                true,
+            ));
+        } else if self.visible || force_all {
+            self.list.commands(out, force_all);
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::close_popup(&self.key_config),
+                    true,
+                    true,
+                )
+                .order(1),
+            );
+            out.push(CommandInfo::new(
+                strings::commands::log_details_open(&self.key_config),
+                self.selected_commit().is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::log_pickaxe(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if self.input.is_visible() {
+                if let Event::Key(e) = ev {
+                    if e == self.key_config.enter {
+                        self.find()?;
+                        return Ok(true);
+                    }
+                }
+
+                self.input.event(ev)?;
+
+                // canceling the first prompt closes the popup
+                if !self.input.is_visible() && self.git_log.is_none()
+                {
+                    self.hide();
+                }
+
+                return Ok(true);
+            }
+
+            if self.list.event(ev)? {
+                self.update()?;
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.enter
+                    || e == self.key_config.focus_right
+                {
+                    if let Some(id) = self.selected_commit() {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::InspectCommit(id, None),
+                        );
+                    }
+                } else if e == self.key_config.log_pickaxe {
+                    self.open()?;
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.input.hide();
+        self.git_log = None;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,400 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, ui,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{
+        self, CommitId, RebaseAction, RebaseEntry, RebaseProgress,
+    },
+    CWD,
+};
+use crossterm::event::{Event, KeyEvent};
+use std::{borrow::Cow, cell::Cell, cmp, fmt::Display};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Text},
+    Frame,
+};
+use ui::style::SharedTheme;
+
+/// lets the user edit the plan of an interactive rebase
+pub struct RebasePlanComponent {
+    base: Option<CommitId>,
+    entries: Vec<RebaseEntry>,
+    selection: usize,
+    visible: bool,
+    reword: TextInputComponent,
+    current_height: Cell<usize>,
+    scroll_top: Cell<usize>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for RebasePlanComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let area = ui::centered_rect(80, 60, rect);
+            let height = area.height.saturating_sub(2) as usize;
+            self.current_height.set(height);
+
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            let base: String = self
+                .base
+                .map(|id| id.to_string().chars().take(7).collect())
+                .unwrap_or_default();
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height).iter())
+                    .block(
+                        Block::default()
+                            .title(&strings::rebase_plan_title(
+                                &self.key_config,
+                                &base,
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .title_style(self.theme.title(true)),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+
+            self.reword.draw(f, rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for RebasePlanComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.reword.is_visible() {
+            self.reword.commands(out, force_all);
+        } else if self.visible || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_set_action(
+                    &self.key_config,
+                ),
+                !self.entries.is_empty(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_move(&self.key_config),
+                self.entries.len() > 1,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_start(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if self.reword.is_visible() {
+                if let Event::Key(e) = ev {
+                    if e == self.key_config.enter {
+                        self.set_reword_message();
+                        return Ok(true);
+                    }
+                }
+
+                return self.reword.event(ev);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.enter {
+                    self.start();
+                } else if e == self.key_config.move_up {
+                    self.move_selection(ScrollType::Up);
+                } else if e == self.key_config.move_down {
+                    self.move_selection(ScrollType::Down);
+                } else if e == self.key_config.page_up {
+                    self.move_selection(ScrollType::PageUp);
+                } else if e == self.key_config.page_down {
+                    self.move_selection(ScrollType::PageDown);
+                } else if e == self.key_config.home {
+                    self.move_selection(ScrollType::Home);
+                } else if e == self.key_config.end {
+                    self.move_selection(ScrollType::End);
+                } else if e == self.key_config.shift_up {
+                    self.move_entry(true);
+                } else if e == self.key_config.shift_down {
+                    self.move_entry(false);
+                } else if e == self.key_config.rebase_reword {
+                    self.open_reword()?;
+                } else if let Some(action) = self.action_for_key(e) {
+                    self.set_action(action);
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.reword.hide();
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl RebasePlanComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            base: None,
+            entries: Vec::new(),
+            selection: 0,
+            visible: false,
+            reword: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::rebase_reword_popup_title(&key_config),
+                &strings::rebase_reword_popup_msg(&key_config),
+            ),
+            current_height: Cell::new(0),
+            scroll_top: Cell::new(0),
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// opens the plan for rebasing HEAD onto `base`
+    pub fn open(&mut self, base: CommitId) -> Result<()> {
+        let entries = sync::get_rebase_plan(CWD, base)?;
+
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        self.base = Some(base);
+        self.entries = entries;
+        self.selection = 0;
+        self.scroll_top.set(0);
+        self.show()
+    }
+
+    /// reports the outcome of a rebase step via `queue`,
+    /// stops get resolved in the status tab
+    pub fn handle_progress<E: Display>(
+        queue: &Queue,
+        res: std::result::Result<RebaseProgress, E>,
+    ) {
+        match res {
+            Ok(RebaseProgress::Done) => {}
+            Ok(RebaseProgress::Conflict | RebaseProgress::Edit) => {
+                queue
+                    .borrow_mut()
+                    .push_back(InternalEvent::TabSwitch);
+            }
+            Err(e) => {
+                queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "rebase error:\n{}",
+                        e,
+                    )),
+                );
+            }
+        }
+
+        queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+    }
+
+    fn start(&mut self) {
+        if let Some(base) = self.base.take() {
+            Self::handle_progress(
+                &self.queue,
+                sync::rebase_interactive(CWD, base, &self.entries),
+            );
+        }
+
+        self.entries.clear();
+        self.hide();
+    }
+
+    fn action_for_key(&self, e: KeyEvent) -> Option<RebaseAction> {
+        if e == self.key_config.rebase_pick {
+            Some(RebaseAction::Pick)
+        } else if e == self.key_config.rebase_squash {
+            Some(RebaseAction::Squash)
+        } else if e == self.key_config.rebase_fixup {
+            Some(RebaseAction::Fixup)
+        } else if e == self.key_config.rebase_drop {
+            Some(RebaseAction::Drop)
+        } else if e == self.key_config.rebase_edit {
+            Some(RebaseAction::Edit)
+        } else {
+            None
+        }
+    }
+
+    fn set_action(&mut self, action: RebaseAction) {
+        if let Some(entry) = self.entries.get_mut(self.selection) {
+            entry.action = action;
+            entry.message = None;
+        }
+    }
+
+    fn open_reword(&mut self) -> Result<()> {
+        if let Some(entry) = self.entries.get(self.selection) {
+            let msg = entry
+                .message
+                .clone()
+                .unwrap_or_else(|| entry.summary.clone());
+            self.reword.set_text(msg);
+            self.reword.show()?;
+        }
+
+        Ok(())
+    }
+
+    fn set_reword_message(&mut self) {
+        let msg = self.reword.get_text().trim().to_string();
+
+        if let Some(entry) = self.entries.get_mut(self.selection) {
+            if !msg.is_empty() {
+                entry.action = RebaseAction::Reword;
+                entry.message = Some(msg);
+            }
+        }
+
+        self.reword.clear();
+        self.reword.hide();
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) {
+        let max = self.entries.len().saturating_sub(1);
+        let page = self.current_height.get().saturating_sub(1).max(1);
+
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_sub(1),
+            ScrollType::Down => self.selection.saturating_add(1),
+            ScrollType::PageUp => self.selection.saturating_sub(page),
+            ScrollType::PageDown => {
+                self.selection.saturating_add(page)
+            }
+            ScrollType::Home => 0,
+            ScrollType::End => max,
+        };
+
+        self.selection = cmp::min(new_selection, max);
+    }
+
+    /// swaps the selected entry with its neighbour
+    fn move_entry(&mut self, up: bool) {
+        let other = if up {
+            self.selection.checked_sub(1)
+        } else {
+            Some(self.selection + 1)
+                .filter(|idx| *idx < self.entries.len())
+        };
+
+        if let Some(other) = other {
+            self.entries.swap(self.selection, other);
+            self.selection = other;
+        }
+    }
+
+    fn get_text(&self, height: usize) -> Vec<Text> {
+        let mut txt = Vec::with_capacity(height * 4);
+
+        for (idx, e) in self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+        {
+            let selected = idx == self.selection;
+
+            let action = format!(
+                "{:7}",
+                strings::rebase_action(&self.key_config, e.action)
+            );
+            let hash: String =
+                e.id.to_string().chars().take(7).collect();
+            let summary = e
+                .message
+                .as_ref()
+                .map_or(e.summary.as_str(), |msg| {
+                    msg.lines().next().unwrap_or_default()
+                });
+
+            txt.push(Text::Styled(
+                Cow::from(action),
+                self.theme
+                    .text(e.action != RebaseAction::Drop, selected),
+            ));
+            txt.push(Text::Styled(
+                Cow::from(hash),
+                self.theme.commit_hash(selected),
+            ));
+            txt.push(Text::Styled(
+                Cow::from(format!(" {}", summary)),
+                self.theme
+                    .text(e.action != RebaseAction::Drop, selected),
+            ));
+            txt.push(Text::Raw(Cow::from("\n")));
+        }
+
+        txt
+    }
+}
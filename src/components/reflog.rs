@@ -0,0 +1,168 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, CommitList,
+    Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, CommitInfo},
+    CWD,
+};
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, widgets::Clear, Frame};
+
+/// lists the reflog of `HEAD` to inspect or reset to former states
+pub struct ReflogComponent {
+    list: CommitList,
+    visible: bool,
+    queue: Queue,
+    key_config: SharedKeyConfig,
+}
+
+impl ReflogComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            list: CommitList::new(
+                &strings::reflog_title(&key_config),
+                theme,
+                key_config.clone(),
+            ),
+            visible: false,
+            queue: queue.clone(),
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self) -> Result<()> {
+        self.show()?;
+        self.update()
+    }
+
+    ///
+    pub fn update(&mut self) -> Result<()> {
+        if self.visible {
+            let entries = sync::get_reflog(CWD, "HEAD")?
+                .into_iter()
+                .map(|e| CommitInfo {
+                    message: e.message,
+                    time: e.time,
+                    author: e.committer,
+                    id: e.id,
+                    parents: Vec::new(),
+                })
+                .collect::<Vec<_>>();
+
+            self.list.set_count_total(entries.len());
+            self.list.items().set_items(0, entries);
+        }
+
+        Ok(())
+    }
+
+    fn selected_commit(&self) -> Option<CommitId> {
+        self.list.selected_entry().map(|e| e.id)
+    }
+
+    fn push_selected(&self, ev: fn(CommitId) -> InternalEvent) {
+        if let Some(id) = self.selected_commit() {
+            self.queue.borrow_mut().push_back(ev(id));
+        }
+    }
+}
+
+impl DrawableComponent for ReflogComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            f.render_widget(Clear, rect);
+            self.list.draw(f, rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ReflogComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            self.list.commands(out, force_all);
+
+            let selection_valid = self.selected_commit().is_some();
+            out.push(CommandInfo::new(
+                strings::commands::log_details_open(&self.key_config),
+                selection_valid,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::log_reset_head(&self.key_config),
+                selection_valid,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if self.list.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(k) = ev {
+                if k == self.key_config.exit_popup {
+                    self.hide();
+                } else if k == self.key_config.enter
+                    || k == self.key_config.focus_right
+                {
+                    self.push_selected(|id| {
+                        InternalEvent::InspectCommit(id, None)
+                    });
+                } else if k == self.key_config.log_reset_head {
+                    self.push_selected(InternalEvent::ResetHead);
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{
+    components::{
+        popup_paragraph, visibility_blocking, CommandBlocking,
+        CommandInfo, Component, DrawableComponent,
+    },
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, HookResult, PullResult},
+    AsyncJob, AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use std::{borrow::Cow, fmt::Debug, marker::PhantomData};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    widgets::{Clear, Text},
+    Frame,
+};
+
+/// a git operation on the upstream shown by `RemoteJobComponent`
+pub trait RemoteJob {
+    ///
+    type Output: Clone + Debug + Send + 'static;
+
+    /// sent by asyncgit once the job finished
+    const NOTIFICATION: AsyncNotification;
+
+    /// used in the error message
+    const NAME: &'static str;
+
+    ///
+    fn title(key_config: &SharedKeyConfig) -> String;
+
+    ///
+    fn msg(key_config: &SharedKeyConfig) -> String;
+
+    /// starts the job in the background
+    fn request(git_job: &mut AsyncJob<Self::Output>) -> Result<()>;
+
+    /// called after the job succeeded
+    fn finished(_queue: &Queue, _result: Self::Output) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// pushes the branch HEAD points to into its upstream
+pub struct Push;
+
+impl RemoteJob for Push {
+    type Output = ();
+
+    const NOTIFICATION: AsyncNotification = AsyncNotification::Push;
+    const NAME: &'static str = "push";
+
+    fn title(key_config: &SharedKeyConfig) -> String {
+        strings::push_popup_title(key_config)
+    }
+
+    fn msg(key_config: &SharedKeyConfig) -> String {
+        strings::push_popup_msg(key_config)
+    }
+
+    fn request(git_job: &mut AsyncJob<()>) -> Result<()> {
+        git_job.request(sync::push_upstream)?;
+        Ok(())
+    }
+}
+
+/// pulls the upstream into the branch HEAD points to
+pub struct Pull;
+
+impl RemoteJob for Pull {
+    type Output = PullResult;
+
+    const NOTIFICATION: AsyncNotification = AsyncNotification::Pull;
+    const NAME: &'static str = "pull";
+
+    fn title(key_config: &SharedKeyConfig) -> String {
+        strings::pull_popup_title(key_config)
+    }
+
+    fn msg(key_config: &SharedKeyConfig) -> String {
+        strings::pull_popup_msg(key_config)
+    }
+
+    fn request(git_job: &mut AsyncJob<PullResult>) -> Result<()> {
+        git_job.request(sync::pull)?;
+        Ok(())
+    }
+
+    fn finished(queue: &Queue, result: PullResult) -> Result<()> {
+        if !matches!(
+            result,
+            PullResult::FastForward | PullResult::Merged
+        ) {
+            return Ok(());
+        }
+
+        if let HookResult::NotOk(e) =
+            sync::hooks_post_merge(CWD, false)?
+        {
+            log::error!("post-merge hook error: {}", e);
+            queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "post-merge hook error:\n{}",
+                    e
+                )),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+///
+pub type PushComponent = RemoteJobComponent<Push>;
+///
+pub type PullComponent = RemoteJobComponent<Pull>;
+
+/// shown while a `RemoteJob` runs in the background
+pub struct RemoteJobComponent<J: RemoteJob> {
+    visible: bool,
+    git_job: AsyncJob<J::Output>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+    job: PhantomData<J>,
+}
+
+impl<J: RemoteJob> DrawableComponent for RemoteJobComponent<J> {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let txt = vec![Text::Styled(
+                Cow::from(J::msg(&self.key_config)),
+                self.theme.text(true, false),
+            )];
+
+            let area = ui::centered_rect_absolute(30, 3, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &J::title(&self.key_config),
+                    txt.iter(),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<J: RemoteJob> Component for RemoteJobComponent<J> {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl<J: RemoteJob> RemoteJobComponent<J> {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            visible: false,
+            git_job: AsyncJob::new(sender, J::NOTIFICATION),
+            queue: queue.clone(),
+            theme,
+            key_config,
+            job: PhantomData,
+        }
+    }
+
+    /// starts the job and shows the popup until it finished
+    pub fn start(&mut self) -> Result<()> {
+        J::request(&mut self.git_job)?;
+        self.show()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_job.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if ev == J::NOTIFICATION && !self.git_job.is_pending() {
+            self.hide();
+
+            if let Some(err) = self.git_job.last_error()? {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "{} failed:\n{}",
+                        J::NAME,
+                        err
+                    )),
+                );
+            } else {
+                if let Some(result) = self.git_job.last_result()? {
+                    J::finished(&self.queue, result)?;
+                }
+
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::Update(NeedsUpdate::ALL),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
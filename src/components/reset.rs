@@ -159,6 +159,55 @@ impl ResetComponent {
                     strings::confirm_title_reset(&self.key_config),
                     strings::confirm_msg_resethunk(&self.key_config),
                 ),
+                Action::ResetLines(_, _) => (
+                    strings::confirm_title_reset(&self.key_config),
+                    strings::confirm_msg_reset_lines(
+                        &self.key_config,
+                    ),
+                ),
+                Action::DeleteBranch(_) => (
+                    strings::confirm_title_delete_branch(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_delete_branch(
+                        &self.key_config,
+                    ),
+                ),
+                Action::StashAndCheckout(_) => (
+                    strings::confirm_title_stash_checkout(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_stash_checkout(
+                        &self.key_config,
+                    ),
+                ),
+                Action::AbortMerge => (
+                    strings::confirm_title_abort_merge(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_abort_merge(
+                        &self.key_config,
+                    ),
+                ),
+                Action::AbortRebase => (
+                    strings::confirm_title_abort_rebase(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_abort_rebase(
+                        &self.key_config,
+                    ),
+                ),
+                Action::ResetHead(id, mode, preview) => (
+                    strings::confirm_title_reset_head(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_reset_head(
+                        &self.key_config,
+                        &id.to_string(),
+                        *mode,
+                        *preview,
+                    ),
+                ),
             };
         }
 
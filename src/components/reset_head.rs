@@ -0,0 +1,199 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, Queue},
+    strings, ui,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, ResetMode},
+    CWD,
+};
+use crossterm::event::Event;
+use std::borrow::Cow;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Text},
+    Frame,
+};
+use ui::style::SharedTheme;
+
+const MODES: [ResetMode; 3] =
+    [ResetMode::Soft, ResetMode::Mixed, ResetMode::Hard];
+
+/// lets the user pick how to reset HEAD to a commit
+pub struct ResetHeadComponent {
+    target: Option<CommitId>,
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ResetHeadComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const SIZE: (u16, u16) = (60, 5);
+
+            let target: String = self
+                .target
+                .map(|id| id.to_string().chars().take(7).collect())
+                .unwrap_or_default();
+
+            let txt = MODES
+                .iter()
+                .enumerate()
+                .map(|(idx, mode)| {
+                    Text::Styled(
+                        Cow::from(format!(
+                            "{}\n",
+                            strings::reset_mode(
+                                &self.key_config,
+                                *mode
+                            )
+                        )),
+                        self.theme.text(true, idx == self.selection),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let area =
+                ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(txt.iter())
+                    .block(
+                        Block::default()
+                            .title(&strings::reset_head_popup_title(
+                                &self.key_config,
+                                &target,
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .title_style(self.theme.title(true)),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ResetHeadComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::reset_head_confirm(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.move_down {
+                    self.selection =
+                        (self.selection + 1).min(MODES.len() - 1);
+                } else if e == self.key_config.enter {
+                    self.confirm()?;
+                }
+
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl ResetHeadComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            target: None,
+            selection: 1,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, id: CommitId) -> Result<()> {
+        self.target = Some(id);
+        self.selection = 1;
+        self.show()
+    }
+
+    fn confirm(&mut self) -> Result<()> {
+        if let Some(id) = self.target.take() {
+            let preview = sync::reset_head_preview(CWD, id)?;
+
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::ResetHead(
+                    id,
+                    MODES[self.selection],
+                    preview,
+                )),
+            );
+        }
+
+        self.hide();
+
+        Ok(())
+    }
+}
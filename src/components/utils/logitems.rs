@@ -1,5 +1,5 @@
 use super::time_to_string;
-use asyncgit::sync::{CommitId, CommitInfo};
+use asyncgit::sync::{CommitId, CommitInfo, GraphRow};
 use std::slice::Iter;
 
 static SLICE_OFFSET_RELOAD_THRESHOLD: usize = 100;
@@ -10,6 +10,7 @@ pub struct LogEntry {
     pub msg: String,
     pub hash_short: String,
     pub id: CommitId,
+    pub graph: Option<GraphRow>,
 }
 
 impl From<CommitInfo> for LogEntry {
@@ -22,6 +23,7 @@ impl From<CommitInfo> for LogEntry {
             time: time_to_string(c.time, true),
             hash_short: hash,
             id: c.id,
+            graph: None,
         }
     }
 }
@@ -64,6 +66,13 @@ impl ItemBatch {
         self.index_offset = start_index;
     }
 
+    /// sets the graph rows of the current items in order
+    pub fn set_graph(&mut self, rows: Vec<GraphRow>) {
+        for (item, row) in self.items.iter_mut().zip(rows) {
+            item.graph = Some(row);
+        }
+    }
+
     /// returns `true` if we should fetch updated list of items
     pub fn needs_data(&self, idx: usize, idx_max: usize) -> bool {
         let want_min =
@@ -3,6 +3,7 @@ use chrono::{DateTime, Local, NaiveDateTime, Utc};
 pub mod filetree;
 pub mod logitems;
 pub mod statustree;
+pub mod word_diff;
 
 /// macro to simplify running code that might return Err.
 /// It will show a popup in that case
@@ -20,6 +21,23 @@ macro_rules! try_or_popup {
     };
 }
 
+/// helper func to convert unix time since epoch to a short relative age like `3mo`
+pub fn time_to_age(secs: i64) -> String {
+    let age = Utc::now().timestamp().saturating_sub(secs).max(0);
+
+    match age {
+        a if a < 60 * 60 => format!("{}m", a / 60),
+        a if a < 60 * 60 * 24 => format!("{}h", a / (60 * 60)),
+        a if a < 60 * 60 * 24 * 30 => {
+            format!("{}d", a / (60 * 60 * 24))
+        }
+        a if a < 60 * 60 * 24 * 365 => {
+            format!("{}mo", a / (60 * 60 * 24 * 30))
+        }
+        a => format!("{}y", a / (60 * 60 * 24 * 365)),
+    }
+}
+
 /// helper func to convert unix time since epoch to formated time string in local timezone
 pub fn time_to_string(secs: i64, short: bool) -> String {
     let time = DateTime::<Local>::from(DateTime::<Utc>::from_utc(
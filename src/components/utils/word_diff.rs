@@ -0,0 +1,193 @@
+use std::ops::Range;
+
+/// longer lines are not compared word by word
+const MAX_TOKENS: usize = 256;
+
+/// byte ranges of a line and whether they changed
+pub type WordSpans = Vec<(Range<usize>, bool)>;
+
+/// compares a deleted with its added line word by word, returns
+/// the changed and unchanged spans of both.
+/// `None` if they have nothing in common or are too long to compare
+pub fn word_diff(
+    old: &str,
+    new: &str,
+) -> Option<(WordSpans, WordSpans)> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+
+    if old_tokens.len() > MAX_TOKENS || new_tokens.len() > MAX_TOKENS
+    {
+        return None;
+    }
+
+    let (old_common, new_common) =
+        common_tokens(old, &old_tokens, new, &new_tokens);
+
+    // common whitespace alone does not make lines similar
+    let similar =
+        old_tokens.iter().zip(&old_common).any(|(token, common)| {
+            *common && !old[token.clone()].trim().is_empty()
+        });
+    if !similar {
+        return None;
+    }
+
+    Some((
+        spans(&old_tokens, &old_common),
+        spans(&new_tokens, &new_common),
+    ))
+}
+
+/// splits into runs of alphanumeric chars, runs of whitespace
+/// and single other chars
+fn tokenize(line: &str) -> Vec<Range<usize>> {
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Other,
+    }
+
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Other
+        }
+    };
+
+    let mut tokens: Vec<Range<usize>> = Vec::new();
+    let mut last = None;
+
+    for (idx, c) in line.char_indices() {
+        let current = class(c);
+        let end = idx + c.len_utf8();
+
+        match tokens.last_mut() {
+            Some(token)
+                if current != Class::Other
+                    && last.as_ref() == Some(&current) =>
+            {
+                token.end = end;
+            }
+            _ => tokens.push(idx..end),
+        }
+
+        last = Some(current);
+    }
+
+    tokens
+}
+
+/// marks the tokens that are part of the longest common
+/// subsequence of both lines
+fn common_tokens(
+    old: &str,
+    old_tokens: &[Range<usize>],
+    new: &str,
+    new_tokens: &[Range<usize>],
+) -> (Vec<bool>, Vec<bool>) {
+    let equal = |i: usize, j: usize| {
+        old[old_tokens[i].clone()] == new[new_tokens[j].clone()]
+    };
+
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+    // lengths of the common subsequences of the suffixes
+    let mut table = vec![vec![0_u16; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if equal(i, j) {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_common = vec![false; n];
+    let mut new_common = vec![false; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if equal(i, j) {
+            old_common[i] = true;
+            new_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    (old_common, new_common)
+}
+
+/// joins neighbouring tokens of the same kind
+fn spans(tokens: &[Range<usize>], common: &[bool]) -> WordSpans {
+    let mut res: WordSpans = Vec::new();
+
+    for (token, common) in tokens.iter().zip(common) {
+        let changed = !common;
+
+        match res.last_mut() {
+            Some((range, last_changed))
+                if *last_changed == changed =>
+            {
+                range.end = token.end;
+            }
+            _ => res.push((token.clone(), changed)),
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changed<'a>(line: &'a str, spans: &WordSpans) -> Vec<&'a str> {
+        spans
+            .iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(range, _)| &line[range.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let line = "let a_b = f(x);";
+        let tokens: Vec<&str> =
+            tokenize(line).into_iter().map(|r| &line[r]).collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                "let", " ", "a_b", " ", "=", " ", "f", "(", "x", ")",
+                ";"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_word_changed() {
+        let old = "let value = compute(a, b);";
+        let new = "let value = compute(a, c);";
+
+        let (old_spans, new_spans) = word_diff(old, new).unwrap();
+
+        assert_eq!(changed(old, &old_spans), vec!["b"]);
+        assert_eq!(changed(new, &new_spans), vec!["c"]);
+        assert_eq!(old_spans.last().unwrap().0.end, old.len());
+    }
+
+    #[test]
+    fn test_nothing_in_common() {
+        assert!(word_diff("foo", "bar").is_none());
+        assert!(word_diff("foo bar", "baz qux").is_none());
+    }
+}
@@ -21,6 +21,7 @@ pub struct KeyConfig {
     pub tab_log: KeyEvent,
     pub tab_stashing: KeyEvent,
     pub tab_stashes: KeyEvent,
+    pub tab_branches: KeyEvent,
     pub tab_toggle: KeyEvent,
     pub tab_toggle_reverse: KeyEvent,
     pub tab_toggle_reverse_windows: KeyEvent,
@@ -60,6 +61,49 @@ pub struct KeyConfig {
     pub commit_amend: KeyEvent,
     pub copy: KeyEvent,
     pub create_branch: KeyEvent,
+    pub push: KeyEvent,
+    pub fetch: KeyEvent,
+    pub pull: KeyEvent,
+    pub rename_branch: KeyEvent,
+    pub delete_branch: KeyEvent,
+    pub set_branch_upstream: KeyEvent,
+    pub merge_branch: KeyEvent,
+    pub abort_merge: KeyEvent,
+    pub continue_merge: KeyEvent,
+    pub conflict_take_ours: KeyEvent,
+    pub conflict_take_theirs: KeyEvent,
+    pub conflict_take_both: KeyEvent,
+    pub conflict_file_ours: KeyEvent,
+    pub conflict_file_theirs: KeyEvent,
+    pub conflict_file_both: KeyEvent,
+    pub rebase_interactive: KeyEvent,
+    pub rebase_pick: KeyEvent,
+    pub rebase_reword: KeyEvent,
+    pub rebase_squash: KeyEvent,
+    pub rebase_fixup: KeyEvent,
+    pub rebase_drop: KeyEvent,
+    pub rebase_edit: KeyEvent,
+    pub rebase_continue: KeyEvent,
+    pub rebase_skip: KeyEvent,
+    pub rebase_abort: KeyEvent,
+    pub log_mark_commit: KeyEvent,
+    pub log_cherry_pick: KeyEvent,
+    pub log_revert: KeyEvent,
+    pub log_reset_head: KeyEvent,
+    pub open_reflog: KeyEvent,
+    pub blame: KeyEvent,
+    pub blame_parent: KeyEvent,
+    pub file_history: KeyEvent,
+    pub log_search: KeyEvent,
+    pub log_search_next: KeyEvent,
+    pub log_search_prev: KeyEvent,
+    pub log_search_filter: KeyEvent,
+    pub log_pickaxe: KeyEvent,
+    pub log_options: KeyEvent,
+    pub diff_stage_lines: KeyEvent,
+    pub diff_reset_lines: KeyEvent,
+    pub diff_toggle_split: KeyEvent,
+    pub diff_options: KeyEvent,
 }
 
 #[rustfmt::skip]
@@ -70,6 +114,7 @@ impl Default for KeyConfig {
 			tab_log: KeyEvent { code: KeyCode::Char('2'), modifiers: KeyModifiers::empty()},
 			tab_stashing: KeyEvent { code: KeyCode::Char('3'), modifiers: KeyModifiers::empty()},
 			tab_stashes: KeyEvent { code: KeyCode::Char('4'), modifiers: KeyModifiers::empty()},
+			tab_branches: KeyEvent { code: KeyCode::Char('5'), modifiers: KeyModifiers::empty()},
 			tab_toggle: KeyEvent { code: KeyCode::Tab, modifiers: KeyModifiers::empty()},
 			tab_toggle_reverse: KeyEvent { code: KeyCode::BackTab, modifiers: KeyModifiers::empty()},
 			tab_toggle_reverse_windows: KeyEvent { code: KeyCode::BackTab, modifiers: KeyModifiers::SHIFT},
@@ -109,6 +154,49 @@ impl Default for KeyConfig {
 			commit_amend: KeyEvent { code: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL},
             copy: KeyEvent { code: KeyCode::Char('y'), modifiers: KeyModifiers::empty()},
             create_branch: KeyEvent { code: KeyCode::Char('b'), modifiers: KeyModifiers::empty()},
+            push: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+            fetch: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty()},
+            pull: KeyEvent { code: KeyCode::Char('P'), modifiers: KeyModifiers::SHIFT},
+            rename_branch: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+            delete_branch: KeyEvent { code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
+            set_branch_upstream: KeyEvent { code: KeyCode::Char('u'), modifiers: KeyModifiers::empty()},
+            merge_branch: KeyEvent { code: KeyCode::Char('m'), modifiers: KeyModifiers::empty()},
+            abort_merge: KeyEvent { code: KeyCode::Char('A'), modifiers: KeyModifiers::SHIFT},
+            continue_merge: KeyEvent { code: KeyCode::Char('C'), modifiers: KeyModifiers::SHIFT},
+            conflict_take_ours: KeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::empty()},
+            conflict_take_theirs: KeyEvent { code: KeyCode::Char('t'), modifiers: KeyModifiers::empty()},
+            conflict_take_both: KeyEvent { code: KeyCode::Char('b'), modifiers: KeyModifiers::empty()},
+            conflict_file_ours: KeyEvent { code: KeyCode::Char('O'), modifiers: KeyModifiers::SHIFT},
+            conflict_file_theirs: KeyEvent { code: KeyCode::Char('T'), modifiers: KeyModifiers::SHIFT},
+            conflict_file_both: KeyEvent { code: KeyCode::Char('B'), modifiers: KeyModifiers::SHIFT},
+            rebase_interactive: KeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT},
+            rebase_pick: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+            rebase_reword: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+            rebase_squash: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::empty()},
+            rebase_fixup: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty()},
+            rebase_drop: KeyEvent { code: KeyCode::Char('d'), modifiers: KeyModifiers::empty()},
+            rebase_edit: KeyEvent { code: KeyCode::Char('e'), modifiers: KeyModifiers::empty()},
+            rebase_continue: KeyEvent { code: KeyCode::Char('C'), modifiers: KeyModifiers::SHIFT},
+            rebase_skip: KeyEvent { code: KeyCode::Char('S'), modifiers: KeyModifiers::SHIFT},
+            rebase_abort: KeyEvent { code: KeyCode::Char('A'), modifiers: KeyModifiers::SHIFT},
+            log_mark_commit: KeyEvent { code: KeyCode::Char(' '), modifiers: KeyModifiers::empty()},
+            log_cherry_pick: KeyEvent { code: KeyCode::Char('c'), modifiers: KeyModifiers::empty()},
+            log_revert: KeyEvent { code: KeyCode::Char('v'), modifiers: KeyModifiers::empty()},
+            log_reset_head: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+            open_reflog: KeyEvent { code: KeyCode::Char('L'), modifiers: KeyModifiers::SHIFT},
+            blame: KeyEvent { code: KeyCode::Char('B'), modifiers: KeyModifiers::SHIFT},
+            blame_parent: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+            file_history: KeyEvent { code: KeyCode::Char('H'), modifiers: KeyModifiers::SHIFT},
+            log_search: KeyEvent { code: KeyCode::Char('/'), modifiers: KeyModifiers::empty()},
+            log_search_next: KeyEvent { code: KeyCode::Char('n'), modifiers: KeyModifiers::empty()},
+            log_search_prev: KeyEvent { code: KeyCode::Char('N'), modifiers: KeyModifiers::SHIFT},
+            log_search_filter: KeyEvent { code: KeyCode::Char('F'), modifiers: KeyModifiers::SHIFT},
+            log_pickaxe: KeyEvent { code: KeyCode::Char('G'), modifiers: KeyModifiers::SHIFT},
+            log_options: KeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::empty()},
+            diff_stage_lines: KeyEvent { code: KeyCode::Char('l'), modifiers: KeyModifiers::empty()},
+            diff_reset_lines: KeyEvent { code: KeyCode::Char('L'), modifiers: KeyModifiers::SHIFT},
+            diff_toggle_split: KeyEvent { code: KeyCode::Char('x'), modifiers: KeyModifiers::empty()},
+            diff_options: KeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::empty()},
         }
     }
 }
@@ -16,6 +16,7 @@ mod components;
 mod input;
 mod keys;
 mod notify_mutex;
+mod options;
 mod profiler;
 mod queue;
 mod spinner;
@@ -141,7 +142,7 @@ fn main() -> Result<()> {
                     }
                     app.event(ev)?
                 }
-                QueueEvent::Tick => app.update()?,
+                QueueEvent::Tick => app.tick()?,
                 QueueEvent::GitEvent(ev)
                     if ev != AsyncNotification::FinishUnchanged =>
                 {
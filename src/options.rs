@@ -0,0 +1,91 @@
+use crate::get_app_config_path;
+use anyhow::Result;
+use asyncgit::{sync::DiffOptions, CWD};
+use ron::{
+    de::from_bytes,
+    ser::{to_string_pretty, PrettyConfig},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{Read, Write},
+    path::PathBuf,
+    rc::Rc,
+    time::Duration,
+};
+
+pub type SharedOptions = Rc<RefCell<Options>>;
+
+/// general app options persisted in `options.ron`
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Options {
+    /// seconds between automatic background fetches (0 disables it)
+    pub fetch_interval_secs: u64,
+    /// show diffs side by side instead of unified
+    pub diff_split: bool,
+    /// read from git config, changes last for the session only
+    #[serde(skip)]
+    pub diff: DiffOptions,
+}
+
+impl Options {
+    /// interval to fetch all remotes in the background
+    pub fn fetch_interval(&self) -> Option<Duration> {
+        if self.fetch_interval_secs == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(self.fetch_interval_secs))
+        }
+    }
+
+    /// changes and persists the diff layout
+    pub fn set_diff_split(&mut self, split: bool) {
+        self.diff_split = split;
+
+        if self.save().is_err() {
+            log::warn!("failed to store options to disk.")
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        let options_file = Self::get_options_file()?;
+        let mut file = File::create(options_file)?;
+        let data = to_string_pretty(self, PrettyConfig::default())?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    fn get_options_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("options.ron"))
+    }
+
+    fn read_file(options_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(options_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(from_bytes(&buffer)?)
+    }
+
+    fn init_internal() -> Result<Self> {
+        let file = Self::get_options_file()?;
+        if file.exists() {
+            Ok(Self::read_file(file)?)
+        } else {
+            let def = Self::default();
+            if def.save().is_err() {
+                log::warn!("failed to store default options to disk.")
+            }
+            Ok(def)
+        }
+    }
+
+    pub fn init() -> Self {
+        let mut options = Self::init_internal().unwrap_or_default();
+        options.diff =
+            DiffOptions::from_config(CWD).unwrap_or_default();
+        options
+    }
+}
@@ -1,5 +1,8 @@
 use crate::tabs::StashingOptions;
-use asyncgit::sync::{CommitId, CommitTags};
+use asyncgit::sync::{
+    CommitId, CommitTags, DiffLinePosition, LogOptions, LogSearch,
+    ResetMode, ResetPreview,
+};
 use bitflags::bitflags;
 use std::{cell::RefCell, collections::VecDeque, rc::Rc};
 
@@ -27,7 +30,13 @@ pub struct ResetItem {
 pub enum Action {
     Reset(ResetItem),
     ResetHunk(String, u64),
+    ResetLines(String, Vec<DiffLinePosition>),
     StashDrop(CommitId),
+    DeleteBranch(String),
+    StashAndCheckout(String),
+    AbortMerge,
+    AbortRebase,
+    ResetHead(CommitId, ResetMode, ResetPreview),
 }
 
 ///
@@ -54,6 +63,42 @@ pub enum InternalEvent {
     CreateBranch,
     ///
     OpenExternalEditor(Option<String>),
+    /// push the current branch to its upstream
+    Push,
+    /// fetch all remotes showing the progress
+    Fetch,
+    /// pull the upstream of the current branch
+    Pull,
+    /// open rename popup for branch (ref name, current name)
+    RenameBranch(String, String),
+    /// open upstream popup for branch (ref name, upstream)
+    SetUpstream(String, Option<String>),
+    /// open interactive rebase plan of HEAD onto commit
+    OpenRebasePlan(CommitId),
+    /// blame file at commit (`HEAD` if `None`)
+    BlameFile(String, Option<CommitId>),
+    /// open the history of a file
+    OpenFileRevlog(String),
+    /// open the reflog of HEAD
+    OpenReflog,
+    /// open the content search of the history
+    OpenPickaxe,
+    /// open the log options showing the current ones
+    OpenLogOptions(LogOptions),
+    /// restart the log with new options
+    SetLogOptions(LogOptions),
+    /// open the options of file diffs
+    OpenDiffOptions,
+    /// open the log search prompt showing the current query
+    OpenLogSearch(String),
+    /// search the log (query, parsed search)
+    SearchLog(String, LogSearch),
+    /// open popup to choose how to reset HEAD to commit
+    ResetHead(CommitId),
+    /// cherry-pick commits onto HEAD
+    CherryPick(Vec<CommitId>),
+    /// revert commits
+    Revert(Vec<CommitId>),
 }
 
 ///
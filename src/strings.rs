@@ -1,4 +1,8 @@
 use crate::keys::{get_hint, SharedKeyConfig};
+use asyncgit::sync::{
+    ConflictResolution, DiffAlgorithm, LogOptions, LogScope, LogSort,
+    RebaseAction, RebaseStatus, RepoState, ResetMode, ResetPreview,
+};
 
 pub mod order {
     pub static NAV: i8 = 1;
@@ -13,6 +17,26 @@ pub fn title_status(key_config: &SharedKeyConfig) -> String {
 pub fn title_diff(_key_config: &SharedKeyConfig) -> String {
     "Diff: ".to_string()
 }
+pub fn title_conflict(_key_config: &SharedKeyConfig) -> String {
+    "Conflict: ".to_string()
+}
+pub fn conflict_resolved(
+    _key_config: &SharedKeyConfig,
+    resolution: ConflictResolution,
+) -> String {
+    match resolution {
+        ConflictResolution::Ours => "resolved: ours",
+        ConflictResolution::Theirs => "resolved: theirs",
+        ConflictResolution::Both => "resolved: ours and theirs",
+    }
+    .to_string()
+}
+pub fn conflict_binary(_key_config: &SharedKeyConfig) -> String {
+    "binary file: take ours or theirs as a whole".to_string()
+}
+pub fn title_conflicts(_key_config: &SharedKeyConfig) -> String {
+    "Conflicts".to_string()
+}
 pub fn title_index(key_config: &SharedKeyConfig) -> String {
     format!("Staged Changes [{}]", get_hint(key_config.focus_stage))
 }
@@ -28,6 +52,38 @@ pub fn tab_stashing(key_config: &SharedKeyConfig) -> String {
 pub fn tab_stashes(key_config: &SharedKeyConfig) -> String {
     format!("Stashes [{}]", get_hint(key_config.tab_stashes))
 }
+pub fn tab_branches(key_config: &SharedKeyConfig) -> String {
+    format!("Branches [{}]", get_hint(key_config.tab_branches))
+}
+pub fn branch_status_detached(
+    _key_config: &SharedKeyConfig,
+    id: &str,
+) -> String {
+    format!("HEAD detached at {}", &id[..7.min(id.len())])
+}
+pub fn branch_status_upstream(
+    _key_config: &SharedKeyConfig,
+    upstream: &str,
+    ahead: usize,
+    behind: usize,
+) -> String {
+    format!(" [{} \u{2191}{} \u{2193}{}]", upstream, ahead, behind)
+}
+pub fn branch_status_state(
+    _key_config: &SharedKeyConfig,
+    state: RepoState,
+) -> String {
+    match state {
+        RepoState::Clean => "",
+        RepoState::Merge => " MERGING",
+        RepoState::Rebase => " REBASING",
+        RepoState::CherryPick => " CHERRY-PICKING",
+        RepoState::Revert => " REVERTING",
+        RepoState::Bisect => " BISECTING",
+        RepoState::ApplyMailbox => " AM",
+    }
+    .to_string()
+}
 pub fn tab_divider(_key_config: &SharedKeyConfig) -> String {
     " | ".to_string()
 }
@@ -43,6 +99,26 @@ pub fn msg_title_error(_key_config: &SharedKeyConfig) -> String {
 pub fn commit_title(_key_config: &SharedKeyConfig) -> String {
     "Commit".to_string()
 }
+pub fn commit_title_merge(_key_config: &SharedKeyConfig) -> String {
+    "Commit (Merge)".to_string()
+}
+pub fn commit_title_cherry_pick(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Commit (Cherry-pick)".to_string()
+}
+pub fn commit_title_revert(_key_config: &SharedKeyConfig) -> String {
+    "Commit (Revert)".to_string()
+}
+pub fn pick_not_applied(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    format!(
+        "stopped on conflicts, {} more commit(s) were not applied",
+        count
+    )
+}
 pub fn commit_title_amend(_key_config: &SharedKeyConfig) -> String {
     "Commit (Amend)".to_string()
 }
@@ -82,8 +158,272 @@ pub fn confirm_msg_resethunk(
 ) -> String {
     "confirm reset hunk?".to_string()
 }
-pub fn log_title(_key_config: &SharedKeyConfig) -> String {
-    "Commit".to_string()
+pub fn confirm_msg_reset_lines(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "confirm reset selected lines?".to_string()
+}
+pub fn confirm_title_delete_branch(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Delete Branch".to_string()
+}
+pub fn confirm_msg_delete_branch(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "branch is not fully merged. delete anyway?".to_string()
+}
+pub fn confirm_title_stash_checkout(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Checkout".to_string()
+}
+pub fn confirm_msg_stash_checkout(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "working tree has local changes. stash them and checkout?"
+        .to_string()
+}
+pub fn confirm_title_abort_merge(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Abort Merge".to_string()
+}
+pub fn confirm_msg_abort_merge(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "all changes in the working tree will be lost. abort merge?"
+        .to_string()
+}
+pub fn confirm_title_abort_rebase(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Abort Rebase".to_string()
+}
+pub fn confirm_msg_abort_rebase(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "all rebased commits and changes in the working tree will be lost. abort rebase?"
+        .to_string()
+}
+pub fn confirm_title_reset_head(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Reset HEAD".to_string()
+}
+pub fn confirm_msg_reset_head(
+    _key_config: &SharedKeyConfig,
+    target: &str,
+    mode: ResetMode,
+    preview: ResetPreview,
+) -> String {
+    let lost = match mode {
+        ResetMode::Soft => format!(
+            "{} commit(s) will be removed from the branch, their changes stay staged.",
+            preview.commits
+        ),
+        ResetMode::Mixed => format!(
+            "{} commit(s) will be removed from the branch, their changes stay in the working tree.",
+            preview.commits
+        ),
+        ResetMode::Hard => format!(
+            "{} commit(s) and the changes to {} file(s) will be lost.",
+            preview.commits, preview.files
+        ),
+    };
+
+    format!(
+        "reset HEAD to {}?\n{}\nundo from the reflog (ORIG_HEAD).",
+        &target[..7.min(target.len())],
+        lost
+    )
+}
+pub fn reset_head_popup_title(
+    _key_config: &SharedKeyConfig,
+    target: &str,
+) -> String {
+    format!("Reset HEAD to {}", target)
+}
+pub fn reset_mode(
+    _key_config: &SharedKeyConfig,
+    mode: ResetMode,
+) -> String {
+    match mode {
+        ResetMode::Soft => "soft   keep index and working tree",
+        ResetMode::Mixed => "mixed  reset index, keep working tree",
+        ResetMode::Hard => "hard   reset index and working tree",
+    }
+    .to_string()
+}
+pub fn rebase_plan_title(
+    _key_config: &SharedKeyConfig,
+    base: &str,
+) -> String {
+    format!("Rebase onto {} (oldest first)", base)
+}
+pub fn rebase_reword_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Reword".to_string()
+}
+pub fn rebase_reword_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "type new commit message".to_string()
+}
+pub fn rebase_action(
+    _key_config: &SharedKeyConfig,
+    action: RebaseAction,
+) -> String {
+    match action {
+        RebaseAction::Pick => "pick",
+        RebaseAction::Reword => "reword",
+        RebaseAction::Squash => "squash",
+        RebaseAction::Fixup => "fixup",
+        RebaseAction::Drop => "drop",
+        RebaseAction::Edit => "edit",
+    }
+    .to_string()
+}
+pub fn rebase_banner(
+    key_config: &SharedKeyConfig,
+    status: &RebaseStatus,
+) -> String {
+    let stopped = match status.stopped {
+        Some((_, id)) if status.conflict => {
+            format!(": conflicts applying {}", &id.to_string()[..7])
+        }
+        Some((action, id)) => format!(
+            ": stopped at {} {}",
+            rebase_action(key_config, action),
+            &id.to_string()[..7]
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        "rebase in progress ({}/{}){} - continue [{}] skip [{}] abort [{}]",
+        status.done,
+        status.total,
+        stopped,
+        get_hint(key_config.rebase_continue),
+        get_hint(key_config.rebase_skip),
+        get_hint(key_config.rebase_abort),
+    )
+}
+pub fn log_title(
+    _key_config: &SharedKeyConfig,
+    options: &LogOptions,
+) -> String {
+    let mut args = Vec::new();
+    match &options.scope {
+        LogScope::Head => (),
+        LogScope::All => args.push("--all"),
+        LogScope::Revision(revision) => args.push(revision),
+    }
+    if options.first_parent {
+        args.push("--first-parent");
+    }
+    if options.no_merges {
+        args.push("--no-merges");
+    }
+    if options.sort == LogSort::Topological {
+        args.push("--topo-order");
+    }
+
+    if args.is_empty() {
+        "Commit".to_string()
+    } else {
+        format!("Commit ({})", args.join(" "))
+    }
+}
+pub fn log_options_title(_key_config: &SharedKeyConfig) -> String {
+    "Log options".to_string()
+}
+pub fn log_scope(
+    _key_config: &SharedKeyConfig,
+    scope: &LogScope,
+) -> String {
+    match scope {
+        LogScope::Head => "HEAD".to_string(),
+        LogScope::All => "all branches (--all)".to_string(),
+        LogScope::Revision(revision) => {
+            format!("revision or range: {}", revision)
+        }
+    }
+}
+pub fn log_option_first_parent(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "first parent only (--first-parent)".to_string()
+}
+pub fn log_option_no_merges(_key_config: &SharedKeyConfig) -> String {
+    "hide merges (--no-merges)".to_string()
+}
+pub fn log_option_topo_order(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "topological order (--topo-order)".to_string()
+}
+pub fn log_revision_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Log revision".to_string()
+}
+pub fn log_revision_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "branch, ref or range like main..feature".to_string()
+}
+pub fn diff_options_title(_key_config: &SharedKeyConfig) -> String {
+    "Diff options".to_string()
+}
+pub fn diff_option_ignore_whitespace(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "ignore all whitespace (-w)".to_string()
+}
+pub fn diff_option_ignore_whitespace_change(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "ignore whitespace amount (-b)".to_string()
+}
+pub fn diff_option_ignore_whitespace_eol(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "ignore whitespace at line end (--ignore-space-at-eol)"
+        .to_string()
+}
+pub fn diff_option_context(
+    _key_config: &SharedKeyConfig,
+    context: u32,
+) -> String {
+    format!("context lines: {}", context)
+}
+pub fn diff_option_algorithm(
+    _key_config: &SharedKeyConfig,
+    algorithm: DiffAlgorithm,
+) -> String {
+    format!(
+        "algorithm: {}",
+        match algorithm {
+            DiffAlgorithm::Myers => "myers",
+            DiffAlgorithm::Minimal => "minimal",
+            DiffAlgorithm::Patience => "patience",
+        }
+    )
+}
+pub fn diff_option_renames(
+    _key_config: &SharedKeyConfig,
+    threshold: u16,
+) -> String {
+    format!("detect renames in commits, {}% similar (-M)", threshold)
+}
+pub fn diff_option_copies(
+    _key_config: &SharedKeyConfig,
+    threshold: u16,
+) -> String {
+    format!("detect copies in commits, {}% similar (-C)", threshold)
 }
 pub fn tag_commit_popup_title(
     _key_config: &SharedKeyConfig,
@@ -96,6 +436,89 @@ pub fn tag_commit_popup_msg(_key_config: &SharedKeyConfig) -> String {
 pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
     "Stashes".to_string()
 }
+pub fn reflog_title(_key_config: &SharedKeyConfig) -> String {
+    "Reflog HEAD".to_string()
+}
+pub fn blame_title(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+    commit: &str,
+    loading: bool,
+) -> String {
+    format!(
+        "Blame {} @ {}{}",
+        path,
+        commit,
+        if loading { " (loading)" } else { "" }
+    )
+}
+pub fn file_log_title(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+) -> String {
+    format!("History {}", path)
+}
+pub fn log_search_title(
+    _key_config: &SharedKeyConfig,
+    query: &str,
+    matches: usize,
+    filtered: bool,
+) -> String {
+    format!(
+        "Commit ['{}': {} matches{}]",
+        query,
+        matches,
+        if filtered { ", filtered" } else { "" }
+    )
+}
+pub fn log_search_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Search".to_string()
+}
+pub fn log_search_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "text, /regex/, author:, since:, until:, sha:".to_string()
+}
+pub fn pickaxe_title(
+    _key_config: &SharedKeyConfig,
+    query: &str,
+    searching: bool,
+) -> String {
+    format!(
+        "Content '{}'{}",
+        query,
+        if searching { " (searching...)" } else { "" }
+    )
+}
+pub fn pickaxe_popup_title(_key_config: &SharedKeyConfig) -> String {
+    "Find content".to_string()
+}
+pub fn pickaxe_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "string added/removed or /regex/ of changed lines".to_string()
+}
+pub fn branchlist_title(_key_config: &SharedKeyConfig) -> String {
+    "Branches".to_string()
+}
+pub fn rename_branch_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Rename Branch".to_string()
+}
+pub fn rename_branch_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "type new branch name".to_string()
+}
+pub fn set_upstream_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Upstream".to_string()
+}
+pub fn set_upstream_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "type remote branch (empty to unset)".to_string()
+}
 pub fn help_title(_key_config: &SharedKeyConfig) -> String {
     "Help: all commands".to_string()
 }
@@ -120,9 +543,25 @@ pub fn create_branch_popup_msg(
 ) -> String {
     "type branch name".to_string()
 }
+pub fn push_popup_title(_key_config: &SharedKeyConfig) -> String {
+    "Push".to_string()
+}
+pub fn push_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "pushing to upstream...".to_string()
+}
+pub fn fetch_popup_title(_key_config: &SharedKeyConfig) -> String {
+    "Fetch".to_string()
+}
+pub fn pull_popup_title(_key_config: &SharedKeyConfig) -> String {
+    "Pull".to_string()
+}
+pub fn pull_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "pulling from upstream...".to_string()
+}
 
 pub mod commit {
     use crate::keys::SharedKeyConfig;
+    use asyncgit::sync::SignatureStatus;
     pub fn details_author(_key_config: &SharedKeyConfig) -> String {
         "Author: ".to_string()
     }
@@ -140,6 +579,31 @@ pub mod commit {
     pub fn details_tags(_key_config: &SharedKeyConfig) -> String {
         "Tags: ".to_string()
     }
+    pub fn details_signature(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        "Signature: ".to_string()
+    }
+    pub fn signature_status(
+        _key_config: &SharedKeyConfig,
+        status: &SignatureStatus,
+    ) -> String {
+        match status {
+            SignatureStatus::Good(signer) => {
+                format!("good signature by {}", signer)
+            }
+            SignatureStatus::Bad => "BAD signature".to_string(),
+            SignatureStatus::UnknownKey => {
+                "signed with an unknown key".to_string()
+            }
+            SignatureStatus::Unsigned => "unsigned".to_string(),
+        }
+    }
+    pub fn signature_checking(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        "checking...".to_string()
+    }
     pub fn details_info_title(
         _key_config: &SharedKeyConfig,
     ) -> String {
@@ -168,6 +632,7 @@ pub mod commands {
     static CMD_GROUP_STASHING: &str = "-- Stashing --";
     static CMD_GROUP_STASHES: &str = "-- Stashes --";
     static CMD_GROUP_LOG: &str = "-- Log --";
+    static CMD_GROUP_BRANCHES: &str = "-- Branches --";
 
     pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
@@ -181,11 +646,12 @@ pub mod commands {
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Tab [{}{}{}{}]",
+                "Tab [{}{}{}{}{}]",
                 get_hint(key_config.tab_status),
                 get_hint(key_config.tab_log),
                 get_hint(key_config.tab_stashing),
                 get_hint(key_config.tab_stashes),
+                get_hint(key_config.tab_branches),
             ),
             "switch top level tabs directly",
             CMD_GROUP_GENERAL,
@@ -229,63 +695,212 @@ pub mod commands {
     pub fn scroll(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Scroll [{}{}]",
-                get_hint(key_config.focus_above),
-                get_hint(key_config.focus_below)
+                "Scroll [{}{}]",
+                get_hint(key_config.focus_above),
+                get_hint(key_config.focus_below)
+            ),
+            "scroll up or down in focused view",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn copy(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Copy [{}]", get_hint(key_config.copy),),
+            "copy selected lines to clipboard",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_home_end(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Jump up/down [{},{},{},{}]",
+                get_hint(key_config.home),
+                get_hint(key_config.end),
+                get_hint(key_config.move_up),
+                get_hint(key_config.move_down)
+            ),
+            "scroll to top or bottom of diff",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_hunk_add(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Add hunk [{}]", get_hint(key_config.enter),),
+            "adds selected hunk to stage",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_hunk_revert(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Revert hunk [{}]",
+                get_hint(key_config.status_reset_item),
+            ),
+            "reverts selected hunk",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_hunk_remove(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Remove hunk [{}]", get_hint(key_config.enter),),
+            "removes selected hunk from stage",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_lines_stage(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Stage lines [{}]",
+                get_hint(key_config.diff_stage_lines),
+            ),
+            "adds selected lines to stage",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_lines_unstage(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Unstage lines [{}]",
+                get_hint(key_config.diff_stage_lines),
+            ),
+            "removes selected lines from stage",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_lines_revert(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Revert lines [{}]",
+                get_hint(key_config.diff_reset_lines),
+            ),
+            "reverts selected lines",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_toggle_split(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Split view [{}]",
+                get_hint(key_config.diff_toggle_split),
+            ),
+            "toggles between unified and side-by-side diff",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_options(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Options [{}]", get_hint(key_config.diff_options)),
+            "choose whitespace handling, context lines, algorithm and rename detection of diffs",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_options_toggle(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Toggle [{}]", get_hint(key_config.enter)),
+            "toggle the selected diff option",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_options_change(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Less/More [{}{}]",
+                get_hint(key_config.move_left),
+                get_hint(key_config.move_right)
+            ),
+            "change the number of context lines or the similarity",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn conflict_take_ours(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Take Ours [{}]",
+                get_hint(key_config.conflict_take_ours),
             ),
-            "scroll up or down in focused view",
-            CMD_GROUP_GENERAL,
+            "take ours for selected conflict",
+            CMD_GROUP_DIFF,
         )
     }
-    pub fn copy(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn conflict_file_ours(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Copy [{}]", get_hint(key_config.copy),),
-            "copy selected lines to clipboard",
+            format!(
+                "File Ours [{}]",
+                get_hint(key_config.conflict_file_ours),
+            ),
+            "take ours for the whole file and stage it",
             CMD_GROUP_DIFF,
         )
     }
-    pub fn diff_home_end(
+    pub fn conflict_take_theirs(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Jump up/down [{},{},{},{}]",
-                get_hint(key_config.home),
-                get_hint(key_config.end),
-                get_hint(key_config.move_up),
-                get_hint(key_config.move_down)
+                "Take Theirs [{}]",
+                get_hint(key_config.conflict_take_theirs),
             ),
-            "scroll to top or bottom of diff",
+            "take theirs for selected conflict",
             CMD_GROUP_DIFF,
         )
     }
-    pub fn diff_hunk_add(
+    pub fn conflict_file_theirs(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Add hunk [{}]", get_hint(key_config.enter),),
-            "adds selected hunk to stage",
+            format!(
+                "File Theirs [{}]",
+                get_hint(key_config.conflict_file_theirs),
+            ),
+            "take theirs for the whole file and stage it",
             CMD_GROUP_DIFF,
         )
     }
-    pub fn diff_hunk_revert(
+    pub fn conflict_take_both(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Revert hunk [{}]",
-                get_hint(key_config.status_reset_item),
+                "Take Both [{}]",
+                get_hint(key_config.conflict_take_both),
             ),
-            "reverts selected hunk",
+            "take ours then theirs for selected conflict",
             CMD_GROUP_DIFF,
         )
     }
-    pub fn diff_hunk_remove(
+    pub fn conflict_file_both(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Remove hunk [{}]", get_hint(key_config.enter),),
-            "removes selected hunk from stage",
+            format!(
+                "File Both [{}]",
+                get_hint(key_config.conflict_file_both),
+            ),
+            "take ours then theirs for all conflicts of the file and stage it",
             CMD_GROUP_DIFF,
         )
     }
@@ -584,6 +1199,76 @@ pub mod commands {
             CMD_GROUP_GENERAL,
         )
     }
+    pub fn branch_checkout(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Checkout [{}]", get_hint(key_config.enter),),
+            "checkout selected branch",
+            CMD_GROUP_BRANCHES,
+        )
+    }
+    pub fn branch_merge(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Merge [{}]", get_hint(key_config.merge_branch),),
+            "merge selected branch into HEAD",
+            CMD_GROUP_BRANCHES,
+        )
+    }
+    pub fn branch_rename(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Rename [{}]",
+                get_hint(key_config.rename_branch),
+            ),
+            "rename selected branch",
+            CMD_GROUP_BRANCHES,
+        )
+    }
+    pub fn branch_delete(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Delete [{}]",
+                get_hint(key_config.delete_branch),
+            ),
+            "delete selected branch",
+            CMD_GROUP_BRANCHES,
+        )
+    }
+    pub fn branch_set_upstream(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Upstream [{}]",
+                get_hint(key_config.set_branch_upstream),
+            ),
+            "set upstream of selected branch",
+            CMD_GROUP_BRANCHES,
+        )
+    }
+    pub fn rename_branch_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Rename Branch [{}]", get_hint(key_config.enter),),
+            "rename branch",
+            CMD_GROUP_BRANCHES,
+        )
+    }
+    pub fn set_upstream_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Set Upstream [{}]", get_hint(key_config.enter),),
+            "set upstream",
+            CMD_GROUP_BRANCHES,
+        )
+    }
     pub fn open_branch_create_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
@@ -596,4 +1281,300 @@ pub mod commands {
             CMD_GROUP_GENERAL,
         )
     }
+    pub fn push(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Push [{}]", get_hint(key_config.push),),
+            "push current branch to its upstream",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn fetch(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Fetch [{}]", get_hint(key_config.fetch),),
+            "fetch all remotes",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn pull(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Pull [{}]", get_hint(key_config.pull),),
+            "fetch and integrate upstream of current branch",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn abort_merge(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Abort Merge [{}]",
+                get_hint(key_config.abort_merge),
+            ),
+            "abort merge and reset working tree to HEAD",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn continue_merge(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Continue Merge [{}]",
+                get_hint(key_config.continue_merge),
+            ),
+            "commit the resolved merge",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn log_mark_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Mark [{}]",
+                get_hint(key_config.log_mark_commit),
+            ),
+            "mark commit to cherry-pick or revert a range",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_cherry_pick(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Cherry-pick [{}]",
+                get_hint(key_config.log_cherry_pick),
+            ),
+            "apply marked or selected commits onto HEAD",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_revert(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Revert [{}]", get_hint(key_config.log_revert),),
+            "revert marked or selected commits",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_reset_head(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset [{}]",
+                get_hint(key_config.log_reset_head),
+            ),
+            "reset HEAD to the selected commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn blame_file(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Blame [{}]", get_hint(key_config.blame)),
+            "open blame view of the selected file",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn blame_parent(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Blame parent [{}]",
+                get_hint(key_config.blame_parent)
+            ),
+            "blame again before the selected line was changed",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn file_history(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "History [{}]",
+                get_hint(key_config.file_history)
+            ),
+            "show the commits changing the selected file",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn log_search(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Search [{}]", get_hint(key_config.log_search)),
+            "search commits by message, author, date or sha",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_next(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Next/Prev match [{}{}]",
+                get_hint(key_config.log_search_next),
+                get_hint(key_config.log_search_prev),
+            ),
+            "jump to the next or previous search match",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_filter(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Filter [{}]",
+                get_hint(key_config.log_search_filter)
+            ),
+            "toggle showing only the commits matching the search",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_clear(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Clear search [{}]",
+                get_hint(key_config.exit_popup)
+            ),
+            "clear the search of the log",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Search [{}]", get_hint(key_config.enter)),
+            "search the log, an empty query clears the search",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_pickaxe(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Find content [{}]",
+                get_hint(key_config.log_pickaxe)
+            ),
+            "find commits adding or removing a string",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn pickaxe_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Find [{}]", get_hint(key_config.enter)),
+            "walk the history for commits changing the content",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_options(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Options [{}]", get_hint(key_config.log_options)),
+            "choose branches, merges and order of the log",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_options_toggle(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Toggle [{}]", get_hint(key_config.enter)),
+            "toggle the selected log option",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn open_reflog(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Reflog [{}]", get_hint(key_config.open_reflog)),
+            "browse the reflog of HEAD to inspect or restore former states",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn reset_head_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Reset [{}]", get_hint(key_config.enter)),
+            "reset HEAD using the selected mode",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_interactive(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Rebase [{}]",
+                get_hint(key_config.rebase_interactive),
+            ),
+            "interactively rebase commits above the selected one",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_set_action(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Pick/Reword/Squash/Fixup/Drop/Edit [{}{}{}{}{}{}]",
+                get_hint(key_config.rebase_pick),
+                get_hint(key_config.rebase_reword),
+                get_hint(key_config.rebase_squash),
+                get_hint(key_config.rebase_fixup),
+                get_hint(key_config.rebase_drop),
+                get_hint(key_config.rebase_edit),
+            ),
+            "set action of the selected commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_move(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Move [{}{}]",
+                get_hint(key_config.shift_up),
+                get_hint(key_config.shift_down),
+            ),
+            "move the selected commit up or down",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_start(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!("Start [{}]", get_hint(key_config.enter),),
+            "start rebasing with this plan",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_continue(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Continue Rebase [{}]",
+                get_hint(key_config.rebase_continue),
+            ),
+            "continue the rebase with the staged changes",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn rebase_skip(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Skip Commit [{}]",
+                get_hint(key_config.rebase_skip),
+            ),
+            "drop the commit the rebase stopped on",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn rebase_abort(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Abort Rebase [{}]",
+                get_hint(key_config.rebase_abort),
+            ),
+            "abort rebase and restore the branch",
+            CMD_GROUP_GENERAL,
+        )
+    }
 }
@@ -0,0 +1,507 @@
+use crate::{
+    components::{
+        visibility_blocking, CommandBlocking, CommandInfo, Component,
+        DrawableComponent, ScrollType,
+    },
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{calc_scroll_top, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, BranchInfo, HookResult, MergeResult},
+    CWD,
+};
+use crossterm::event::Event;
+use std::{borrow::Cow, cell::Cell, cmp};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    widgets::{Block, Borders, Paragraph, Text},
+    Frame,
+};
+
+const NAME_WIDTH_MAX: usize = 30;
+
+/// lists local and remote-tracking branches
+pub struct BranchList {
+    branches: Vec<BranchInfo>,
+    selection: usize,
+    visible: bool,
+    current_height: Cell<usize>,
+    scroll_top: Cell<usize>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl BranchList {
+    ///
+    pub fn new(
+        queue: &Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            branches: Vec::new(),
+            selection: 0,
+            visible: false,
+            current_height: Cell::new(0),
+            scroll_top: Cell::new(0),
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn update(&mut self) -> Result<()> {
+        if self.visible {
+            self.branches = sync::get_branches_info(CWD)?;
+            self.selection = cmp::min(
+                self.selection,
+                self.branches.len().saturating_sub(1),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn selected_branch(&self) -> Option<&BranchInfo> {
+        self.branches.get(self.selection)
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) -> bool {
+        let max = self.branches.len().saturating_sub(1);
+        let page = self.current_height.get().saturating_sub(1).max(1);
+
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_sub(1),
+            ScrollType::Down => self.selection.saturating_add(1),
+            ScrollType::PageUp => self.selection.saturating_sub(page),
+            ScrollType::PageDown => {
+                self.selection.saturating_add(page)
+            }
+            ScrollType::Home => 0,
+            ScrollType::End => max,
+        };
+
+        let new_selection = cmp::min(new_selection, max);
+        let changed = new_selection != self.selection;
+        self.selection = new_selection;
+
+        changed
+    }
+
+    fn checkout(&self) -> Result<()> {
+        if let Some(branch) = self.selected_branch() {
+            if branch.is_head {
+                return Ok(());
+            }
+
+            let branch_ref = branch.reference.clone();
+
+            if sync::is_workdir_clean(CWD)? {
+                Self::checkout_branch(&self.queue, &branch_ref);
+            } else {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ConfirmAction(
+                        Action::StashAndCheckout(branch_ref),
+                    ),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// checks out `branch_ref` reporting errors via the `queue`
+    pub fn checkout_branch(queue: &Queue, branch_ref: &str) {
+        let previous = sync::get_head(CWD).ok();
+        match sync::checkout_branch(CWD, branch_ref) {
+            Ok(()) => {
+                if let (Some(previous), Ok(new)) =
+                    (previous, sync::get_head(CWD))
+                {
+                    Self::run_hook(
+                        queue,
+                        "post-checkout",
+                        sync::hooks_post_checkout(CWD, previous, new),
+                    );
+                }
+                queue.borrow_mut().push_back(InternalEvent::Update(
+                    NeedsUpdate::ALL,
+                ));
+            }
+            Err(e) => {
+                queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "checkout error:\n{}",
+                        e,
+                    )),
+                );
+            }
+        }
+    }
+
+    /// stashes local changes before checking out `branch_ref`
+    pub fn stash_and_checkout(queue: &Queue, branch_ref: &str) {
+        let msg = format!("before checkout of '{}'", branch_ref);
+        match sync::stash_save(CWD, Some(&msg), false, false) {
+            Ok(_) => Self::checkout_branch(queue, branch_ref),
+            Err(e) => {
+                queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "stash error:\n{}",
+                        e,
+                    )),
+                );
+            }
+        }
+    }
+
+    fn merge(&self) {
+        if let Some(branch) = self.selected_branch() {
+            if branch.is_head {
+                return;
+            }
+
+            match sync::merge_branch(CWD, &branch.reference) {
+                Ok(MergeResult::Conflict) => {
+                    // resolving happens in the status tab
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::TabSwitch);
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::ALL),
+                    );
+                }
+                Ok(MergeResult::UpToDate) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::ALL),
+                    );
+                }
+                Ok(
+                    MergeResult::FastForward | MergeResult::Merged,
+                ) => {
+                    Self::run_hook(
+                        &self.queue,
+                        "post-merge",
+                        sync::hooks_post_merge(CWD, false),
+                    );
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::ALL),
+                    );
+                }
+                Err(e) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "merge error:\n{}",
+                            e,
+                        )),
+                    );
+                }
+            }
+        }
+    }
+
+    /// reports a failing hook, its result cannot undo the operation
+    fn run_hook<E: std::fmt::Display>(
+        queue: &Queue,
+        hook: &str,
+        res: Result<HookResult, E>,
+    ) {
+        let err = match res {
+            Ok(HookResult::Ok) => return,
+            Ok(HookResult::NotOk(e)) => e,
+            Err(e) => e.to_string(),
+        };
+
+        log::error!("{} hook error: {}", hook, err);
+        queue.borrow_mut().push_back(InternalEvent::ShowErrorMsg(
+            format!("{} hook error:\n{}", hook, err),
+        ));
+    }
+
+    fn delete(&self) -> Result<()> {
+        if let Some(branch) = self.selected_branch() {
+            let branch_ref = branch.reference.clone();
+
+            if sync::branch_is_merged(CWD, &branch_ref)? {
+                Self::delete_branch(&self.queue, &branch_ref);
+            } else {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ConfirmAction(
+                        Action::DeleteBranch(branch_ref),
+                    ),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// deletes `branch_ref` reporting errors via the `queue`
+    pub fn delete_branch(queue: &Queue, branch_ref: &str) {
+        match sync::delete_branch(CWD, branch_ref) {
+            Ok(()) => {
+                queue.borrow_mut().push_back(InternalEvent::Update(
+                    NeedsUpdate::ALL,
+                ));
+            }
+            Err(e) => {
+                queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "delete branch error:\n{}",
+                        e,
+                    )),
+                );
+            }
+        }
+    }
+
+    fn rename(&self) {
+        if let Some(branch) = self.selected_branch() {
+            if branch.is_local {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::RenameBranch(
+                        branch.reference.clone(),
+                        branch.name.clone(),
+                    ),
+                );
+            }
+        }
+    }
+
+    fn set_upstream(&self) -> Result<()> {
+        if let Some(branch) = self.selected_branch() {
+            if branch.is_local {
+                let upstream = if branch.upstream.is_some() {
+                    branch.upstream.clone()
+                } else {
+                    sync::get_remotes(CWD)?.first().map(|remote| {
+                        format!("{}/{}", remote, branch.name)
+                    })
+                };
+
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::SetUpstream(
+                        branch.reference.clone(),
+                        upstream,
+                    ),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_text(&self, height: usize) -> Vec<Text> {
+        let name_width = self
+            .branches
+            .iter()
+            .map(|b| b.name.chars().count())
+            .max()
+            .unwrap_or_default()
+            .min(NAME_WIDTH_MAX);
+
+        let mut txt = Vec::with_capacity(height * 6);
+
+        for (idx, b) in self
+            .branches
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+        {
+            let selected = idx == self.selection;
+
+            let name = format!(
+                "{} {:w$} ",
+                if b.is_head { "*" } else { " " },
+                b.name,
+                w = name_width
+            );
+
+            let hash: String =
+                b.top_commit.to_string().chars().take(7).collect();
+
+            let tracking = match (&b.upstream, b.ahead_behind) {
+                (Some(upstream), Some((ahead, behind))) => format!(
+                    " [{} \u{2191}{} \u{2193}{}]",
+                    upstream, ahead, behind
+                ),
+                (Some(upstream), None) => format!(" [{}]", upstream),
+                _ => String::new(),
+            };
+
+            txt.push(Text::Styled(
+                Cow::from(name),
+                self.theme.text(true, selected),
+            ));
+            txt.push(Text::Styled(
+                Cow::from(hash),
+                self.theme.commit_hash(selected),
+            ));
+            txt.push(Text::Styled(
+                Cow::from(tracking),
+                self.theme.tags(selected),
+            ));
+            txt.push(Text::Styled(
+                Cow::from(format!(" {}", b.top_commit_message)),
+                self.theme.text(true, selected),
+            ));
+            txt.push(Text::Raw(Cow::from("\n")));
+        }
+
+        txt
+    }
+}
+
+impl DrawableComponent for BranchList {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        let height = rect.height.saturating_sub(2) as usize;
+        self.current_height.set(height);
+
+        self.scroll_top.set(calc_scroll_top(
+            self.scroll_top.get(),
+            height,
+            self.selection,
+        ));
+
+        let title = format!(
+            "{} {}/{}",
+            strings::branchlist_title(&self.key_config),
+            self.selection.saturating_add(1).min(self.branches.len()),
+            self.branches.len(),
+        );
+
+        f.render_widget(
+            Paragraph::new(self.get_text(height).iter())
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(title.as_str())
+                        .border_style(self.theme.block(true))
+                        .title_style(self.theme.title(true)),
+                )
+                .alignment(Alignment::Left),
+            rect,
+        );
+
+        Ok(())
+    }
+}
+
+impl Component for BranchList {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            let selected = self.selected_branch();
+            let selected_local =
+                selected.map_or(false, |b| b.is_local);
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                selected.is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::branch_checkout(&self.key_config),
+                selected.map_or(false, |b| !b.is_head),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::branch_merge(&self.key_config),
+                selected.map_or(false, |b| !b.is_head),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::branch_rename(&self.key_config),
+                selected_local,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::branch_delete(&self.key_config),
+                selected.map_or(false, |b| !b.is_head),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::branch_set_upstream(
+                    &self.key_config,
+                ),
+                selected_local,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(k) = ev {
+                if k == self.key_config.move_up {
+                    return Ok(self.move_selection(ScrollType::Up));
+                } else if k == self.key_config.move_down {
+                    return Ok(self.move_selection(ScrollType::Down));
+                } else if k == self.key_config.shift_up
+                    || k == self.key_config.home
+                {
+                    return Ok(self.move_selection(ScrollType::Home));
+                } else if k == self.key_config.shift_down
+                    || k == self.key_config.end
+                {
+                    return Ok(self.move_selection(ScrollType::End));
+                } else if k == self.key_config.page_up {
+                    return Ok(
+                        self.move_selection(ScrollType::PageUp)
+                    );
+                } else if k == self.key_config.page_down {
+                    return Ok(
+                        self.move_selection(ScrollType::PageDown)
+                    );
+                } else if k == self.key_config.enter {
+                    self.checkout()?;
+                    return Ok(true);
+                } else if k == self.key_config.merge_branch {
+                    self.merge();
+                    return Ok(true);
+                } else if k == self.key_config.delete_branch {
+                    self.delete()?;
+                    return Ok(true);
+                } else if k == self.key_config.rename_branch {
+                    self.rename();
+                    return Ok(true);
+                } else if k == self.key_config.set_branch_upstream {
+                    self.set_upstream()?;
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.update()?;
+        Ok(())
+    }
+}
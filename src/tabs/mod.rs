@@ -1,8 +1,10 @@
+mod branchlist;
 mod revlog;
 mod stashing;
 mod stashlist;
 mod status;
 
+pub use branchlist::BranchList;
 pub use revlog::Revlog;
 pub use stashing::{Stashing, StashingOptions};
 pub use stashlist::StashList;
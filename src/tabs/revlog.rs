@@ -12,12 +12,14 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
     cached,
-    sync::{self, CommitId},
-    AsyncLog, AsyncNotification, AsyncTags, FetchStatus, CWD,
+    sync::{
+        self, CommitGraph, CommitId, LogOptions, LogScope, LogSearch,
+    },
+    AsyncLog, AsyncNotification, AsyncRefs, FetchStatus, CWD,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
-use std::time::Duration;
+use std::{collections::HashSet, time::Duration};
 use sync::CommitTags;
 use tui::{
     backend::Backend,
@@ -27,12 +29,25 @@ use tui::{
 
 const SLICE_SIZE: usize = 1200;
 
+/// active search of the log
+struct SearchState {
+    query: String,
+    log: AsyncLog,
+    /// amount of matches already highlighted in the list
+    highlighted: usize,
+}
+
 ///
 pub struct Revlog {
     commit_details: CommitDetailsComponent,
     list: CommitList,
     git_log: AsyncLog,
-    git_tags: AsyncTags,
+    options: LogOptions,
+    search: Option<SearchState>,
+    /// list only shows the matches of `search`
+    filtered: bool,
+    sender: Sender<AsyncNotification>,
+    git_refs: AsyncRefs,
     queue: Queue,
     visible: bool,
     branch_name: cached::BranchName,
@@ -56,12 +71,19 @@ impl Revlog {
                 key_config.clone(),
             ),
             list: CommitList::new(
-                &strings::log_title(&key_config),
+                &strings::log_title(
+                    &key_config,
+                    &LogOptions::default(),
+                ),
                 theme,
                 key_config.clone(),
             ),
             git_log: AsyncLog::new(sender),
-            git_tags: AsyncTags::new(sender),
+            options: LogOptions::default(),
+            search: None,
+            filtered: false,
+            sender: sender.clone(),
+            git_refs: AsyncRefs::new(sender),
             visible: false,
             branch_name: cached::BranchName::new(CWD),
             key_config,
@@ -71,7 +93,11 @@ impl Revlog {
     ///
     pub fn any_work_pending(&self) -> bool {
         self.git_log.is_pending()
-            || self.git_tags.is_pending()
+            || self
+                .search
+                .as_ref()
+                .map_or(false, |s| s.log.is_pending())
+            || self.git_refs.is_pending()
             || self.commit_details.any_work_pending()
     }
 
@@ -80,8 +106,16 @@ impl Revlog {
         if self.visible {
             let log_changed =
                 self.git_log.fetch()? == FetchStatus::Started;
+            let search_changed = self.update_search()?;
 
-            self.list.set_count_total(self.git_log.count()?);
+            let log_changed = if self.filtered {
+                search_changed
+            } else {
+                log_changed
+            };
+
+            let count = self.active_log().count()?;
+            self.list.set_count_total(count);
 
             let selection = self.list.selection();
             let selection_max = self.list.selection_max();
@@ -91,7 +125,7 @@ impl Revlog {
                 self.fetch_commits()?;
             }
 
-            self.git_tags.request(Duration::from_secs(3), false)?;
+            self.git_refs.request(Duration::from_secs(3), false)?;
 
             self.list.set_branch(
                 self.branch_name.lookup().map(Some).unwrap_or(None),
@@ -116,10 +150,17 @@ impl Revlog {
         if self.visible {
             match ev {
                 AsyncNotification::CommitFiles
+                | AsyncNotification::Signature
                 | AsyncNotification::Log => self.update()?,
-                AsyncNotification::Tags => {
-                    if let Some(tags) = self.git_tags.last()? {
-                        self.list.set_tags(tags);
+                AsyncNotification::Refs => {
+                    if let Some(refs) = self.git_refs.last()? {
+                        self.list.set_refs(refs);
+
+                        // only a log of HEAD notices moved refs itself
+                        if self.options.scope != LogScope::Head {
+                            self.restart_logs();
+                        }
+
                         self.update()?;
                     }
                 }
@@ -130,23 +171,261 @@ impl Revlog {
         Ok(())
     }
 
+    /// searches the log, an empty `search` clears the search
+    pub fn search(
+        &mut self,
+        query: String,
+        search: LogSearch,
+    ) -> Result<()> {
+        let selected = self.selected_commit();
+
+        if search.is_empty() {
+            self.search = None;
+            self.filtered = false;
+            self.list.set_highlighting(None);
+        } else {
+            let mut log = AsyncLog::with_search(&self.sender, search);
+            log.set_options(self.options.clone());
+
+            self.search = Some(SearchState {
+                query,
+                log,
+                highlighted: 0,
+            });
+            self.list.set_highlighting(Some(HashSet::new()));
+        }
+
+        self.update_title()?;
+        self.reselect(selected)
+    }
+
+    /// restarts the log and a running search with `options`
+    pub fn set_options(&mut self, options: LogOptions) -> Result<()> {
+        let selected = self.selected_commit();
+
+        self.options = options;
+        self.git_log.set_options(self.options.clone());
+        if let Some(search) = self.search.as_mut() {
+            search.log.set_options(self.options.clone());
+        }
+
+        self.update_title()?;
+        self.reselect(selected)
+    }
+
+    fn restart_logs(&mut self) {
+        self.git_log.restart();
+        if let Some(search) = self.search.as_mut() {
+            search.log.restart();
+        }
+    }
+
+    fn clear_search(&mut self) -> Result<()> {
+        self.search(String::new(), LogSearch::default())
+    }
+
+    fn toggle_filter(&mut self) -> Result<()> {
+        let selected = self.selected_commit();
+        self.filtered = !self.filtered;
+        self.update_title()?;
+        self.reselect(selected)
+    }
+
+    /// selects `id` in the now active log, or the first entry
+    fn reselect(&mut self, id: Option<CommitId>) -> Result<()> {
+        let log = self.active_log();
+        let count = log.count()?;
+        let position = id.and_then(|id| {
+            log.get_slice(0, count)
+                .ok()
+                .and_then(|ids| ids.iter().position(|e| *e == id))
+        });
+
+        self.list.clear();
+        self.list.set_count_total(count);
+        self.list.set_selection(position.unwrap_or_default());
+
+        self.update()
+    }
+
+    fn active_log(&mut self) -> &mut AsyncLog {
+        match self.search {
+            Some(ref mut search) if self.filtered => &mut search.log,
+            _ => &mut self.git_log,
+        }
+    }
+
+    /// fetches the search log and highlights new matches,
+    /// returns whether the search (re)started
+    fn update_search(&mut self) -> Result<bool> {
+        let mut started = false;
+
+        if let Some(search) = self.search.as_mut() {
+            if search.log.fetch()? == FetchStatus::Started {
+                started = true;
+                search.highlighted = 0;
+                self.list.set_highlighting(Some(HashSet::new()));
+            }
+
+            let count = search.log.count()?;
+            if count > search.highlighted {
+                let ids = search.log.get_slice(
+                    search.highlighted,
+                    count - search.highlighted,
+                )?;
+                search.highlighted += ids.len();
+                self.list.add_highlighting(&ids);
+            }
+        }
+
+        self.update_title()?;
+
+        Ok(started)
+    }
+
+    fn update_title(&mut self) -> Result<()> {
+        let title = if let Some(search) = self.search.as_mut() {
+            strings::log_search_title(
+                &self.key_config,
+                &search.query,
+                search.log.count()?,
+                self.filtered,
+            )
+        } else {
+            strings::log_title(&self.key_config, &self.options)
+        };
+
+        self.list.set_title(title);
+
+        Ok(())
+    }
+
+    /// selects the next or previous search match
+    fn jump_to_match(&mut self, forward: bool) -> Result<()> {
+        let selection = self.list.selection();
+
+        let target = if self.filtered {
+            if forward {
+                Some(selection + 1)
+            } else {
+                selection.checked_sub(1)
+            }
+        } else {
+            self.find_match(selection, forward)?
+        };
+
+        if let Some(target) = target {
+            self.list.set_selection(target);
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    /// position of the closest highlighted commit after (or
+    /// before) `from` in the unfiltered log
+    fn find_match(
+        &self,
+        from: usize,
+        forward: bool,
+    ) -> Result<Option<usize>> {
+        if forward {
+            let mut start = from + 1;
+            loop {
+                let ids =
+                    self.git_log.get_slice(start, SLICE_SIZE)?;
+                if ids.is_empty() {
+                    return Ok(None);
+                }
+                if let Some(pos) = ids
+                    .iter()
+                    .position(|id| self.list.is_highlighted(id))
+                {
+                    return Ok(Some(start + pos));
+                }
+                start += ids.len();
+            }
+        } else {
+            let mut end = from;
+            while end > 0 {
+                let start = end.saturating_sub(SLICE_SIZE);
+                let ids =
+                    self.git_log.get_slice(start, end - start)?;
+                if let Some(pos) = ids
+                    .iter()
+                    .rposition(|id| self.list.is_highlighted(id))
+                {
+                    return Ok(Some(start + pos));
+                }
+                end = start;
+            }
+
+            Ok(None)
+        }
+    }
+
     fn fetch_commits(&mut self) -> Result<()> {
-        let want_min =
+        // matches of a search are not connected to draw a graph,
+        // the log itself knows whether its walk has one
+        let with_graph = !self.filtered;
+
+        let mut want_min =
             self.list.selection().saturating_sub(SLICE_SIZE / 2);
+        if with_graph {
+            want_min = CommitGraph::slice_start(want_min);
+        }
+
+        let ids =
+            self.active_log().get_slice(want_min, SLICE_SIZE)?;
 
         let commits = sync::get_commits_info(
             CWD,
-            &self.git_log.get_slice(want_min, SLICE_SIZE)?,
+            &ids,
             self.list.current_size().0.into(),
         );
 
         if let Ok(commits) = commits {
+            let rows = if with_graph {
+                self.git_log.graph_rows(want_min, &commits)?
+            } else {
+                None
+            };
+
             self.list.items().set_items(want_min, commits);
+
+            if let Some(rows) = rows {
+                self.list.items().set_graph(rows);
+            }
         }
 
         Ok(())
     }
 
+    /// marked commits or else the selected one
+    fn commits_to_apply(&self) -> Vec<CommitId> {
+        if self.list.marked().is_empty() {
+            self.selected_commit().into_iter().collect()
+        } else {
+            self.list.marked().to_vec()
+        }
+    }
+
+    fn apply_commits(&mut self, revert: bool) -> bool {
+        let ids = self.commits_to_apply();
+        if ids.is_empty() {
+            return false;
+        }
+
+        self.list.clear_marked();
+        self.queue.borrow_mut().push_back(if revert {
+            InternalEvent::Revert(ids)
+        } else {
+            InternalEvent::CherryPick(ids)
+        });
+
+        true
+    }
+
     fn selected_commit(&self) -> Option<CommitId> {
         self.list.selected_entry().map(|e| e.id)
     }
@@ -155,10 +434,12 @@ impl Revlog {
         &self,
         commit: &Option<CommitId>,
     ) -> Option<CommitTags> {
-        let tags = self.list.tags();
+        let refs = self.list.refs();
 
         commit.and_then(|commit| {
-            tags.and_then(|tags| tags.get(&commit).cloned())
+            refs.and_then(|refs| refs.get(&commit))
+                .map(|refs| sync::tags_of(refs))
+                .filter(|tags| !tags.is_empty())
         })
     }
 }
@@ -192,6 +473,7 @@ impl DrawableComponent for Revlog {
 }
 
 impl Component for Revlog {
+    #[allow(clippy::too_many_lines)]
     fn event(&mut self, ev: Event) -> Result<bool> {
         if self.visible {
             let event_used = self.list.event(ev)?;
@@ -236,6 +518,85 @@ impl Component for Revlog {
                         .borrow_mut()
                         .push_back(InternalEvent::CreateBranch);
                     return Ok(true);
+                } else if k == self.key_config.push {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::Push);
+                    return Ok(true);
+                } else if k == self.key_config.fetch {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::Fetch);
+                    return Ok(true);
+                } else if k == self.key_config.log_cherry_pick {
+                    return Ok(self.apply_commits(false));
+                } else if k == self.key_config.log_revert {
+                    return Ok(self.apply_commits(true));
+                } else if k == self.key_config.log_reset_head {
+                    return self.selected_commit().map_or(
+                        Ok(false),
+                        |id| {
+                            self.queue.borrow_mut().push_back(
+                                InternalEvent::ResetHead(id),
+                            );
+                            Ok(true)
+                        },
+                    );
+                } else if k == self.key_config.log_search {
+                    let query = self
+                        .search
+                        .as_ref()
+                        .map(|s| s.query.clone())
+                        .unwrap_or_default();
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::OpenLogSearch(query),
+                    );
+                    return Ok(true);
+                } else if (k == self.key_config.log_search_next
+                    || k == self.key_config.log_search_prev)
+                    && self.search.is_some()
+                {
+                    self.jump_to_match(
+                        k == self.key_config.log_search_next,
+                    )?;
+                    return Ok(true);
+                } else if k == self.key_config.log_search_filter
+                    && self.search.is_some()
+                {
+                    self.toggle_filter()?;
+                    return Ok(true);
+                } else if k == self.key_config.exit_popup
+                    && self.search.is_some()
+                {
+                    self.clear_search()?;
+                    return Ok(true);
+                } else if k == self.key_config.log_options {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::OpenLogOptions(
+                            self.options.clone(),
+                        ),
+                    );
+                    return Ok(true);
+                } else if k == self.key_config.log_pickaxe {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenPickaxe);
+                    return Ok(true);
+                } else if k == self.key_config.open_reflog {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenReflog);
+                    return Ok(true);
+                } else if k == self.key_config.rebase_interactive {
+                    return self.selected_commit().map_or(
+                        Ok(false),
+                        |id| {
+                            self.queue.borrow_mut().push_back(
+                                InternalEvent::OpenRebasePlan(id),
+                            );
+                            Ok(true)
+                        },
+                    );
                 }
             }
         }
@@ -279,6 +640,84 @@ impl Component for Revlog {
             self.visible || force_all,
         ));
 
+        out.push(CommandInfo::new(
+            strings::commands::push(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::fetch(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_cherry_pick(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_revert(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_reset_head(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_next(&self.key_config),
+            true,
+            (self.visible && self.search.is_some()) || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_filter(&self.key_config),
+            true,
+            (self.visible && self.search.is_some()) || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_clear(&self.key_config),
+            true,
+            (self.visible && self.search.is_some()) || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_options(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_pickaxe(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::open_reflog(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::rebase_interactive(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
         visibility_blocking(self)
     }
 
@@ -289,6 +728,9 @@ impl Component for Revlog {
     fn hide(&mut self) {
         self.visible = false;
         self.git_log.set_background();
+        if let Some(search) = self.search.as_mut() {
+            search.log.set_background();
+        }
     }
 
     fn show(&mut self) -> Result<()> {
@@ -4,20 +4,22 @@ use crate::{
         command_pump, event_pump, visibility_blocking,
         ChangesComponent, CommandBlocking, CommandInfo, Component,
         DiffComponent, DrawableComponent, FileTreeItemKind,
+        RebasePlanComponent,
     },
     keys::SharedKeyConfig,
-    queue::{InternalEvent, Queue, ResetItem},
+    options::SharedOptions,
+    queue::{Action, InternalEvent, Queue, ResetItem},
     strings::{self, order},
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{self, status::StatusType},
+    sync::{self, status::StatusType, RebaseStatus, RepoState},
     AsyncDiff, AsyncNotification, AsyncStatus, DiffParams, DiffType,
-    StatusParams, CWD,
+    StatusItem, StatusItemType, StatusParams, CWD,
 };
 use crossbeam_channel::Sender;
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent};
 use tui::layout::{Constraint, Direction, Layout};
 
 ///
@@ -26,6 +28,7 @@ enum Focus {
     WorkDir,
     Diff,
     Stage,
+    Conflicts,
 }
 
 ///
@@ -33,6 +36,7 @@ enum Focus {
 enum DiffTarget {
     Stage,
     WorkingDir,
+    Conflicts,
 }
 
 pub struct Status {
@@ -41,12 +45,16 @@ pub struct Status {
     diff_target: DiffTarget,
     index: ChangesComponent,
     index_wd: ChangesComponent,
+    conflicts: ChangesComponent,
+    repo_state: RepoState,
+    rebase: Option<RebaseStatus>,
     diff: DiffComponent,
     git_diff: AsyncDiff,
     git_status_workdir: AsyncStatus,
     git_status_stage: AsyncStatus,
     queue: Queue,
     git_action_executed: bool,
+    options: SharedOptions,
     key_config: SharedKeyConfig,
 }
 
@@ -74,23 +82,42 @@ impl DrawableComponent for Status {
             )
             .split(rect);
 
+        let left = if self.show_conflicts() {
+            let conflict_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Percentage(70),
+                    ]
+                    .as_ref(),
+                )
+                .split(chunks[0]);
+
+            self.conflicts.draw(f, conflict_chunks[0])?;
+
+            conflict_chunks[1]
+        } else {
+            chunks[0]
+        };
+
         let left_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
-                if self.diff_target == DiffTarget::WorkingDir {
+                if self.diff_target == DiffTarget::Stage {
                     [
-                        Constraint::Percentage(60),
                         Constraint::Percentage(40),
+                        Constraint::Percentage(60),
                     ]
                 } else {
                     [
-                        Constraint::Percentage(40),
                         Constraint::Percentage(60),
+                        Constraint::Percentage(40),
                     ]
                 }
                 .as_ref(),
             )
-            .split(chunks[0]);
+            .split(left);
 
         self.index_wd.draw(f, left_chunks[0])?;
         self.index.draw(f, left_chunks[1])?;
@@ -101,12 +128,13 @@ impl DrawableComponent for Status {
 }
 
 impl Status {
-    accessors!(self, [index, index_wd, diff]);
+    accessors!(self, [conflicts, index, index_wd, diff]);
 
     ///
     pub fn new(
         queue: &Queue,
         sender: &Sender<AsyncNotification>,
+        options: SharedOptions,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
@@ -123,6 +151,16 @@ impl Status {
                 theme.clone(),
                 key_config.clone(),
             ),
+            conflicts: ChangesComponent::new(
+                &strings::title_conflicts(&key_config),
+                false,
+                true,
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            repo_state: RepoState::Clean,
+            rebase: None,
             index: ChangesComponent::new(
                 &strings::title_index(&key_config),
                 false,
@@ -133,6 +171,7 @@ impl Status {
             ),
             diff: DiffComponent::new(
                 queue.clone(),
+                options.clone(),
                 theme,
                 key_config.clone(),
                 false,
@@ -141,6 +180,7 @@ impl Status {
             git_status_workdir: AsyncStatus::new(sender.clone()),
             git_status_stage: AsyncStatus::new(sender.clone()),
             git_action_executed: false,
+            options,
             key_config,
         }
     }
@@ -149,10 +189,32 @@ impl Status {
         match self.focus {
             Focus::WorkDir => self.index_wd.is_file_seleted(),
             Focus::Stage => self.index.is_file_seleted(),
+            Focus::Conflicts => self.conflicts.is_file_seleted(),
             Focus::Diff => false,
         }
     }
 
+    /// conflicts get their own section while merging
+    /// or as long as there are any left
+    fn show_conflicts(&self) -> bool {
+        self.is_merging() || !self.conflicts.is_empty()
+    }
+
+    /// merge, cherry-pick or revert in progress,
+    /// all of them get continued by committing
+    const fn is_merging(&self) -> bool {
+        matches!(
+            self.repo_state,
+            RepoState::Merge
+                | RepoState::CherryPick
+                | RepoState::Revert
+        )
+    }
+
+    const fn is_rebasing(&self) -> bool {
+        self.rebase.is_some()
+    }
+
     fn switch_focus(&mut self, f: Focus) -> Result<bool> {
         if self.focus != f {
             self.focus = f;
@@ -166,9 +228,14 @@ impl Status {
                     self.set_diff_target(DiffTarget::Stage);
                     self.diff.focus(false);
                 }
+                Focus::Conflicts => {
+                    self.set_diff_target(DiffTarget::Conflicts);
+                    self.diff.focus(false);
+                }
                 Focus::Diff => {
                     self.index.focus(false);
                     self.index_wd.focus(false);
+                    self.conflicts.focus(false);
 
                     self.diff.focus(true);
                 }
@@ -184,16 +251,19 @@ impl Status {
 
     fn set_diff_target(&mut self, target: DiffTarget) {
         self.diff_target = target;
-        let is_stage = self.diff_target == DiffTarget::Stage;
-
-        self.index_wd.focus_select(!is_stage);
-        self.index.focus_select(is_stage);
+        self.index_wd
+            .focus_select(self.diff_target == DiffTarget::WorkingDir);
+        self.index
+            .focus_select(self.diff_target == DiffTarget::Stage);
+        self.conflicts
+            .focus_select(self.diff_target == DiffTarget::Conflicts);
     }
 
     pub fn selected_path(&self) -> Option<(String, bool)> {
         let (idx, is_stage) = match self.diff_target {
             DiffTarget::Stage => (&self.index, true),
             DiffTarget::WorkingDir => (&self.index_wd, false),
+            DiffTarget::Conflicts => (&self.conflicts, false),
         };
 
         if let Some(item) = idx.selection() {
@@ -243,11 +313,24 @@ impl Status {
     }
 
     fn update_status(&mut self) -> Result<()> {
-        let stage_status = self.git_status_stage.last()?;
-        self.index.set_items(&stage_status.items)?;
+        self.repo_state = sync::repo_state(CWD)?;
+        self.rebase = sync::rebase_status(CWD)?;
+
+        let (conflicts, stage_items) = Self::split_conflicts(
+            &self.git_status_stage.last()?.items,
+        );
+        self.index.set_items(&stage_items)?;
+
+        let (_, workdir_items) = Self::split_conflicts(
+            &self.git_status_workdir.last()?.items,
+        );
+        self.index_wd.set_items(&workdir_items)?;
+
+        self.conflicts.set_items(&conflicts)?;
 
-        let workdir_status = self.git_status_workdir.last()?;
-        self.index_wd.set_items(&workdir_status.items)?;
+        if self.focus == Focus::Conflicts && !self.show_conflicts() {
+            self.switch_focus(Focus::WorkDir)?;
+        }
 
         self.update_diff()?;
 
@@ -255,12 +338,12 @@ impl Status {
             self.git_action_executed = false;
 
             if self.focus == Focus::WorkDir
-                && workdir_status.items.is_empty()
-                && !stage_status.items.is_empty()
+                && workdir_items.is_empty()
+                && !stage_items.is_empty()
             {
                 self.switch_focus(Focus::Stage)?;
             } else if self.focus == Focus::Stage
-                && stage_status.items.is_empty()
+                && stage_items.is_empty()
             {
                 self.switch_focus(Focus::WorkDir)?;
             }
@@ -269,8 +352,23 @@ impl Status {
         Ok(())
     }
 
+    /// conflicted paths show up in both stage and workdir status,
+    /// returns them separately from the remaining `items`
+    fn split_conflicts(
+        items: &[StatusItem],
+    ) -> (Vec<StatusItem>, Vec<StatusItem>) {
+        items
+            .iter()
+            .cloned()
+            .partition(|i| i.status == StatusItemType::Conflicted)
+    }
+
     ///
     pub fn update_diff(&mut self) -> Result<()> {
+        if self.diff_target == DiffTarget::Conflicts {
+            return self.update_conflict();
+        }
+
         if let Some((path, is_stage)) = self.selected_path() {
             let diff_type = if is_stage {
                 DiffType::Stage
@@ -281,14 +379,20 @@ impl Status {
             let diff_params = DiffParams {
                 path: path.clone(),
                 diff_type,
+                options: self.options.borrow().diff,
             };
 
             if self.diff.current() == (path.clone(), is_stage) {
                 // we are already showing a diff of the right file
                 // maybe the diff changed (outside file change)
+                // or the diff options changed
                 if let Some((params, last)) = self.git_diff.last()? {
                     if params == diff_params {
                         self.diff.update(path, is_stage, last)?;
+                    } else if let Some(diff) =
+                        self.git_diff.request(diff_params)?
+                    {
+                        self.diff.update(path, is_stage, diff)?;
                     }
                 }
             } else {
@@ -308,6 +412,144 @@ impl Status {
         Ok(())
     }
 
+    /// conflicts are not diffed asynchronously,
+    /// the three-way view is built straight from the index
+    fn update_conflict(&mut self) -> Result<()> {
+        if let Some((path, _)) = self.selected_path() {
+            if let Some(conflict) =
+                sync::get_file_conflict(CWD, &path)?
+            {
+                self.diff.update_conflict(path, conflict)?;
+                return Ok(());
+            }
+        }
+
+        self.diff.clear(false)?;
+
+        Ok(())
+    }
+
+    /// called after confirmation
+    pub fn abort_merge(&self) {
+        if let Err(e) = sync::abort_merge(CWD) {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "abort merge failed:\n{}",
+                    e
+                )),
+            );
+        }
+    }
+
+    fn continue_merge(&self) {
+        if self.conflicts.is_empty() {
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::OpenCommit);
+        } else {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(
+                    "resolve and stage all conflicts first"
+                        .to_string(),
+                ),
+            );
+        }
+    }
+
+    /// called after confirmation
+    pub fn abort_rebase(&self) {
+        if let Err(e) = sync::rebase_abort(CWD) {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "abort rebase failed:\n{}",
+                    e
+                )),
+            );
+        }
+    }
+
+    fn continue_rebase(&self) {
+        RebasePlanComponent::handle_progress(
+            &self.queue,
+            sync::rebase_continue(CWD),
+        );
+    }
+
+    fn skip_rebase(&self) {
+        RebasePlanComponent::handle_progress(
+            &self.queue,
+            sync::rebase_skip(CWD),
+        );
+    }
+
+    /// continue/abort commands of a merge or rebase in progress
+    fn operation_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        out.push(CommandInfo::new(
+            strings::commands::abort_merge(&self.key_config),
+            true,
+            (self.visible && self.is_merging()) || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::continue_merge(&self.key_config),
+            self.conflicts.is_empty(),
+            (self.visible && self.is_merging()) || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::rebase_continue(&self.key_config),
+            self.conflicts.is_empty(),
+            (self.visible && self.is_rebasing()) || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::rebase_skip(&self.key_config),
+            true,
+            (self.visible && self.is_rebasing()) || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::rebase_abort(&self.key_config),
+            true,
+            (self.visible && self.is_rebasing()) || force_all,
+        ));
+    }
+
+    /// handles keys continuing/aborting a merge or rebase in progress
+    fn operation_event(&self, k: KeyEvent) -> bool {
+        if k == self.key_config.abort_merge && self.is_merging() {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::AbortMerge),
+            );
+        } else if k == self.key_config.continue_merge
+            && self.is_merging()
+        {
+            self.continue_merge();
+        } else if k == self.key_config.rebase_continue
+            && self.is_rebasing()
+        {
+            self.continue_rebase();
+        } else if k == self.key_config.rebase_skip
+            && self.is_rebasing()
+        {
+            self.skip_rebase();
+        } else if k == self.key_config.rebase_abort
+            && self.is_rebasing()
+        {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::AbortRebase),
+            );
+        } else {
+            return false;
+        }
+
+        true
+    }
+
     /// called after confirmation
     pub fn reset(&mut self, item: &ResetItem) -> bool {
         if let Err(e) = sync::reset_workdir(CWD, item.path.as_str()) {
@@ -370,6 +612,26 @@ impl Component for Status {
             true,
         ));
 
+        out.push(CommandInfo::new(
+            strings::commands::push(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::fetch(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::pull(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        self.operation_commands(out, force_all);
+
         out.push(
             CommandInfo::new(
                 strings::commands::select_status(&self.key_config),
@@ -435,6 +697,7 @@ impl Component for Status {
                     self.switch_focus(match self.diff_target {
                         DiffTarget::Stage => Focus::Stage,
                         DiffTarget::WorkingDir => Focus::WorkDir,
+                        DiffTarget::Conflicts => Focus::Conflicts,
                     })
                 } else if k == self.key_config.move_down
                     && self.focus == Focus::WorkDir
@@ -446,13 +709,37 @@ impl Component for Status {
                     && !self.index_wd.is_empty()
                 {
                     self.switch_focus(Focus::WorkDir)
+                } else if k == self.key_config.move_up
+                    && self.focus == Focus::WorkDir
+                    && !self.conflicts.is_empty()
+                {
+                    self.switch_focus(Focus::Conflicts)
+                } else if k == self.key_config.move_down
+                    && self.focus == Focus::Conflicts
+                {
+                    self.switch_focus(Focus::WorkDir)
                 } else if k == self.key_config.create_branch {
                     self.queue
                         .borrow_mut()
                         .push_back(InternalEvent::CreateBranch);
                     Ok(true)
+                } else if k == self.key_config.push {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::Push);
+                    Ok(true)
+                } else if k == self.key_config.fetch {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::Fetch);
+                    Ok(true)
+                } else if k == self.key_config.pull {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::Pull);
+                    Ok(true)
                 } else {
-                    Ok(false)
+                    Ok(self.operation_event(k))
                 };
             }
         }
@@ -1,6 +1,9 @@
 use crate::get_app_config_path;
 use anyhow::Result;
-use asyncgit::{DiffLineType, StatusItemType};
+use asyncgit::{
+    sync::{RefKind, SignatureStatus},
+    DiffLineType, StatusItemType,
+};
 use ron::{
     de::from_bytes,
     ser::{to_string_pretty, PrettyConfig},
@@ -17,6 +20,7 @@ use tui::style::{Color, Modifier, Style};
 pub type SharedTheme = Rc<Theme>;
 
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
 pub struct Theme {
     #[serde(with = "ColorDef")]
     selected_tab: Color,
@@ -48,6 +52,26 @@ pub struct Theme {
     commit_author: Color,
     #[serde(with = "ColorDef")]
     danger_fg: Color,
+    #[serde(with = "ColorDef")]
+    blame_band_bg: Color,
+    #[serde(with = "ColorDef")]
+    search_match_fg: Color,
+    #[serde(with = "ColorDef")]
+    head_fg: Color,
+    #[serde(with = "ColorDef")]
+    branch_fg: Color,
+    #[serde(with = "ColorDef")]
+    remote_branch_fg: Color,
+    #[serde(with = "ColorDef")]
+    stash_fg: Color,
+    #[serde(with = "ColorDef")]
+    diff_word_add: Color,
+    #[serde(with = "ColorDef")]
+    diff_word_delete: Color,
+    #[serde(with = "ColorDef")]
+    diff_line_add_bg: Color,
+    #[serde(with = "ColorDef")]
+    diff_line_delete_bg: Color,
 }
 
 impl Theme {
@@ -90,6 +114,36 @@ impl Theme {
             })
     }
 
+    pub fn commit_ref(&self, kind: RefKind, selected: bool) -> Style {
+        let fg = match kind {
+            RefKind::Head => self.head_fg,
+            RefKind::LocalBranch => self.branch_fg,
+            RefKind::RemoteBranch => self.remote_branch_fg,
+            RefKind::Tag => return self.tags(selected),
+            RefKind::Stash => self.stash_fg,
+        };
+
+        self.apply_select(
+            Style::default().fg(fg).modifier(Modifier::BOLD),
+            selected,
+        )
+    }
+
+    pub fn signature(&self, status: &SignatureStatus) -> Style {
+        match status {
+            SignatureStatus::Good(_) => {
+                Style::default().fg(self.diff_line_add)
+            }
+            SignatureStatus::Bad => Style::default()
+                .fg(self.danger_fg)
+                .modifier(Modifier::BOLD),
+            SignatureStatus::UnknownKey => {
+                Style::default().fg(self.diff_file_modified)
+            }
+            SignatureStatus::Unsigned => self.text(true, false),
+        }
+    }
+
     pub fn text(&self, enabled: bool, selected: bool) -> Style {
         match (enabled, selected) {
             (false, _) => Style::default().fg(self.disabled_fg),
@@ -115,6 +169,9 @@ impl Theme {
                 Style::default().fg(self.diff_file_moved)
             }
             StatusItemType::Typechange => Style::default(),
+            StatusItemType::Conflicted => Style::default()
+                .fg(self.danger_fg)
+                .modifier(Modifier::BOLD),
         };
 
         self.apply_select(style, selected)
@@ -173,10 +230,66 @@ impl Theme {
         self.apply_select(style, selected)
     }
 
+    /// changed words within an added or deleted line
+    pub fn diff_word(
+        &self,
+        typ: DiffLineType,
+        selected: bool,
+    ) -> Style {
+        let color = match typ {
+            DiffLineType::Add => self.diff_word_add,
+            DiffLineType::Delete => self.diff_word_delete,
+            _ => return self.diff_line(typ, selected),
+        };
+
+        if selected {
+            Style::default()
+                .fg(color)
+                .bg(self.selection_bg)
+                .modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default().fg(color).modifier(Modifier::REVERSED)
+        }
+    }
+
+    /// content coloured by its syntax, added and deleted lines
+    /// keep their colour as background
+    pub fn diff_syntax(
+        &self,
+        typ: DiffLineType,
+        color: Color,
+        selected: bool,
+    ) -> Style {
+        let style = Style::default().fg(color);
+        let style = match typ {
+            DiffLineType::Add => style.bg(self.diff_line_add_bg),
+            DiffLineType::Delete => {
+                style.bg(self.diff_line_delete_bg)
+            }
+            DiffLineType::Header => {
+                return self.diff_line(typ, selected)
+            }
+            DiffLineType::None => style,
+        };
+
+        self.apply_select(style, selected)
+    }
+
+    pub fn diff_line_number(&self, selected: bool) -> Style {
+        self.apply_select(
+            Style::default().fg(self.disabled_fg),
+            selected,
+        )
+    }
+
     pub fn text_danger(&self) -> Style {
         Style::default().fg(self.danger_fg)
     }
 
+    pub fn gauge(&self) -> Style {
+        Style::default().fg(self.selection_bg)
+    }
+
     pub fn commandbar(&self, enabled: bool, line: usize) -> Style {
         if enabled {
             Style::default().fg(self.command_fg)
@@ -209,6 +322,29 @@ impl Theme {
         )
     }
 
+    /// every other commit in the blame gutter gets a background
+    pub fn blame_band(
+        &self,
+        style: Style,
+        odd_band: bool,
+        selected: bool,
+    ) -> Style {
+        if odd_band && !selected {
+            style.bg(self.blame_band_bg)
+        } else {
+            style
+        }
+    }
+
+    pub fn search_match(&self, selected: bool) -> Style {
+        self.apply_select(
+            Style::default()
+                .fg(self.search_match_fg)
+                .modifier(Modifier::BOLD),
+            selected,
+        )
+    }
+
     fn save(&self) -> Result<()> {
         let theme_file = Self::get_theme_file()?;
         let mut file = File::create(theme_file)?;
@@ -265,6 +401,16 @@ impl Default for Theme {
             commit_time: Color::LightCyan,
             commit_author: Color::Green,
             danger_fg: Color::Red,
+            blame_band_bg: Color::DarkGray,
+            search_match_fg: Color::LightYellow,
+            head_fg: Color::LightCyan,
+            branch_fg: Color::LightGreen,
+            remote_branch_fg: Color::LightRed,
+            stash_fg: Color::LightMagenta,
+            diff_word_add: Color::LightGreen,
+            diff_word_delete: Color::LightRed,
+            diff_line_add_bg: Color::Indexed(22),
+            diff_line_delete_bg: Color::Indexed(52),
         }
     }
 }